@@ -0,0 +1,81 @@
+//! Actions taken during the session, for the activity panel and, when enabled
+//! in Settings, appended to `activity.log` in the data directory so the trail
+//! survives a restart.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config;
+use crate::systemd::ActionResult;
+
+const ACTIVITY_FILE: &str = "activity.log";
+
+#[derive(Debug, Clone)]
+pub struct ActionRecord {
+    pub at: SystemTime,
+    /// `None` for manager-wide actions such as `daemon-reload`.
+    pub unit: Option<String>,
+    /// What was run, e.g. `restart` or `stop + disable`.
+    pub action: &'static str,
+    pub result: ActionResult,
+}
+
+impl ActionRecord {
+    pub fn new(unit: Option<String>, action: &'static str, result: &ActionResult) -> ActionRecord {
+        ActionRecord {
+            at: SystemTime::now(),
+            unit,
+            action,
+            result: result.clone(),
+        }
+    }
+
+    /// "restart nginx.service", or just the action for manager-wide ones.
+    pub fn target(&self) -> String {
+        match &self.unit {
+            Some(unit) => format!("{} {}", self.action, unit),
+            None => self.action.to_string(),
+        }
+    }
+
+    /// One JSON object per line: time (Unix seconds), unit, action, ok, and
+    /// systemctl's message if there was one.
+    fn to_json_line(&self) -> String {
+        let time = self
+            .at
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        let (ok, message) = match &self.result {
+            Ok(warning) => (true, warning.clone()),
+            Err(e) => (false, Some(e.clone())),
+        };
+        serde_json::json!({
+            "time": time,
+            "unit": self.unit,
+            "action": self.action,
+            "ok": ok,
+            "message": message,
+        })
+        .to_string()
+    }
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    config::data_path(ACTIVITY_FILE)
+}
+
+pub fn append(path: &Path, record: &ActionRecord) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", record.to_json_line())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -0,0 +1,20 @@
+//! Desktop notifications for units that just failed, so the window can be
+//! left open as a passive monitor.
+
+use notify_rust::{Notification, Urgency};
+
+/// Shows one notification about units that entered the failed state.
+/// Best effort: without a notification daemon it is only logged.
+pub fn units_failed(summary: &str, units: &[String]) {
+    let result = Notification::new()
+        .appname("systemd-service-gui")
+        .summary(summary)
+        .body(&units.join("\n"))
+        .icon("dialog-error")
+        .sound_name("dialog-warning")
+        .urgency(Urgency::Critical)
+        .show();
+    if let Err(e) = result {
+        tracing::warn!(error = %e, "failed to show notification");
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: systemd-service-gui [OPTIONS]
+
+Options:
+  -f, --filter <TEXT>   Start with the service name filter set to TEXT
+      --failed-only     Start with only failed units shown
+      --check           Print a summary without opening the window; exit 1 if
+                        any unit (matching --filter, if given) has failed
+      --control-socket <PATH>
+                        Accept JSON commands on a Unix socket at PATH
+      --import <FILE>   Show units from FILE, saved with `systemctl list-units
+                        --output=json`, read-only instead of the live system
+      --serve <ADDR>    Serve the service list as JSON over HTTP at ADDR
+                        (e.g. 127.0.0.1:9400) instead of opening the window;
+                        needs a build with `--features serve`
+  -H, --host <HOST>     Manage the services of [USER@]HOST over SSH
+  -M, --machine <NAME>  Manage the services of the local container NAME
+  -h, --help            Print this help and exit
+";
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub name_filter: Option<String>,
+    pub failed_only: bool,
+    pub check: bool,
+    pub control_socket: Option<PathBuf>,
+    pub import: Option<PathBuf>,
+    pub serve: Option<String>,
+    pub host: Option<String>,
+    pub machine: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Invocation {
+    Run(Options),
+    Help,
+}
+
+pub fn parse_args<I>(args: I) -> Result<Invocation, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Invocation::Help),
+            "--failed-only" => options.failed_only = true,
+            "--check" => options.check = true,
+            "-f" | "--filter" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.name_filter = Some(value);
+            }
+            "--control-socket" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.control_socket = Some(PathBuf::from(value));
+            }
+            "--import" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.import = Some(PathBuf::from(value));
+            }
+            "--serve" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.serve = Some(value);
+            }
+            "-H" | "--host" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.host = Some(value);
+            }
+            "-M" | "--machine" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", arg))?;
+                options.machine = Some(value);
+            }
+            _ => {
+                if let Some(value) = arg.strip_prefix("--filter=") {
+                    options.name_filter = Some(value.to_string());
+                } else {
+                    return Err(format!("Unrecognized argument: {}", arg));
+                }
+            }
+        }
+    }
+
+    if options.host.is_some() && options.machine.is_some() {
+        return Err("--host and --machine can't be combined".to_string());
+    }
+
+    Ok(Invocation::Run(options))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Invocation, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn options(args: &[&str]) -> Options {
+        match parse(args) {
+            Ok(Invocation::Run(options)) => options,
+            other => panic!("{:?}: {:?}", args, other),
+        }
+    }
+
+    #[test]
+    fn options_are_parsed() {
+        let parsed = options(&["-f", "ssh", "--failed-only", "--import", "units.json"]);
+        assert_eq!(parsed.name_filter.as_deref(), Some("ssh"));
+        assert!(parsed.failed_only);
+        assert_eq!(parsed.import, Some(PathBuf::from("units.json")));
+        assert_eq!(options(&["--filter=nginx"]).name_filter.as_deref(), Some("nginx"));
+        assert_eq!(options(&["--filter="]).name_filter.as_deref(), Some(""));
+        assert_eq!(options(&["-H", "root@box"]).host.as_deref(), Some("root@box"));
+        assert_eq!(options(&["--machine", "web"]).machine.as_deref(), Some("web"));
+        assert!(matches!(parse(&["--check", "-h"]), Ok(Invocation::Help)));
+        assert!(matches!(parse(&["--help"]), Ok(Invocation::Help)));
+    }
+
+    #[test]
+    fn bad_arguments_are_reported() {
+        let cases = [
+            (&["--filter"][..], "Missing value for --filter"),
+            (&["--failed-only", "-M"][..], "Missing value for -M"),
+            (&["--verbose"][..], "Unrecognized argument: --verbose"),
+            (&["nginx"][..], "Unrecognized argument: nginx"),
+            (&["-H", "box", "-M", "web"][..], "--host and --machine can't be combined"),
+        ];
+        for (args, error) in cases {
+            assert_eq!(parse(args).unwrap_err(), error, "{:?}", args);
+        }
+    }
+}
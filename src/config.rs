@@ -0,0 +1,468 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+use crate::systemd::ExtraArgs;
+
+const APP_DIR: &str = "systemd-service-gui";
+
+/// Units whose stop or restart ends the session or cuts off the network;
+/// the starting point for `Config::protected_units`.
+const DEFAULT_PROTECTED: [&str; 3] =
+    ["systemd-logind.service", "dbus.service", "NetworkManager.service"];
+const CONFIG_FILE: &str = "config.json";
+
+pub const MIN_ZOOM: u16 = 50;
+pub const MAX_ZOOM: u16 = 200;
+pub const ZOOM_STEP: u16 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListColumn {
+    Name,
+    Description,
+    Load,
+    Active,
+    Sub,
+    UnitFile,
+    Preset,
+    LastLog,
+    Tasks,
+    Details,
+}
+
+impl ListColumn {
+    pub const ALL: [ListColumn; 10] = [
+        ListColumn::Name,
+        ListColumn::Description,
+        ListColumn::Load,
+        ListColumn::Active,
+        ListColumn::Sub,
+        ListColumn::UnitFile,
+        ListColumn::Preset,
+        ListColumn::LastLog,
+        ListColumn::Tasks,
+        ListColumn::Details,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ListColumn::Name => t("column_name"),
+            ListColumn::Description => t("column_description"),
+            ListColumn::Load => t("column_load"),
+            ListColumn::Active => t("column_active"),
+            ListColumn::Sub => t("column_sub"),
+            ListColumn::UnitFile => t("column_unit_file"),
+            ListColumn::Preset => t("column_preset"),
+            ListColumn::LastLog => t("column_last_log"),
+            ListColumn::Tasks => t("column_tasks"),
+            ListColumn::Details => t("column_details"),
+        }
+    }
+
+    pub fn width(self) -> f32 {
+        match self {
+            ListColumn::Name => 250.0,
+            ListColumn::Description => 300.0,
+            ListColumn::Details => 260.0,
+            _ => 100.0,
+        }
+    }
+
+    fn visible_by_default(self) -> bool {
+        !matches!(
+            self,
+            ListColumn::Load | ListColumn::UnitFile | ListColumn::Preset | ListColumn::Details
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnConfig {
+    pub column: ListColumn,
+    pub visible: bool,
+}
+
+/// What the service list does with its scroll position after a refresh.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollOnRefresh {
+    #[default]
+    Preserve,
+    Top,
+}
+
+/// The state buttons above the list; `failed` is also `--failed-only`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusFilter {
+    Running,
+    Exited,
+    Dead,
+    Active,
+    Inactive,
+    Failed,
+}
+
+/// A user-defined filter chip, e.g. "web stack" for units matching
+/// `nginx|php|redis` that are active.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub name: String,
+    /// Name substrings separated by `|`; a unit matching any of them passes.
+    /// Empty matches every unit.
+    pub pattern: String,
+    pub status: Option<StatusFilter>,
+}
+
+impl SavedFilter {
+    pub fn matches_name(&self, unit: &str) -> bool {
+        matches_pattern(&self.pattern, unit)
+    }
+}
+
+/// Case-insensitive substring match against `|`-separated alternatives, as
+/// used by the name filter and saved filters. An empty pattern matches.
+pub fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let text = text.to_lowercase();
+    let mut alternatives = pattern
+        .split('|')
+        .map(str::trim)
+        .filter(|alternative| !alternative.is_empty())
+        .peekable();
+    alternatives.peek().is_none()
+        || alternatives.any(|alternative| text.contains(&alternative.to_lowercase()))
+}
+
+/// The state a profile brings its units to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileState {
+    #[default]
+    Started,
+    Stopped,
+}
+
+impl ProfileState {
+    pub const ALL: [ProfileState; 2] = [ProfileState::Started, ProfileState::Stopped];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProfileState::Started => t("profile_started"),
+            ProfileState::Stopped => t("profile_stopped"),
+        }
+    }
+
+    /// The systemctl verb that gets a unit there.
+    pub fn verb(self) -> &'static str {
+        match self {
+            ProfileState::Started => "start",
+            ProfileState::Stopped => "stop",
+        }
+    }
+}
+
+/// A named set of units run together, e.g. "dev" starting postgres, redis
+/// and the app, or "off" stopping them again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub units: Vec<String>,
+    pub state: ProfileState,
+}
+
+/// A user-defined entry in the row action menu, e.g. "Health check" running
+/// `/usr/local/bin/check {unit}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomAction {
+    pub name: String,
+    /// Program and arguments, with `{unit}` standing for the unit's name.
+    /// Run without a shell; see `systemd::custom_command`.
+    pub command: String,
+}
+
+/// Which fields the name filter searches.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterScope {
+    #[default]
+    Name,
+    NameDescription,
+    /// Name, description and the unit file path.
+    All,
+}
+
+impl FilterScope {
+    pub const ALL: [FilterScope; 3] = [
+        FilterScope::Name,
+        FilterScope::NameDescription,
+        FilterScope::All,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FilterScope::Name => t("filter_scope_name"),
+            FilterScope::NameDescription => t("filter_scope_description"),
+            FilterScope::All => t("filter_scope_all"),
+        }
+    }
+}
+
+/// How unit names are shown in the list. systemctl is always given the
+/// raw name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NameStyle {
+    /// Exactly as systemd reports it, e.g. `foo\x2dbar@tty1.service`.
+    #[default]
+    Raw,
+    /// With `\xNN` escapes decoded: `foo-bar@tty1.service`.
+    Unescaped,
+    /// Decoded and without the `.service` suffix: `foo-bar@tty1`.
+    Short,
+}
+
+impl NameStyle {
+    pub const ALL: [NameStyle; 3] = [NameStyle::Raw, NameStyle::Unescaped, NameStyle::Short];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NameStyle::Raw => t("name_style_raw"),
+            NameStyle::Unescaped => t("name_style_unescaped"),
+            NameStyle::Short => t("name_style_short"),
+        }
+    }
+}
+
+/// Last known window size and position, in logical pixels. Position is not
+/// reported on every platform (e.g. Wayland), so it may be missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub width: u32,
+    pub height: u32,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub columns: Vec<ColumnConfig>,
+    pub window: Option<WindowGeometry>,
+    /// Accent for primary buttons as sRGB bytes; `None` keeps the theme's own.
+    pub accent: Option<[u8; 3]>,
+    /// Built-in iced theme by name (e.g. "Nord"); `None` is the default.
+    pub theme: Option<String>,
+    /// UI language code (e.g. "de"); `None` follows `LANG`/`LC_MESSAGES`.
+    pub language: Option<String>,
+    /// High-contrast theme with larger, bold text and outlined controls.
+    pub high_contrast: bool,
+    /// UI zoom as a percentage, adjusted with Ctrl+= / Ctrl+- or in Settings.
+    pub zoom_percent: u16,
+    pub scroll_on_refresh: ScrollOnRefresh,
+    pub filter_scope: FilterScope,
+    pub name_style: NameStyle,
+    /// Interval for polling the shown units' states without a full reload;
+    /// 0 turns polling off.
+    pub state_poll_seconds: u64,
+    /// Filter chips shown under the status filters.
+    pub saved_filters: Vec<SavedFilter>,
+    pub profiles: Vec<Profile>,
+    pub custom_actions: Vec<CustomAction>,
+    /// Units pinned to the top of the list, by name.
+    pub pinned: BTreeSet<String>,
+    /// Units left out of the list ("Hide this unit"), by name.
+    pub hidden: BTreeSet<String>,
+    /// Extra flags for systemctl actions, e.g. `--now` for enable/disable.
+    pub systemctl_args: ExtraArgs,
+    /// Also append every action to `activity.log` in the data directory.
+    pub log_activity: bool,
+    /// Desktop notification when a refresh finds a unit newly failed.
+    pub notify_failures: bool,
+    /// How long "Restart and wait" waits for the unit to become active.
+    pub restart_wait_seconds: u64,
+    /// Ask before every stop and restart.
+    pub confirm_stop_restart: bool,
+    /// Units that ask before a stop or restart even with
+    /// `confirm_stop_restart` off, by name.
+    pub protected_units: BTreeSet<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            columns: ListColumn::ALL
+                .iter()
+                .map(|&column| ColumnConfig {
+                    column,
+                    visible: column.visible_by_default(),
+                })
+                .collect(),
+            window: None,
+            accent: None,
+            theme: None,
+            language: None,
+            high_contrast: false,
+            zoom_percent: 100,
+            scroll_on_refresh: ScrollOnRefresh::default(),
+            filter_scope: FilterScope::default(),
+            name_style: NameStyle::default(),
+            state_poll_seconds: 0,
+            saved_filters: Vec::new(),
+            profiles: Vec::new(),
+            custom_actions: Vec::new(),
+            pinned: BTreeSet::new(),
+            hidden: BTreeSet::new(),
+            systemctl_args: ExtraArgs::default(),
+            log_activity: false,
+            notify_failures: false,
+            restart_wait_seconds: 30,
+            confirm_stop_restart: true,
+            protected_units: DEFAULT_PROTECTED.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file, falling back to defaults when it is missing or
+    /// unreadable so a bad file never prevents the app from starting.
+    pub fn load() -> Config {
+        let mut config = config_path()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|bytes| serde_json::from_slice::<Config>(&bytes).ok())
+            .unwrap_or_default();
+        config.normalize();
+        config.set_zoom(config.zoom_percent as i32);
+        config
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = config_path().ok_or_else(|| "No config directory available".to_string())?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn move_column(&mut self, column: ListColumn, offset: isize) {
+        let Some(from) = self.columns.iter().position(|c| c.column == column) else {
+            return;
+        };
+        let to = from as isize + offset;
+        if to >= 0 && (to as usize) < self.columns.len() {
+            self.columns.swap(from, to as usize);
+        }
+    }
+
+    pub fn set_column_visible(&mut self, column: ListColumn, visible: bool) {
+        if let Some(entry) = self.columns.iter_mut().find(|c| c.column == column) {
+            entry.visible = visible;
+        }
+    }
+
+    pub fn toggle_pinned(&mut self, name: &str) {
+        if !self.pinned.remove(name) {
+            self.pinned.insert(name.to_string());
+        }
+    }
+
+    /// Adds the filter, replacing a saved one of the same name.
+    pub fn save_filter(&mut self, filter: SavedFilter) {
+        match self.saved_filters.iter_mut().find(|f| f.name == filter.name) {
+            Some(existing) => *existing = filter,
+            None => self.saved_filters.push(filter),
+        }
+    }
+
+    /// Adds the profile, replacing a saved one of the same name.
+    pub fn save_profile(&mut self, profile: Profile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    /// Adds the custom action, replacing a saved one of the same name.
+    pub fn save_custom_action(&mut self, action: CustomAction) {
+        match self.custom_actions.iter_mut().find(|a| a.name == action.name) {
+            Some(existing) => *existing = action,
+            None => self.custom_actions.push(action),
+        }
+    }
+
+    /// Whether `name` is on the protected list; an entry without a suffix
+    /// such as "dbus" stands for its `.service` unit.
+    pub fn is_protected(&self, name: &str) -> bool {
+        self.protected_units.contains(name)
+            || name
+                .strip_suffix(".service")
+                .is_some_and(|base| self.protected_units.contains(base))
+    }
+
+    pub fn set_zoom(&mut self, percent: i32) {
+        self.zoom_percent = percent.clamp(MIN_ZOOM as i32, MAX_ZOOM as i32) as u16;
+    }
+
+    /// Drops duplicate columns and appends any the file doesn't mention yet,
+    /// e.g. columns added in a newer version. Also drops systemctl flags that
+    /// aren't on the allowed list.
+    fn normalize(&mut self) {
+        self.systemctl_args.retain_allowed();
+
+        let mut seen = Vec::new();
+        self.columns.retain(|c| {
+            let first = !seen.contains(&c.column);
+            seen.push(c.column);
+            first
+        });
+
+        for column in ListColumn::ALL {
+            if !seen.contains(&column) {
+                self.columns.push(ColumnConfig {
+                    column,
+                    visible: column.visible_by_default(),
+                });
+            }
+        }
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config").map(|dir| dir.join(APP_DIR).join(CONFIG_FILE))
+}
+
+/// Path of `file` in the app's data directory (`$XDG_DATA_HOME/systemd-service-gui`).
+pub fn data_path(file: &str) -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", ".local/share").map(|dir| dir.join(APP_DIR).join(file))
+}
+
+fn xdg_dir(var: &str, home_fallback: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(home_fallback)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protected_entries_without_suffix_match_the_service() {
+        let mut config = Config::default();
+        assert!(config.is_protected("systemd-logind.service"));
+        assert!(!config.is_protected("systemd-logind"));
+
+        config.protected_units.insert("sshd".to_string());
+        assert!(config.is_protected("sshd.service"));
+        assert!(config.is_protected("sshd"));
+        assert!(!config.is_protected("sshd.socket"));
+        assert!(!config.is_protected("sshd-keygen.service"));
+    }
+}
@@ -0,0 +1,96 @@
+//! Optional local control socket (`--control-socket <PATH>`).
+//!
+//! Clients send one JSON object per line, e.g. `{"action":"refresh"}` or
+//! `{"action":"filter","value":"nginx"}`, and get `{"ok":true}` or
+//! `{"ok":false,"error":"..."}` back for each line.
+
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use iced::futures::channel::mpsc;
+use iced::futures::SinkExt;
+use iced::{subscription, Subscription};
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum ControlCommand {
+    Refresh,
+    Filter { value: String },
+}
+
+/// Listens on `path` for the lifetime of the subscription. Setup failures are
+/// reported once as an `Err` and the socket stays closed.
+pub fn listen(path: PathBuf) -> Subscription<Result<ControlCommand, String>> {
+    subscription::channel(path.clone(), 16, move |mut output| async move {
+        match bind(&path) {
+            Ok(listener) => loop {
+                if let Ok((stream, _)) = listener.accept().await {
+                    tokio::spawn(serve(stream, output.clone()));
+                }
+            },
+            Err(e) => {
+                let _ = output.send(Err(e)).await;
+                std::future::pending().await
+            }
+        }
+    })
+}
+
+fn bind(path: &PathBuf) -> Result<UnixListener, String> {
+    // Replace a socket left behind by a previous run, but never clobber
+    // anything that isn't a socket.
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(format!(
+                "Refusing to replace {}: not a socket",
+                path.display()
+            ));
+        }
+        std::fs::remove_file(path)
+            .map_err(|e| format!("Failed to remove stale socket {}: {}", path.display(), e))?;
+    }
+
+    let listener = UnixListener::bind(path)
+        .map_err(|e| format!("Failed to bind control socket {}: {}", path.display(), e))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .map_err(|e| format!("Failed to restrict control socket {}: {}", path.display(), e))?;
+
+    Ok(listener)
+}
+
+/// Removes the socket file on exit. Like `bind`, leaves anything that isn't
+/// a socket alone.
+pub fn remove_socket(path: &Path) {
+    let is_socket = std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket());
+    if is_socket {
+        if let Err(e) = std::fs::remove_file(path) {
+            tracing::warn!(path = %path.display(), error = %e, "failed to remove control socket");
+        }
+    }
+}
+
+async fn serve(stream: UnixStream, mut output: mpsc::Sender<Result<ControlCommand, String>>) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let reply = match serde_json::from_str::<ControlCommand>(&line) {
+            Ok(command) => match output.send(Ok(command)).await {
+                Ok(()) => serde_json::json!({ "ok": true }),
+                Err(_) => break,
+            },
+            Err(e) => serde_json::json!({ "ok": false, "error": e.to_string() }),
+        };
+
+        if writer.write_all(format!("{}\n", reply).as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Optional D-Bus backend, built with `--features dbus`. It answers the
+//! `systemctl` invocations that have a direct systemd Manager method (listing
+//! services, start/stop/restart, reset-failed, daemon-reload) without
+//! spawning a process, and hands everything else to the real `systemctl`.
+//!
+//! Replies are shaped like systemctl's own output so the parsing and error
+//! handling in `systemd.rs` apply unchanged.
+
+use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{ExitStatus, Output};
+use std::time::Instant;
+
+use serde_json::json;
+use zbus::blocking::{Connection, Proxy};
+use zbus::zvariant::OwnedObjectPath;
+
+use crate::systemd::{CommandRunner, SystemRunner};
+
+/// One entry of `ListUnits`: name, description, load, active and sub state,
+/// followed unit, object path, job id, job type and job path.
+type UnitRow = (
+    String,
+    String,
+    String,
+    String,
+    String,
+    String,
+    OwnedObjectPath,
+    u32,
+    String,
+    OwnedObjectPath,
+);
+
+/// Errors systemctl would resolve by asking a polkit agent for a password,
+/// which a plain D-Bus call can't do.
+const AUTHORIZATION_ERRORS: [&str; 2] = [
+    "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired",
+    "org.freedesktop.DBus.Error.AccessDenied",
+];
+
+pub struct DbusRunner {
+    manager: Proxy<'static>,
+}
+
+impl DbusRunner {
+    /// Connects to systemd on the system bus; `None` if it can't be reached,
+    /// in which case callers stay with the subprocess path.
+    pub fn connect() -> Option<DbusRunner> {
+        let connection = Connection::system()
+            .inspect_err(|e| tracing::debug!(error = %e, "no system bus, using systemctl"))
+            .ok()?;
+        let manager = Proxy::new(
+            &connection,
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+        )
+        .ok()?;
+        // Creating the proxy doesn't talk to systemd yet; make sure it answers.
+        manager
+            .get_property::<String>("Version")
+            .inspect_err(|e| tracing::debug!(error = %e, "systemd not on D-Bus, using systemctl"))
+            .ok()?;
+
+        Some(DbusRunner { manager })
+    }
+
+    /// The D-Bus equivalent of `systemctl <args>`, or `None` for invocations
+    /// it doesn't cover (including any extra flags).
+    fn call(&self, args: &[&str]) -> Option<zbus::Result<Vec<u8>>> {
+        let result = match args {
+            ["list-units", "--type=service", "--all", "--no-pager", "--output=json"] => {
+                self.list_services(None)
+            }
+            ["list-units", "--type=service", "--all", state, "--no-pager", "--output=json"] => {
+                self.list_services(Some(state.strip_prefix("--state=")?))
+            }
+            [verb, unit] if !unit.starts_with('-') => self.unit_call(verb, unit)?.map(|()| Vec::new()),
+            ["reset-failed"] => self.manager.call::<_, _, ()>("ResetFailed", &()).map(|()| Vec::new()),
+            ["daemon-reload"] => self.manager.call::<_, _, ()>("Reload", &()).map(|()| Vec::new()),
+            _ => return None,
+        };
+        Some(result)
+    }
+
+    /// `ListUnits` filtered to services, as the JSON `list-units --output=json`
+    /// prints; `ListUnitsFiltered` when only units in `state` are wanted.
+    fn list_services(&self, state: Option<&str>) -> zbus::Result<Vec<u8>> {
+        let units: Vec<UnitRow> = match state {
+            Some(state) => self.manager.call("ListUnitsFiltered", &(vec![state],))?,
+            None => self.manager.call("ListUnits", &())?,
+        };
+        let rows: Vec<_> = units
+            .into_iter()
+            .filter(|unit| unit.0.ends_with(".service"))
+            .map(|(unit, description, load, active, sub, ..)| {
+                json!({
+                    "unit": unit,
+                    "load": load,
+                    "active": active,
+                    "sub": sub,
+                    "description": description,
+                })
+            })
+            .collect();
+        Ok(serde_json::to_vec(&rows).unwrap_or_default())
+    }
+
+    /// Unlike systemctl, the job methods return once the job is queued rather
+    /// than when it finishes; a unit that fails to start shows up as failed on
+    /// the next refresh instead of as an error here.
+    fn unit_call(&self, verb: &str, unit: &str) -> Option<zbus::Result<()>> {
+        let method = match verb {
+            "start" => "StartUnit",
+            "stop" => "StopUnit",
+            "restart" => "RestartUnit",
+            "try-restart" => "TryRestartUnit",
+            "reload" => "ReloadUnit",
+            "reset-failed" => {
+                return Some(self.manager.call::<_, _, ()>("ResetFailedUnit", &(unit,)));
+            }
+            _ => return None,
+        };
+        Some(
+            self.manager
+                .call::<_, _, OwnedObjectPath>(method, &(unit, "replace"))
+                .map(drop),
+        )
+    }
+}
+
+impl CommandRunner for DbusRunner {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        if program == "systemctl" {
+            let started = Instant::now();
+            if let Some(result) = self.call(args) {
+                tracing::debug!(?args, ok = result.is_ok(), elapsed = ?started.elapsed(), "D-Bus call finished");
+                match result {
+                    Ok(stdout) => return Ok(output(0, stdout, String::new())),
+                    // Let systemctl retry it; it can ask a polkit agent.
+                    Err(zbus::Error::MethodError(name, _, _))
+                        if AUTHORIZATION_ERRORS.contains(&name.as_str()) => {}
+                    Err(e) => return Ok(output(exit_code(&e), Vec::new(), error_message(&e))),
+                }
+            }
+        }
+
+        SystemRunner.output(program, args)
+    }
+
+    fn stream(
+        &self,
+        program: &str,
+        args: &[&str],
+        line: &mut dyn FnMut(&str) -> bool,
+    ) -> io::Result<Output> {
+        SystemRunner.stream(program, args, line)
+    }
+}
+
+fn output(code: i32, stdout: Vec<u8>, stderr: String) -> Output {
+    Output {
+        status: ExitStatus::from_raw(code << 8),
+        stdout,
+        stderr: stderr.into_bytes(),
+    }
+}
+
+/// systemctl exits with 5 (LSB "not installed") for units that aren't loaded.
+fn exit_code(error: &zbus::Error) -> i32 {
+    match error {
+        zbus::Error::MethodError(name, _, _) if name.as_str().ends_with(".NoSuchUnit") => 5,
+        _ => 1,
+    }
+}
+
+fn error_message(error: &zbus::Error) -> String {
+    match error {
+        zbus::Error::MethodError(name, detail, _) => {
+            detail.clone().unwrap_or_else(|| name.to_string())
+        }
+        e => e.to_string(),
+    }
+}
@@ -0,0 +1,64 @@
+//! "Copy diagnostics": a Markdown block for bug reports with the app and
+//! systemd versions, the OS, the last error and failed `systemctl` command,
+//! and the config. Journals are deliberately left out.
+
+use std::fmt::Write;
+use std::fs;
+
+use crate::config::Config;
+use crate::identity::Identity;
+use crate::systemd::{last_failure, systemd_version};
+
+/// Gathers the report; runs `systemctl --version`, so call it off the UI thread.
+pub fn collect(config: &Config, identity: Option<&Identity>, error: Option<&str>) -> String {
+    let mut report = String::from("### systemd-service-gui diagnostics\n\n");
+    let _ = writeln!(report, "- App: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "- Features: {}", features());
+    let _ = writeln!(report, "- OS: {}", os_release().unwrap_or_else(|| "unknown".to_string()));
+    let _ = writeln!(
+        report,
+        "- systemd: {}",
+        systemd_version().unwrap_or_else(|e| format!("unknown ({})", e))
+    );
+    if let Some(identity) = identity {
+        let _ = writeln!(report, "- Running as: {} (uid {})", identity.name, identity.uid);
+    }
+
+    if let Some(error) = error {
+        let _ = write!(report, "\n#### Error\n\n```\n{}\n```\n", error.trim_end());
+    }
+
+    if let Some(failure) = last_failure() {
+        let _ = write!(
+            report,
+            "\n#### Last failed command\n\n`{}` exited with {}\n\n```\n{}\n```\n",
+            failure.command,
+            failure.status,
+            failure.stderr.trim_end()
+        );
+    }
+
+    let config = serde_json::to_string_pretty(config).unwrap_or_default();
+    let _ = write!(report, "\n#### Config\n\n```json\n{}\n```\n", config);
+    report
+}
+
+fn features() -> &'static str {
+    match (cfg!(feature = "dbus"), cfg!(feature = "serve")) {
+        (true, true) => "dbus, serve",
+        (true, false) => "dbus",
+        (false, true) => "serve",
+        (false, false) => "none",
+    }
+}
+
+/// `PRETTY_NAME` from os-release(5).
+fn os_release() -> Option<String> {
+    let release = fs::read_to_string("/etc/os-release")
+        .or_else(|_| fs::read_to_string("/usr/lib/os-release"))
+        .ok()?;
+    release.lines().find_map(|line| {
+        let value = line.strip_prefix("PRETTY_NAME=")?;
+        Some(value.trim_matches('"').to_string())
+    })
+}
@@ -0,0 +1,36 @@
+//! Copying units' unit files and drop-ins into a directory, to back up local
+//! customizations before reinstalling or moving to another machine.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Outcome of `export_files`, file by file.
+#[derive(Debug, Clone, Default)]
+pub struct ExportReport {
+    /// Where each file ended up.
+    pub copied: Vec<PathBuf>,
+    /// Source files that couldn't be read or written, with the reason.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Copies each of `paths` into `dir` under its full path, so
+/// `/etc/systemd/system/nginx.service.d/override.conf` becomes
+/// `<dir>/etc/systemd/system/nginx.service.d/override.conf`. Keeping the
+/// layout means drop-ins with the same name in different directories don't
+/// collide, and the bundle can be copied back over `/`. A file that can't be
+/// copied (often for lack of permission) doesn't stop the rest.
+pub fn export_files(paths: &[PathBuf], dir: &Path) -> ExportReport {
+    let mut report = ExportReport::default();
+    for path in paths {
+        let target = dir.join(path.strip_prefix("/").unwrap_or(path));
+        let copied = target
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::copy(path, &target));
+        match copied {
+            Ok(_) => report.copied.push(target),
+            Err(e) => report.failed.push((path.clone(), e.to_string())),
+        }
+    }
+    report
+}
@@ -0,0 +1,1189 @@
+//! Minimal UI string lookup. Strings are keyed by a short identifier and
+//! looked up in the active locale, falling back to English and then to the key
+//! itself. To add a language, add a table below and an entry to `LOCALES`.
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub struct Locale {
+    pub code: &'static str,
+    pub name: &'static str,
+    strings: &'static [(&'static str, &'static str)],
+}
+
+pub const LOCALES: [Locale; 3] = [
+    Locale {
+        code: "en",
+        name: "English",
+        strings: EN,
+    },
+    Locale {
+        code: "de",
+        name: "Deutsch",
+        strings: DE,
+    },
+    Locale {
+        code: "fr",
+        name: "Français",
+        strings: FR,
+    },
+];
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+/// Selects the locale from `configured` (a code such as "de"), or from the
+/// environment when it is `None`. Unknown languages fall back to English.
+pub fn set_locale(configured: Option<&str>) {
+    let code = configured.map(str::to_string).or_else(env_language);
+    let index = code
+        .and_then(|code| LOCALES.iter().position(|locale| locale.code == code))
+        .unwrap_or(0);
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+pub fn t(key: &'static str) -> &'static str {
+    lookup(&LOCALES[CURRENT.load(Ordering::Relaxed)], key)
+        .or_else(|| lookup(&LOCALES[0], key))
+        .unwrap_or(key)
+}
+
+/// Like `t`, filling each `{}` in the string with the next argument.
+pub fn t_args(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = t(key).split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+fn lookup(locale: &Locale, key: &str) -> Option<&'static str> {
+    locale
+        .strings
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|&(_, value)| value)
+}
+
+/// Language code from `LC_ALL`, `LC_MESSAGES` or `LANG`, in that order of
+/// precedence, e.g. "de" for `de_DE.UTF-8`.
+fn env_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            value
+                .split(['_', '.', '@'])
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase()
+        })
+}
+
+const EN: &[(&str, &str)] = &[
+    ("refresh", "Refresh"),
+    ("running_as_root", "running as root"),
+    ("running_as_user", "running as {}"),
+    (
+        "escalation_hint",
+        "Not running as root: starting, stopping, or editing system units needs polkit \
+         authorization (you may be prompted via pkexec) and can fail without it.",
+    ),
+    ("audit", "Audit"),
+    ("settings", "Settings"),
+    ("save_snapshot", "Save snapshot"),
+    ("compare_snapshot", "Compare with snapshot"),
+    ("reset_failed", "Reset failed"),
+    ("daemon_reload", "Daemon reload"),
+    ("daemon_reexec", "Daemon reexec"),
+    ("reload_and_refresh", "Run daemon-reload and refresh"),
+    (
+        "not_found_hint",
+        "systemd has no unit file loaded for this name. If you just created it, systemd hasn't \
+         re-read unit files yet.",
+    ),
+    (
+        "confirm_reexec",
+        "Re-execute the systemd manager? Its state is serialized and restored, but this is more \
+         disruptive than a reload and mainly needed after upgrading systemd.",
+    ),
+    ("next_failed", "Next failed ({})"),
+    ("filter_placeholder", "Filter services by name..."),
+    ("status", "Status:"),
+    ("location", "Location:"),
+    ("has_overrides", "Has overrides"),
+    ("watch", "Watch"),
+    ("stop_watching", "Stop watching"),
+    ("watch_file", "Watch unit file"),
+    ("stop_watching_file", "Stop watching file"),
+    (
+        "confirm_reload_restart",
+        "The unit file of {} changed. Reload the systemd configuration and restart it?",
+    ),
+    ("reload_and_restart", "Reload & restart"),
+    ("show_logs", "Show logs"),
+    ("hide_logs", "Hide logs"),
+    ("current_boot_only", "Current boot only"),
+    ("all_priorities", "all"),
+    (
+        "confirm_session_unit",
+        "{} runs your current session. Stopping it ends the session, including this window. \
+         Type the unit name to confirm:",
+    ),
+    ("activity", "Activity ({})"),
+    ("activity_title", "Actions this session"),
+    ("no_activity", "No actions taken yet."),
+    ("activity_ok", "{}: {}"),
+    ("activity_warning", "{}: {} (warning: {})"),
+    ("activity_failed", "{}: {} failed: {}"),
+    ("activity_log", "Activity log"),
+    ("log_activity", "Also write actions to a log file"),
+    ("log_activity_hint", "Appended as JSON lines to {}"),
+    ("restart_matching", "Restart {} matching"),
+    ("confirm_restart_matching", "This will restart {} units: {}"),
+    ("confirm_stop_profile", "Profile {} will stop: {}"),
+    (
+        "confirm_stop_profile_session",
+        "Profile {} will stop: {}. {} runs your current session; stopping it ends the \
+         session, including this window. Type the profile name to confirm:",
+    ),
+    ("failed_exit_code", "Exited with status {}"),
+    ("failed_signal", "Killed by signal {}"),
+    ("failed_timeout", "Timed out"),
+    ("failed_start_limit", "Restarted too often (start limit hit)"),
+    ("failed_unknown", "Failed"),
+    ("failed_result", "Failed: {}"),
+    ("failed_restarts", "restarted {} times"),
+    ("failed_last_error", "last error: {}"),
+    ("filter_scope", "Name filter searches"),
+    ("filter_scope_name", "Unit name only"),
+    ("filter_scope_description", "Name and description"),
+    ("filter_scope_all", "Name, description and unit file path"),
+    ("copy_diagnostics", "Copy diagnostics"),
+    ("diagnostics_copied", "Diagnostics copied to the clipboard"),
+    ("bug_reports", "Bug reports"),
+    (
+        "bug_reports_hint",
+        "Copies the app and systemd versions, the OS, the last error and failed systemctl \
+         command, and your settings, to paste into a bug report. No journal contents are included.",
+    ),
+    ("saved_filters", "Saved:"),
+    ("filter_name_placeholder", "Name for these filters"),
+    ("save_filter", "Save filter"),
+    ("palette_placeholder", "Type an action or unit, e.g. \"restart nginx\""),
+    ("palette_no_match", "Nothing matches"),
+    ("palette_filter", "Filter {}"),
+    ("palette_select", "Show {}"),
+    ("skipped_condition", "Skipped: condition not met"),
+    ("skipped_assert", "Skipped: assertion failed"),
+    ("hide", "Hide"),
+    ("unhide", "Unhide"),
+    ("hidden_count", "{} hidden"),
+    ("hidden_units", "Hidden units"),
+    (
+        "hidden_units_empty",
+        "No units are hidden. Use \"Hide\" on a row to leave a unit out of the list.",
+    ),
+    ("confirm_stop_dependents", "Stopping {} will also stop: {}"),
+    ("confirm_stop", "Stop {}?"),
+    ("confirm_restart", "Restart {}?"),
+    ("confirmations", "Confirmations"),
+    ("confirm_stop_restart", "Ask before stopping or restarting a unit"),
+    (
+        "protected_units_hint",
+        "Protected units always ask first, even with the option above off. A name \
+         without a suffix stands for its .service unit.",
+    ),
+    ("protected_unit_placeholder", "Unit name, e.g. sshd.service"),
+    ("protect_unit", "Protect"),
+    (
+        "confirm_stop_unchecked",
+        "Couldn't check which units depend on {} ({}). Stop it anyway?",
+    ),
+    ("show_properties", "Show properties"),
+    ("hide_properties", "Hide properties"),
+    ("show_environment", "Show environment"),
+    ("hide_environment", "Hide environment"),
+    ("reveal_secrets", "Reveal tokens, secrets and passwords"),
+    ("no_environment", "No Environment= or EnvironmentFile= set"),
+    ("env_overridden", "{} (overridden below)"),
+    ("env_file_unreadable", "Can't read {}: {}"),
+    ("property_filter_placeholder", "Filter properties, e.g. Exec"),
+    ("alerts", "Alerts"),
+    ("notify_failures", "Notify me when a unit fails"),
+    (
+        "notify_failures_hint",
+        "Shows a desktop notification when a refresh or state poll finds a unit that \
+         has just entered the failed state, even while the window is in the background.",
+    ),
+    ("alert_unit_failed", "{} failed"),
+    ("alert_units_failed", "{} units failed"),
+    ("theme", "Theme"),
+    ("theme_high_contrast_hint", "High contrast is on and takes precedence over the theme."),
+    ("log_search", "Log search"),
+    ("log_search_placeholder", "Text to find in the logs, e.g. connection refused"),
+    ("log_search_hint", "Searches the last {} journal entries of all services."),
+    ("log_search_no_match", "No service logged this recently."),
+    ("log_search_found", "{} lines from {} units"),
+    ("search", "Search"),
+    ("searching", "Searching..."),
+    ("reload_all", "Reload all"),
+    (
+        "confirm_reload_all",
+        "This runs daemon-reload and then reloads {} active units that support it: {}",
+    ),
+    ("reload_all_done", "Reloaded {} units"),
+    ("slice", "Slice:"),
+    ("group_by_slice", "Group by slice"),
+    ("no_slice", "No slice"),
+    ("slice_cgroup", "Slice: {}, cgroup: {}"),
+    ("not_running", "not running"),
+    ("confirm_symlink_changes", "This changes the following symlinks for {}:\n{}"),
+    (
+        "confirm_symlinks_unchecked",
+        "Couldn't preview the symlinks changed for {} ({}). Continue anyway?",
+    ),
+    ("triggered_by", "Triggered by: {}"),
+    ("starts_on_demand", "Starts on demand, triggered by {}"),
+    ("export_shown", "Export {} shown"),
+    ("export_units", "Export the unit files and drop-ins of {} units"),
+    (
+        "export_hint",
+        "Files are copied under their full path, e.g. <dir>/etc/systemd/system/nginx.service.",
+    ),
+    ("export_dir_placeholder", "Target directory"),
+    ("export", "Export"),
+    ("exporting", "Exporting…"),
+    ("export_copied", "Copied {} files to {}"),
+    ("export_failed", "Couldn't copy {}: {}"),
+    ("name_style", "Unit names"),
+    ("name_style_raw", "As systemd reports them (foo\\x2dbar@tty1.service)"),
+    ("name_style_unescaped", "Unescaped (foo-bar@tty1.service)"),
+    ("name_style_short", "Unescaped, without .service (foo-bar@tty1)"),
+    ("restart_and_wait", "Restart and wait"),
+    ("waiting_until_active", "Waiting until active (up to {}s)…"),
+    ("came_up", "{} is active again after {}s"),
+    ("restart_wait", "Restart and wait gives up after"),
+    ("seconds", "{}s"),
+    ("set_limit", "Set limit:"),
+    ("limit_value_placeholder", "e.g. 512M, 20%, infinity"),
+    ("limit_runtime", "Until reboot only"),
+    ("apply", "Apply"),
+    ("also_known_as", "Also listed as: {}"),
+    ("boot_analysis", "Boot analysis"),
+    ("boot_analysis_summary", "Startup time of {} units during this boot, slowest first"),
+    ("profiles", "Profiles"),
+    (
+        "profiles_hint",
+        "A profile starts or stops a set of units with one click from the button row \
+         above the list. Units that are already in that state are left alone.",
+    ),
+    ("profile_started", "Started"),
+    ("profile_stopped", "Stopped"),
+    ("profile_name_placeholder", "Profile name"),
+    ("profile_units_placeholder", "Units, separated by spaces"),
+    ("save_profile", "Save profile"),
+    ("profile_button", "{} → {}"),
+    ("profile_running", "Running profile {} ({} units)…"),
+    ("profile_done", "Profile {}: {} changed, {} already there, {} failed"),
+    ("custom_actions", "Custom actions"),
+    (
+        "custom_actions_hint",
+        "Extra entries for every row's action menu. The command runs without a shell, \
+         with {unit} in its arguments replaced by the unit's name; its output is shown \
+         above the list.",
+    ),
+    ("custom_action_name_placeholder", "Name, e.g. Health check"),
+    ("custom_action_command_placeholder", "Command, e.g. /usr/local/bin/check {unit}"),
+    ("save_custom_action", "Save action"),
+    ("custom_action_output", "{} on {}"),
+    ("custom_action_no_output", "Finished without output"),
+    ("live_state", "Live: {}"),
+    ("user_services", "User units"),
+    (
+        "user_unit_details",
+        "Journal, dependencies and unit files are only shown for system units.",
+    ),
+    ("unit_file", "Unit file: {}"),
+    ("drop_in", "Drop-in: {}"),
+    ("documentation", "Documentation:"),
+    ("no_documentation", "No documentation listed"),
+    ("memory", "Memory"),
+    ("cpu", "CPU"),
+    ("dependents", "Units that depend on this:"),
+    ("no_dependents", "Nothing depends on this unit"),
+    ("offline_banner", "Offline view of {} (imported, read-only): actions are disabled"),
+    ("no_description", "(no description)"),
+    ("error", "Error: {}"),
+    ("warning", "Warning: {}"),
+    ("no_data_dir", "No data directory available"),
+    ("snapshot_saved", "Snapshot saved to {}"),
+    ("comparing_snapshot", "Comparing with snapshot taken {}"),
+    ("removed_since_snapshot", "No longer listed since snapshot: {}"),
+    ("change_new", "new since snapshot"),
+    ("change_failed", "now failed (was {})"),
+    ("change_active", "now active (was {})"),
+    ("change_state", "changed (was {})"),
+    ("loading", "Loading services..."),
+    ("no_services", "No services found or unable to load services."),
+    (
+        "no_services_hint",
+        "This usually means systemctl could not reach systemd or you lack permission to list \
+         units. Check that `systemctl list-units` works in a terminal as the same user.",
+    ),
+    ("try_again", "Try again"),
+    ("no_match", "No services match the current filters."),
+    ("clear_filters", "Clear filters"),
+    ("pinned", "Pinned"),
+    ("all_services", "All services"),
+    ("template_instances", "{} {} ({} instances)"),
+    ("with_failed", "{}, {} failed"),
+    ("start", "Start"),
+    ("stop", "Stop"),
+    ("restart", "Restart"),
+    ("try_restart", "Try restart"),
+    ("reload", "Reload"),
+    ("row_menu_hint", "Actions (or right-click the row)"),
+    ("stop_and_disable", "Stop & disable"),
+    ("enable_and_start", "Enable & start"),
+    ("enable", "Enable"),
+    ("disable", "Disable"),
+    ("override", "Override"),
+    ("logs", "Logs"),
+    ("logs_for", "Journal of {}"),
+    ("no_log_entries", "No journal entries."),
+    ("back", "Back"),
+    ("masked_label", "masked"),
+    ("legend", "Legend"),
+    ("legend_active", "Running or otherwise active"),
+    ("legend_failed", "Failed; select it to reset or restart"),
+    ("legend_transitional", "Starting, stopping or reloading"),
+    ("legend_inactive", "Not running"),
+    ("legend_static", "No [Install] section, can't be enabled or disabled"),
+    ("legend_masked", "Masked: linked to /dev/null, can't be started until unmasked"),
+    ("legend_enabled", "Shown for enabled units (started at boot)"),
+    ("legend_disabled", "Shown for disabled units"),
+    ("legend_highlight_sample", "highlighted row"),
+    ("legend_highlight", "State changed on the last refresh"),
+    ("static_label", "static"),
+    (
+        "static_tooltip",
+        "Static units have no [Install] section. They are always available to start, manually or \
+         as a dependency of other units, but can't be enabled or disabled.",
+    ),
+    ("command_copied", "Copied: {}"),
+    ("command_hint", "{} (right-click to copy)"),
+    ("override_for", "Override for {}"),
+    ("saving", "Saving…"),
+    ("save_and_reload", "Save and reload"),
+    ("cancel", "Cancel"),
+    ("close", "Close"),
+    ("unit_no_longer_present", "{} is no longer present after the last refresh"),
+    ("columns", "Columns"),
+    ("columns_hint", "Choose which columns are shown and in what order."),
+    ("accent_color", "Accent color"),
+    ("accent_hint", "Used for selected filters and action buttons."),
+    ("theme_default", "Theme default"),
+    ("custom", "Custom"),
+    ("preview", "Preview"),
+    ("language", "Language"),
+    ("language_auto", "Automatic"),
+    ("after_refresh", "After refresh"),
+    ("scroll_preserve", "Keep the scroll position"),
+    ("scroll_top", "Scroll to the top"),
+    ("state_poll", "Update states:"),
+    ("state_poll_hint", "Re-read the active/sub state of the shown units without reloading the whole list."),
+    ("off", "Off"),
+    ("every_seconds", "every {}s"),
+    ("auto_refresh", "Auto refresh:"),
+    ("seconds_short", "{}s"),
+    ("update_states", "Update states"),
+    ("zoom", "Zoom"),
+    (
+        "zoom_hint",
+        "Scale the whole interface. Ctrl+= and Ctrl+- zoom in and out, Ctrl+0 resets.",
+    ),
+    ("zoom_reset", "Reset"),
+    ("systemctl_options", "systemctl options"),
+    (
+        "systemctl_options_hint",
+        "Extra flags added to each action, e.g. --now to also start or stop a unit when \
+         enabling or disabling it. Hover an action button to see the resulting command.",
+    ),
+    ("accessibility", "Accessibility"),
+    ("high_contrast", "High contrast"),
+    (
+        "high_contrast_hint",
+        "Larger text, strong colors, and outlined controls. Bold text applies after a restart.",
+    ),
+    ("color_blue", "Blue"),
+    ("color_teal", "Teal"),
+    ("color_green", "Green"),
+    ("color_orange", "Orange"),
+    ("color_red", "Red"),
+    ("color_purple", "Purple"),
+    ("column_name", "Name"),
+    ("column_description", "Description"),
+    ("column_load", "Load"),
+    ("column_active", "Active"),
+    ("column_sub", "Sub"),
+    ("column_unit_file", "Unit file"),
+    ("column_preset", "Preset"),
+    ("column_last_log", "Last log"),
+    ("column_tasks", "Tasks"),
+    ("column_details", "Details"),
+    ("updated_ago", "updated {}"),
+    ("seconds_ago", "{}s ago"),
+    ("minutes_ago", "{}m ago"),
+    ("hours_ago", "{}h ago"),
+    ("days_ago", "{}d ago"),
+    ("older_than_days", "> {}d ago"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("refresh", "Aktualisieren"),
+    ("running_as_root", "läuft als root"),
+    ("running_as_user", "läuft als {}"),
+    (
+        "escalation_hint",
+        "Nicht als root gestartet: Starten, Stoppen oder Bearbeiten von System-Units erfordert \
+         eine polkit-Autorisierung (ggf. Abfrage über pkexec) und kann ohne sie fehlschlagen.",
+    ),
+    ("audit", "Prüfung"),
+    ("settings", "Einstellungen"),
+    ("save_snapshot", "Schnappschuss speichern"),
+    ("compare_snapshot", "Mit Schnappschuss vergleichen"),
+    ("reset_failed", "Fehler zurücksetzen"),
+    ("daemon_reload", "Daemon neu laden"),
+    ("daemon_reexec", "Daemon neu ausführen"),
+    ("reload_and_refresh", "daemon-reload ausführen und aktualisieren"),
+    (
+        "not_found_hint",
+        "systemd hat für diesen Namen keine Unit-Datei geladen. Wurde sie gerade erst angelegt, \
+         hat systemd die Unit-Dateien noch nicht neu eingelesen.",
+    ),
+    (
+        "confirm_reexec",
+        "Den systemd-Manager neu ausführen? Sein Zustand wird gesichert und wiederhergestellt, \
+         doch das ist einschneidender als ein Neuladen und meist nur nach einem systemd-Update \
+         nötig.",
+    ),
+    ("next_failed", "Nächster Fehler ({})"),
+    ("filter_placeholder", "Dienste nach Name filtern..."),
+    ("status", "Status:"),
+    ("location", "Ort:"),
+    ("has_overrides", "Mit Overrides"),
+    ("watch", "Beobachten"),
+    ("stop_watching", "Nicht mehr beobachten"),
+    ("watch_file", "Unit-Datei beobachten"),
+    ("stop_watching_file", "Datei nicht mehr beobachten"),
+    (
+        "confirm_reload_restart",
+        "Die Unit-Datei von {} wurde geändert. systemd-Konfiguration neu laden und die Unit \
+         neu starten?",
+    ),
+    ("reload_and_restart", "Neu laden & neu starten"),
+    ("show_logs", "Protokoll anzeigen"),
+    ("hide_logs", "Protokoll ausblenden"),
+    ("current_boot_only", "Nur aktueller Start"),
+    ("all_priorities", "alle"),
+    (
+        "confirm_session_unit",
+        "{} trägt Ihre aktuelle Sitzung. Wird sie gestoppt, endet die Sitzung samt diesem \
+         Fenster. Geben Sie zur Bestätigung den Unit-Namen ein:",
+    ),
+    ("activity", "Aktivität ({})"),
+    ("activity_title", "Aktionen in dieser Sitzung"),
+    ("no_activity", "Noch keine Aktionen."),
+    ("activity_ok", "{}: {}"),
+    ("activity_warning", "{}: {} (Warnung: {})"),
+    ("activity_failed", "{}: {} fehlgeschlagen: {}"),
+    ("activity_log", "Aktivitätsprotokoll"),
+    ("log_activity", "Aktionen auch in eine Protokolldatei schreiben"),
+    ("log_activity_hint", "Als JSON-Zeilen angehängt an {}"),
+    ("restart_matching", "{} passende neu starten"),
+    ("confirm_restart_matching", "Damit werden {} Units neu gestartet: {}"),
+    ("confirm_stop_profile", "Profil {} stoppt: {}"),
+    (
+        "confirm_stop_profile_session",
+        "Profil {} stoppt: {}. {} trägt Ihre aktuelle Sitzung; wird sie gestoppt, endet die \
+         Sitzung samt diesem Fenster. Geben Sie zur Bestätigung den Profilnamen ein:",
+    ),
+    ("failed_exit_code", "Beendet mit Status {}"),
+    ("failed_signal", "Durch Signal {} beendet"),
+    ("failed_timeout", "Zeitüberschreitung"),
+    ("failed_start_limit", "Zu oft neu gestartet (Startlimit erreicht)"),
+    ("failed_unknown", "Fehlgeschlagen"),
+    ("failed_result", "Fehlgeschlagen: {}"),
+    ("failed_restarts", "{}-mal neu gestartet"),
+    ("failed_last_error", "letzter Fehler: {}"),
+    ("filter_scope", "Namensfilter durchsucht"),
+    ("filter_scope_name", "Nur den Unit-Namen"),
+    ("filter_scope_description", "Name und Beschreibung"),
+    ("filter_scope_all", "Name, Beschreibung und Pfad der Unit-Datei"),
+    ("copy_diagnostics", "Diagnose kopieren"),
+    ("diagnostics_copied", "Diagnose in die Zwischenablage kopiert"),
+    ("bug_reports", "Fehlerberichte"),
+    (
+        "bug_reports_hint",
+        "Kopiert App- und systemd-Version, das Betriebssystem, den letzten Fehler und \
+         fehlgeschlagenen systemctl-Befehl sowie Ihre Einstellungen für einen Fehlerbericht. \
+         Journal-Inhalte sind nicht enthalten.",
+    ),
+    ("saved_filters", "Gespeichert:"),
+    ("filter_name_placeholder", "Name für diese Filter"),
+    ("save_filter", "Filter speichern"),
+    ("palette_placeholder", "Aktion oder Unit eingeben, z. B. \"Neustart nginx\""),
+    ("palette_no_match", "Keine Treffer"),
+    ("palette_filter", "Filter {}"),
+    ("palette_select", "{} anzeigen"),
+    ("skipped_condition", "Übersprungen: Bedingung nicht erfüllt"),
+    ("skipped_assert", "Übersprungen: Zusicherung fehlgeschlagen"),
+    ("hide", "Ausblenden"),
+    ("unhide", "Einblenden"),
+    ("hidden_count", "{} ausgeblendet"),
+    ("hidden_units", "Ausgeblendete Units"),
+    (
+        "hidden_units_empty",
+        "Keine Units ausgeblendet. Mit \"Ausblenden\" in einer Zeile wird eine Unit \
+         aus der Liste entfernt.",
+    ),
+    ("confirm_stop_dependents", "Wenn {} gestoppt wird, werden auch gestoppt: {}"),
+    ("confirm_stop", "{} stoppen?"),
+    ("confirm_restart", "{} neu starten?"),
+    ("confirmations", "Bestätigungen"),
+    ("confirm_stop_restart", "Vor dem Stoppen oder Neustarten einer Unit nachfragen"),
+    (
+        "protected_units_hint",
+        "Geschützte Units fragen immer nach, auch wenn die Option oben aus ist. Ein \
+         Name ohne Endung steht für seine .service-Unit.",
+    ),
+    ("protected_unit_placeholder", "Unit-Name, z. B. sshd.service"),
+    ("protect_unit", "Schützen"),
+    (
+        "confirm_stop_unchecked",
+        "Konnte nicht prüfen, welche Units von {} abhängen ({}). Trotzdem stoppen?",
+    ),
+    ("show_properties", "Eigenschaften anzeigen"),
+    ("hide_properties", "Eigenschaften ausblenden"),
+    ("show_environment", "Umgebung anzeigen"),
+    ("hide_environment", "Umgebung ausblenden"),
+    ("reveal_secrets", "Tokens, Secrets und Passwörter anzeigen"),
+    ("no_environment", "Kein Environment= oder EnvironmentFile= gesetzt"),
+    ("env_overridden", "{} (weiter unten überschrieben)"),
+    ("env_file_unreadable", "{} kann nicht gelesen werden: {}"),
+    ("property_filter_placeholder", "Eigenschaften filtern, z. B. Exec"),
+    ("alerts", "Benachrichtigungen"),
+    ("notify_failures", "Benachrichtigen, wenn eine Unit fehlschlägt"),
+    (
+        "notify_failures_hint",
+        "Zeigt eine Desktop-Benachrichtigung, wenn beim Aktualisieren eine Unit gerade \
+         fehlgeschlagen ist, auch wenn das Fenster im Hintergrund ist.",
+    ),
+    ("alert_unit_failed", "{} ist fehlgeschlagen"),
+    ("alert_units_failed", "{} Units sind fehlgeschlagen"),
+    ("theme", "Design"),
+    ("theme_high_contrast_hint", "Hoher Kontrast ist aktiv und hat Vorrang vor dem Design."),
+    ("log_search", "Log-Suche"),
+    ("log_search_placeholder", "Text in den Logs, z. B. connection refused"),
+    ("log_search_hint", "Durchsucht die letzten {} Journal-Einträge aller Dienste."),
+    ("log_search_no_match", "Kein Dienst hat das in letzter Zeit geloggt."),
+    ("log_search_found", "{} Zeilen von {} Units"),
+    ("search", "Suchen"),
+    ("searching", "Suche läuft..."),
+    ("reload_all", "Alle neu laden"),
+    (
+        "confirm_reload_all",
+        "Damit wird daemon-reload ausgeführt und {} aktive Units, die es unterstützen, \
+         werden neu geladen: {}",
+    ),
+    ("reload_all_done", "{} Units neu geladen"),
+    ("slice", "Slice:"),
+    ("group_by_slice", "Nach Slice gruppieren"),
+    ("no_slice", "Ohne Slice"),
+    ("slice_cgroup", "Slice: {}, Cgroup: {}"),
+    ("not_running", "läuft nicht"),
+    ("confirm_symlink_changes", "Dabei ändern sich folgende Symlinks für {}:\n{}"),
+    (
+        "confirm_symlinks_unchecked",
+        "Konnte die geänderten Symlinks für {} nicht ermitteln ({}). Trotzdem fortfahren?",
+    ),
+    ("triggered_by", "Ausgelöst durch: {}"),
+    ("starts_on_demand", "Startet bei Bedarf, ausgelöst durch {}"),
+    ("export_shown", "{} angezeigte exportieren"),
+    ("export_units", "Unit-Dateien und Drop-ins von {} Units exportieren"),
+    (
+        "export_hint",
+        "Dateien werden mit vollem Pfad kopiert, z. B. \
+         <Verzeichnis>/etc/systemd/system/nginx.service.",
+    ),
+    ("export_dir_placeholder", "Zielverzeichnis"),
+    ("export", "Exportieren"),
+    ("exporting", "Exportiere…"),
+    ("export_copied", "{} Dateien nach {} kopiert"),
+    ("export_failed", "Konnte {} nicht kopieren: {}"),
+    ("name_style", "Unit-Namen"),
+    ("name_style_raw", "Wie von systemd gemeldet (foo\\x2dbar@tty1.service)"),
+    ("name_style_unescaped", "Entschlüsselt (foo-bar@tty1.service)"),
+    ("name_style_short", "Entschlüsselt, ohne .service (foo-bar@tty1)"),
+    ("restart_and_wait", "Neu starten und warten"),
+    ("waiting_until_active", "Warte auf aktiv (bis zu {} s)…"),
+    ("came_up", "{} ist nach {} s wieder aktiv"),
+    ("restart_wait", "Neu starten und warten gibt auf nach"),
+    ("seconds", "{} s"),
+    ("set_limit", "Grenze setzen:"),
+    ("limit_value_placeholder", "z. B. 512M, 20%, infinity"),
+    ("limit_runtime", "Nur bis zum Neustart"),
+    ("apply", "Anwenden"),
+    ("also_known_as", "Auch gelistet als: {}"),
+    ("boot_analysis", "Boot-Analyse"),
+    ("boot_analysis_summary", "Startzeit von {} Units in diesem Boot, langsamste zuerst"),
+    ("profiles", "Profile"),
+    (
+        "profiles_hint",
+        "Ein Profil startet oder stoppt mehrere Units mit einem Klick in der Leiste \
+         über der Liste. Units, die schon in diesem Zustand sind, bleiben unverändert.",
+    ),
+    ("profile_started", "Gestartet"),
+    ("profile_stopped", "Gestoppt"),
+    ("profile_name_placeholder", "Profilname"),
+    ("profile_units_placeholder", "Units, durch Leerzeichen getrennt"),
+    ("save_profile", "Profil speichern"),
+    ("profile_button", "{} → {}"),
+    ("profile_running", "Profil {} wird ausgeführt ({} Units)…"),
+    ("profile_done", "Profil {}: {} geändert, {} unverändert, {} fehlgeschlagen"),
+    ("custom_actions", "Eigene Aktionen"),
+    (
+        "custom_actions_hint",
+        "Zusätzliche Einträge im Aktionsmenü jeder Zeile. Der Befehl läuft ohne Shell, \
+         {unit} in seinen Argumenten wird durch den Namen der Unit ersetzt; die Ausgabe \
+         erscheint über der Liste.",
+    ),
+    ("custom_action_name_placeholder", "Name, z. B. Health-Check"),
+    ("custom_action_command_placeholder", "Befehl, z. B. /usr/local/bin/check {unit}"),
+    ("save_custom_action", "Aktion speichern"),
+    ("custom_action_output", "{} für {}"),
+    ("custom_action_no_output", "Ohne Ausgabe beendet"),
+    ("live_state", "Aktuell: {}"),
+    ("user_services", "Benutzer-Units"),
+    (
+        "user_unit_details",
+        "Journal, Abhängigkeiten und Unit-Dateien werden nur für System-Units angezeigt.",
+    ),
+    ("unit_file", "Unit-Datei: {}"),
+    ("drop_in", "Drop-in: {}"),
+    ("documentation", "Dokumentation:"),
+    ("no_documentation", "Keine Dokumentation angegeben"),
+    ("memory", "Speicher"),
+    ("cpu", "CPU"),
+    ("dependents", "Units, die hiervon abhängen:"),
+    ("no_dependents", "Keine Unit hängt hiervon ab"),
+    ("offline_banner", "Offline-Ansicht von {} (importiert, nur lesend): Aktionen sind deaktiviert"),
+    ("no_description", "(keine Beschreibung)"),
+    ("error", "Fehler: {}"),
+    ("warning", "Warnung: {}"),
+    ("no_data_dir", "Kein Datenverzeichnis verfügbar"),
+    ("snapshot_saved", "Schnappschuss gespeichert unter {}"),
+    ("comparing_snapshot", "Vergleich mit Schnappschuss von {}"),
+    ("removed_since_snapshot", "Seit dem Schnappschuss verschwunden: {}"),
+    ("change_new", "neu seit Schnappschuss"),
+    ("change_failed", "jetzt fehlgeschlagen (war {})"),
+    ("change_active", "jetzt aktiv (war {})"),
+    ("change_state", "geändert (war {})"),
+    ("loading", "Dienste werden geladen..."),
+    ("no_services", "Keine Dienste gefunden oder Laden nicht möglich."),
+    (
+        "no_services_hint",
+        "Meist konnte systemctl systemd nicht erreichen oder es fehlt die Berechtigung, Units \
+         aufzulisten. Prüfen Sie, ob `systemctl list-units` im Terminal als derselbe Benutzer \
+         funktioniert.",
+    ),
+    ("try_again", "Erneut versuchen"),
+    ("no_match", "Keine Dienste entsprechen den aktuellen Filtern."),
+    ("clear_filters", "Filter zurücksetzen"),
+    ("pinned", "Angeheftet"),
+    ("all_services", "Alle Dienste"),
+    ("template_instances", "{} {} ({} Instanzen)"),
+    ("with_failed", "{}, {} fehlgeschlagen"),
+    ("start", "Starten"),
+    ("stop", "Stoppen"),
+    ("restart", "Neu starten"),
+    ("try_restart", "Neu starten, falls aktiv"),
+    ("reload", "Neu laden"),
+    ("row_menu_hint", "Aktionen (oder Rechtsklick auf die Zeile)"),
+    ("stop_and_disable", "Stoppen & deaktivieren"),
+    ("enable_and_start", "Aktivieren & starten"),
+    ("enable", "Aktivieren"),
+    ("disable", "Deaktivieren"),
+    ("override", "Überschreiben"),
+    ("logs", "Protokoll"),
+    ("logs_for", "Protokoll von {}"),
+    ("no_log_entries", "Keine Protokolleinträge."),
+    ("back", "Zurück"),
+    ("masked_label", "maskiert"),
+    ("legend", "Legende"),
+    ("legend_active", "Läuft oder ist anderweitig aktiv"),
+    ("legend_failed", "Fehlgeschlagen; auswählen zum Zurücksetzen oder Neustarten"),
+    ("legend_transitional", "Wird gestartet, gestoppt oder neu geladen"),
+    ("legend_inactive", "Läuft nicht"),
+    ("legend_static", "Kein [Install]-Abschnitt, nicht aktivierbar oder deaktivierbar"),
+    ("legend_masked", "Maskiert: auf /dev/null verlinkt, erst nach Demaskieren startbar"),
+    ("legend_enabled", "Bei aktivierten Units (beim Booten gestartet)"),
+    ("legend_disabled", "Bei deaktivierten Units"),
+    ("legend_highlight_sample", "hervorgehobene Zeile"),
+    ("legend_highlight", "Zustand hat sich beim letzten Aktualisieren geändert"),
+    ("static_label", "statisch"),
+    (
+        "static_tooltip",
+        "Statische Units haben keinen [Install]-Abschnitt. Sie lassen sich jederzeit starten, \
+         manuell oder als Abhängigkeit anderer Units, aber nicht aktivieren oder deaktivieren.",
+    ),
+    ("command_copied", "Kopiert: {}"),
+    ("command_hint", "{} (Rechtsklick zum Kopieren)"),
+    ("override_for", "Override für {}"),
+    ("saving", "Speichern…"),
+    ("save_and_reload", "Speichern und neu laden"),
+    ("cancel", "Abbrechen"),
+    ("close", "Schließen"),
+    ("unit_no_longer_present", "{} ist seit der letzten Aktualisierung nicht mehr vorhanden"),
+    ("columns", "Spalten"),
+    ("columns_hint", "Wählen Sie, welche Spalten in welcher Reihenfolge angezeigt werden."),
+    ("accent_color", "Akzentfarbe"),
+    ("accent_hint", "Für ausgewählte Filter und Aktionsschaltflächen."),
+    ("theme_default", "Standard des Themes"),
+    ("custom", "Benutzerdefiniert"),
+    ("preview", "Vorschau"),
+    ("language", "Sprache"),
+    ("language_auto", "Automatisch"),
+    ("after_refresh", "Nach dem Aktualisieren"),
+    ("scroll_preserve", "Scrollposition beibehalten"),
+    ("scroll_top", "Nach oben scrollen"),
+    ("state_poll", "Zustände aktualisieren:"),
+    ("state_poll_hint", "Liest den Zustand der angezeigten Units neu ein, ohne die ganze Liste neu zu laden."),
+    ("off", "Aus"),
+    ("every_seconds", "alle {} s"),
+    ("auto_refresh", "Automatisch aktualisieren:"),
+    ("seconds_short", "{} s"),
+    ("update_states", "Zustände aktualisieren"),
+    ("zoom", "Zoom"),
+    (
+        "zoom_hint",
+        "Skaliert die gesamte Oberfläche. Strg+= und Strg+- vergrößern und verkleinern, Strg+0 \
+         setzt zurück.",
+    ),
+    ("zoom_reset", "Zurücksetzen"),
+    ("systemctl_options", "systemctl-Optionen"),
+    (
+        "systemctl_options_hint",
+        "Zusätzliche Flags für jede Aktion, z. B. --now, um eine Unit beim Aktivieren oder \
+         Deaktivieren auch zu starten oder zu stoppen. Der Tooltip einer Aktion zeigt den \
+         resultierenden Befehl.",
+    ),
+    ("accessibility", "Barrierefreiheit"),
+    ("high_contrast", "Hoher Kontrast"),
+    (
+        "high_contrast_hint",
+        "Größere Schrift, kräftige Farben und umrandete Bedienelemente. Fettschrift gilt nach \
+         einem Neustart.",
+    ),
+    ("color_blue", "Blau"),
+    ("color_teal", "Petrol"),
+    ("color_green", "Grün"),
+    ("color_orange", "Orange"),
+    ("color_red", "Rot"),
+    ("color_purple", "Lila"),
+    ("column_name", "Name"),
+    ("column_description", "Beschreibung"),
+    ("column_load", "Geladen"),
+    ("column_active", "Aktiv"),
+    ("column_sub", "Unterzustand"),
+    ("column_unit_file", "Unit-Datei"),
+    ("column_preset", "Vorgabe"),
+    ("column_last_log", "Letztes Log"),
+    ("column_tasks", "Tasks"),
+    ("column_details", "Details"),
+    ("updated_ago", "aktualisiert {}"),
+    ("seconds_ago", "vor {} s"),
+    ("minutes_ago", "vor {} min"),
+    ("hours_ago", "vor {} h"),
+    ("days_ago", "vor {} T."),
+    ("older_than_days", "vor > {} T."),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("refresh", "Actualiser"),
+    ("running_as_root", "exécuté en root"),
+    ("running_as_user", "exécuté en tant que {}"),
+    (
+        "escalation_hint",
+        "Pas exécuté en root : démarrer, arrêter ou modifier des unités système nécessite une \
+         autorisation polkit (une demande via pkexec peut apparaître) et peut échouer sans elle.",
+    ),
+    ("audit", "Audit"),
+    ("settings", "Paramètres"),
+    ("save_snapshot", "Enregistrer un instantané"),
+    ("compare_snapshot", "Comparer à l'instantané"),
+    ("reset_failed", "Réinitialiser les échecs"),
+    ("daemon_reload", "Recharger le démon"),
+    ("daemon_reexec", "Ré-exécuter le démon"),
+    ("reload_and_refresh", "Lancer daemon-reload et actualiser"),
+    (
+        "not_found_hint",
+        "systemd n'a chargé aucun fichier d'unité pour ce nom. S'il vient d'être créé, systemd \
+         n'a pas encore relu les fichiers d'unité.",
+    ),
+    (
+        "confirm_reexec",
+        "Ré-exécuter le gestionnaire systemd ? Son état est sauvegardé puis restauré, mais c'est \
+         plus perturbant qu'un rechargement et surtout utile après une mise à jour de systemd.",
+    ),
+    ("next_failed", "Échec suivant ({})"),
+    ("filter_placeholder", "Filtrer les services par nom..."),
+    ("status", "État :"),
+    ("location", "Emplacement :"),
+    ("has_overrides", "Avec surcharges"),
+    ("watch", "Surveiller"),
+    ("stop_watching", "Arrêter la surveillance"),
+    ("watch_file", "Surveiller le fichier d'unité"),
+    ("stop_watching_file", "Arrêter de surveiller le fichier"),
+    (
+        "confirm_reload_restart",
+        "Le fichier d'unité de {} a changé. Recharger la configuration de systemd et \
+         redémarrer l'unité ?",
+    ),
+    ("reload_and_restart", "Recharger et redémarrer"),
+    ("show_logs", "Afficher le journal"),
+    ("hide_logs", "Masquer le journal"),
+    ("current_boot_only", "Démarrage actuel uniquement"),
+    ("all_priorities", "toutes"),
+    (
+        "confirm_session_unit",
+        "{} fait tourner votre session actuelle. L'arrêter met fin à la session, cette fenêtre \
+         comprise. Saisissez le nom de l'unité pour confirmer :",
+    ),
+    ("activity", "Activité ({})"),
+    ("activity_title", "Actions de cette session"),
+    ("no_activity", "Aucune action pour l'instant."),
+    ("activity_ok", "{} : {}"),
+    ("activity_warning", "{} : {} (avertissement : {})"),
+    ("activity_failed", "{} : échec de {} : {}"),
+    ("activity_log", "Journal d'activité"),
+    ("log_activity", "Écrire aussi les actions dans un fichier"),
+    ("log_activity_hint", "Ajoutées en lignes JSON à {}"),
+    ("restart_matching", "Redémarrer les {} correspondantes"),
+    ("confirm_restart_matching", "Cela va redémarrer {} unités : {}"),
+    ("confirm_stop_profile", "Le profil {} va arrêter : {}"),
+    (
+        "confirm_stop_profile_session",
+        "Le profil {} va arrêter : {}. {} fait tourner votre session actuelle ; l'arrêter met \
+         fin à la session, cette fenêtre comprise. Saisissez le nom du profil pour confirmer :",
+    ),
+    ("failed_exit_code", "Terminé avec le code {}"),
+    ("failed_signal", "Tué par le signal {}"),
+    ("failed_timeout", "Délai dépassé"),
+    ("failed_start_limit", "Redémarré trop souvent (limite de démarrage atteinte)"),
+    ("failed_unknown", "Échec"),
+    ("failed_result", "Échec : {}"),
+    ("failed_restarts", "redémarré {} fois"),
+    ("failed_last_error", "dernière erreur : {}"),
+    ("filter_scope", "Le filtre par nom cherche dans"),
+    ("filter_scope_name", "Le nom de l'unité uniquement"),
+    ("filter_scope_description", "Le nom et la description"),
+    ("filter_scope_all", "Le nom, la description et le chemin du fichier d'unité"),
+    ("copy_diagnostics", "Copier le diagnostic"),
+    ("diagnostics_copied", "Diagnostic copié dans le presse-papiers"),
+    ("bug_reports", "Rapports de bogue"),
+    (
+        "bug_reports_hint",
+        "Copie les versions de l'application et de systemd, le système, la dernière erreur et \
+         la dernière commande systemctl échouée, ainsi que vos réglages, pour un rapport de \
+         bogue. Le contenu du journal n'est pas inclus.",
+    ),
+    ("saved_filters", "Enregistrés :"),
+    ("filter_name_placeholder", "Nom pour ces filtres"),
+    ("save_filter", "Enregistrer le filtre"),
+    ("palette_placeholder", "Tapez une action ou une unité, p. ex. « redémarrer nginx »"),
+    ("palette_no_match", "Aucun résultat"),
+    ("palette_filter", "Filtrer {}"),
+    ("palette_select", "Afficher {}"),
+    ("skipped_condition", "Ignoré : condition non remplie"),
+    ("skipped_assert", "Ignoré : assertion échouée"),
+    ("hide", "Masquer"),
+    ("unhide", "Afficher"),
+    ("hidden_count", "{} masquées"),
+    ("hidden_units", "Unités masquées"),
+    (
+        "hidden_units_empty",
+        "Aucune unité masquée. « Masquer » sur une ligne retire une unité de la liste.",
+    ),
+    ("confirm_stop_dependents", "Arrêter {} arrêtera aussi : {}"),
+    ("confirm_stop", "Arrêter {} ?"),
+    ("confirm_restart", "Redémarrer {} ?"),
+    ("confirmations", "Confirmations"),
+    ("confirm_stop_restart", "Demander avant d'arrêter ou de redémarrer une unité"),
+    (
+        "protected_units_hint",
+        "Les unités protégées demandent toujours confirmation, même si l'option \
+         ci-dessus est désactivée. Un nom sans suffixe désigne son unité .service.",
+    ),
+    ("protected_unit_placeholder", "Nom d'unité, p. ex. sshd.service"),
+    ("protect_unit", "Protéger"),
+    (
+        "confirm_stop_unchecked",
+        "Impossible de vérifier quelles unités dépendent de {} ({}). L'arrêter quand même ?",
+    ),
+    ("show_properties", "Afficher les propriétés"),
+    ("hide_properties", "Masquer les propriétés"),
+    ("show_environment", "Afficher l'environnement"),
+    ("hide_environment", "Masquer l'environnement"),
+    ("reveal_secrets", "Afficher les jetons, secrets et mots de passe"),
+    ("no_environment", "Aucun Environment= ni EnvironmentFile= défini"),
+    ("env_overridden", "{} (remplacée plus bas)"),
+    ("env_file_unreadable", "Impossible de lire {} : {}"),
+    ("property_filter_placeholder", "Filtrer les propriétés, p. ex. Exec"),
+    ("alerts", "Alertes"),
+    ("notify_failures", "M'avertir quand une unité échoue"),
+    (
+        "notify_failures_hint",
+        "Affiche une notification de bureau quand une actualisation trouve une unité \
+         qui vient d'échouer, même si la fenêtre est en arrière-plan.",
+    ),
+    ("alert_unit_failed", "{} a échoué"),
+    ("alert_units_failed", "{} unités ont échoué"),
+    ("theme", "Thème"),
+    ("theme_high_contrast_hint", "Le contraste élevé est activé et prime sur le thème."),
+    ("log_search", "Recherche dans les journaux"),
+    ("log_search_placeholder", "Texte à chercher, p. ex. connection refused"),
+    ("log_search_hint", "Parcourt les {} dernières entrées du journal de tous les services."),
+    ("log_search_no_match", "Aucun service n'a journalisé cela récemment."),
+    ("log_search_found", "{} lignes de {} unités"),
+    ("search", "Rechercher"),
+    ("searching", "Recherche..."),
+    ("reload_all", "Tout recharger"),
+    (
+        "confirm_reload_all",
+        "Cela exécute daemon-reload puis recharge {} unités actives qui le permettent : {}",
+    ),
+    ("reload_all_done", "{} unités rechargées"),
+    ("slice", "Slice :"),
+    ("group_by_slice", "Grouper par slice"),
+    ("no_slice", "Sans slice"),
+    ("slice_cgroup", "Slice : {}, cgroup : {}"),
+    ("not_running", "non démarrée"),
+    ("confirm_symlink_changes", "Les liens symboliques suivants changent pour {} :\n{}"),
+    (
+        "confirm_symlinks_unchecked",
+        "Impossible de prévisualiser les liens symboliques modifiés pour {} ({}). \
+         Continuer quand même ?",
+    ),
+    ("triggered_by", "Déclenché par : {}"),
+    ("starts_on_demand", "Démarre à la demande, déclenché par {}"),
+    ("export_shown", "Exporter les {} affichées"),
+    ("export_units", "Exporter les fichiers d'unité et drop-ins de {} unités"),
+    (
+        "export_hint",
+        "Les fichiers sont copiés avec leur chemin complet, p. ex. \
+         <dossier>/etc/systemd/system/nginx.service.",
+    ),
+    ("export_dir_placeholder", "Dossier de destination"),
+    ("export", "Exporter"),
+    ("exporting", "Export en cours…"),
+    ("export_copied", "{} fichiers copiés dans {}"),
+    ("export_failed", "Impossible de copier {} : {}"),
+    ("name_style", "Noms des unités"),
+    ("name_style_raw", "Tels que systemd les donne (foo\\x2dbar@tty1.service)"),
+    ("name_style_unescaped", "Décodés (foo-bar@tty1.service)"),
+    ("name_style_short", "Décodés, sans .service (foo-bar@tty1)"),
+    ("restart_and_wait", "Redémarrer et attendre"),
+    ("waiting_until_active", "Attente de l'état actif (jusqu'à {} s)…"),
+    ("came_up", "{} est de nouveau actif après {} s"),
+    ("restart_wait", "Redémarrer et attendre abandonne après"),
+    ("seconds", "{} s"),
+    ("set_limit", "Définir une limite :"),
+    ("limit_value_placeholder", "p. ex. 512M, 20%, infinity"),
+    ("limit_runtime", "Jusqu'au redémarrage seulement"),
+    ("apply", "Appliquer"),
+    ("also_known_as", "Aussi listée sous : {}"),
+    ("boot_analysis", "Analyse du démarrage"),
+    (
+        "boot_analysis_summary",
+        "Durée de démarrage de {} unités pendant ce boot, la plus lente d'abord",
+    ),
+    ("profiles", "Profils"),
+    (
+        "profiles_hint",
+        "Un profil démarre ou arrête un ensemble d'unités en un clic depuis la barre \
+         au-dessus de la liste. Les unités déjà dans cet état ne sont pas touchées.",
+    ),
+    ("profile_started", "Démarré"),
+    ("profile_stopped", "Arrêté"),
+    ("profile_name_placeholder", "Nom du profil"),
+    ("profile_units_placeholder", "Unités, séparées par des espaces"),
+    ("save_profile", "Enregistrer le profil"),
+    ("profile_button", "{} → {}"),
+    ("profile_running", "Exécution du profil {} ({} unités)…"),
+    ("profile_done", "Profil {} : {} modifiées, {} déjà dans l'état, {} en échec"),
+    ("custom_actions", "Actions personnalisées"),
+    (
+        "custom_actions_hint",
+        "Entrées supplémentaires du menu d'actions de chaque ligne. La commande est \
+         lancée sans shell, {unit} dans ses arguments étant remplacé par le nom de l'unité ; \
+         sa sortie s'affiche au-dessus de la liste.",
+    ),
+    ("custom_action_name_placeholder", "Nom, p. ex. Vérification"),
+    ("custom_action_command_placeholder", "Commande, p. ex. /usr/local/bin/check {unit}"),
+    ("save_custom_action", "Enregistrer l'action"),
+    ("custom_action_output", "{} sur {}"),
+    ("custom_action_no_output", "Terminé sans sortie"),
+    ("live_state", "En direct : {}"),
+    ("user_services", "Unités utilisateur"),
+    (
+        "user_unit_details",
+        "Le journal, les dépendances et les fichiers d'unité ne sont affichés que pour \
+         les unités système.",
+    ),
+    ("unit_file", "Fichier d'unité : {}"),
+    ("drop_in", "Complément (drop-in) : {}"),
+    ("documentation", "Documentation :"),
+    ("no_documentation", "Aucune documentation indiquée"),
+    ("memory", "Mémoire"),
+    ("cpu", "CPU"),
+    ("dependents", "Unités qui en dépendent :"),
+    ("no_dependents", "Aucune unité n'en dépend"),
+    ("offline_banner", "Vue hors ligne de {} (importée, lecture seule) : les actions sont désactivées"),
+    ("no_description", "(aucune description)"),
+    ("error", "Erreur : {}"),
+    ("warning", "Avertissement : {}"),
+    ("no_data_dir", "Aucun répertoire de données disponible"),
+    ("snapshot_saved", "Instantané enregistré dans {}"),
+    ("comparing_snapshot", "Comparaison avec l'instantané pris {}"),
+    ("removed_since_snapshot", "Disparus depuis l'instantané : {}"),
+    ("change_new", "nouveau depuis l'instantané"),
+    ("change_failed", "en échec (était {})"),
+    ("change_active", "actif (était {})"),
+    ("change_state", "modifié (était {})"),
+    ("loading", "Chargement des services..."),
+    ("no_services", "Aucun service trouvé ou chargement impossible."),
+    (
+        "no_services_hint",
+        "En général, systemctl n'a pas pu joindre systemd ou vous n'avez pas le droit de lister \
+         les unités. Vérifiez que `systemctl list-units` fonctionne dans un terminal avec le même \
+         utilisateur.",
+    ),
+    ("try_again", "Réessayer"),
+    ("no_match", "Aucun service ne correspond aux filtres actuels."),
+    ("clear_filters", "Effacer les filtres"),
+    ("pinned", "Épinglés"),
+    ("all_services", "Tous les services"),
+    ("template_instances", "{} {} ({} instances)"),
+    ("with_failed", "{}, {} en échec"),
+    ("start", "Démarrer"),
+    ("stop", "Arrêter"),
+    ("restart", "Redémarrer"),
+    ("try_restart", "Redémarrer si actif"),
+    ("reload", "Recharger"),
+    ("row_menu_hint", "Actions (ou clic droit sur la ligne)"),
+    ("stop_and_disable", "Arrêter et désactiver"),
+    ("enable_and_start", "Activer et démarrer"),
+    ("enable", "Activer"),
+    ("disable", "Désactiver"),
+    ("override", "Surcharger"),
+    ("logs", "Journal"),
+    ("logs_for", "Journal de {}"),
+    ("no_log_entries", "Aucune entrée dans le journal."),
+    ("back", "Retour"),
+    ("masked_label", "masquée"),
+    ("legend", "Légende"),
+    ("legend_active", "En cours d'exécution ou active"),
+    ("legend_failed", "En échec ; sélectionnez-la pour réinitialiser ou redémarrer"),
+    ("legend_transitional", "En cours de démarrage, d'arrêt ou de rechargement"),
+    ("legend_inactive", "Arrêtée"),
+    ("legend_static", "Pas de section [Install], ne peut être activée ni désactivée"),
+    ("legend_masked", "Masquée : liée à /dev/null, démarrable seulement après unmask"),
+    ("legend_enabled", "Affiché pour les unités activées (démarrées au boot)"),
+    ("legend_disabled", "Affiché pour les unités désactivées"),
+    ("legend_highlight_sample", "ligne surlignée"),
+    ("legend_highlight", "L'état a changé lors de la dernière actualisation"),
+    ("static_label", "statique"),
+    (
+        "static_tooltip",
+        "Les unités statiques n'ont pas de section [Install]. Elles peuvent toujours être \
+         démarrées, manuellement ou comme dépendance d'autres unités, mais ne peuvent pas être \
+         activées ni désactivées.",
+    ),
+    ("command_copied", "Copié : {}"),
+    ("command_hint", "{} (clic droit pour copier)"),
+    ("override_for", "Surcharge pour {}"),
+    ("saving", "Enregistrement…"),
+    ("save_and_reload", "Enregistrer et recharger"),
+    ("cancel", "Annuler"),
+    ("close", "Fermer"),
+    ("unit_no_longer_present", "{} n'est plus présente depuis la dernière actualisation"),
+    ("columns", "Colonnes"),
+    ("columns_hint", "Choisissez les colonnes affichées et leur ordre."),
+    ("accent_color", "Couleur d'accent"),
+    ("accent_hint", "Utilisée pour les filtres sélectionnés et les boutons d'action."),
+    ("theme_default", "Thème par défaut"),
+    ("custom", "Personnalisée"),
+    ("preview", "Aperçu"),
+    ("language", "Langue"),
+    ("language_auto", "Automatique"),
+    ("after_refresh", "Après actualisation"),
+    ("scroll_preserve", "Conserver la position de défilement"),
+    ("scroll_top", "Revenir en haut"),
+    ("state_poll", "Mettre à jour les états :"),
+    ("state_poll_hint", "Relit l'état des unités affichées sans recharger toute la liste."),
+    ("off", "Désactivé"),
+    ("every_seconds", "toutes les {} s"),
+    ("auto_refresh", "Actualisation auto :"),
+    ("seconds_short", "{} s"),
+    ("update_states", "Mettre à jour les états"),
+    ("zoom", "Zoom"),
+    (
+        "zoom_hint",
+        "Met à l'échelle toute l'interface. Ctrl+= et Ctrl+- agrandissent et réduisent, Ctrl+0 \
+         réinitialise.",
+    ),
+    ("zoom_reset", "Réinitialiser"),
+    ("systemctl_options", "Options de systemctl"),
+    (
+        "systemctl_options_hint",
+        "Options ajoutées à chaque action, par exemple --now pour aussi démarrer ou arrêter une \
+         unité lors de son activation ou désactivation. Survolez un bouton d'action pour voir la \
+         commande obtenue.",
+    ),
+    ("accessibility", "Accessibilité"),
+    ("high_contrast", "Contraste élevé"),
+    (
+        "high_contrast_hint",
+        "Texte plus grand, couleurs franches et contrôles encadrés. Le gras s'applique après un \
+         redémarrage.",
+    ),
+    ("color_blue", "Bleu"),
+    ("color_teal", "Sarcelle"),
+    ("color_green", "Vert"),
+    ("color_orange", "Orange"),
+    ("color_red", "Rouge"),
+    ("color_purple", "Violet"),
+    ("column_name", "Nom"),
+    ("column_description", "Description"),
+    ("column_load", "Chargement"),
+    ("column_active", "Actif"),
+    ("column_sub", "Sous-état"),
+    ("column_unit_file", "Fichier d'unité"),
+    ("column_preset", "Préréglage"),
+    ("column_last_log", "Dernier journal"),
+    ("column_tasks", "Tâches"),
+    ("column_details", "Détails"),
+    ("updated_ago", "mis à jour {}"),
+    ("seconds_ago", "il y a {} s"),
+    ("minutes_ago", "il y a {} min"),
+    ("hours_ago", "il y a {} h"),
+    ("days_ago", "il y a {} j"),
+    ("older_than_days", "il y a > {} j"),
+];
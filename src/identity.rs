@@ -0,0 +1,41 @@
+use std::fs;
+
+/// The user the app runs as, read from `/proc` and `/etc/passwd` so no libc
+/// bindings are needed.
+#[derive(Debug, Clone)]
+pub struct Identity {
+    pub uid: u32,
+    pub name: String,
+}
+
+impl Identity {
+    /// The effective user, or `None` if `/proc/self/status` can't be read.
+    pub fn current() -> Option<Identity> {
+        let status = fs::read_to_string("/proc/self/status").ok()?;
+        // "Uid:" lists the real, effective, saved and filesystem UIDs.
+        let uid = status
+            .lines()
+            .find_map(|line| line.strip_prefix("Uid:"))?
+            .split_whitespace()
+            .nth(1)?
+            .parse()
+            .ok()?;
+
+        let name = user_name(uid).unwrap_or_else(|| uid.to_string());
+        Some(Identity { uid, name })
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.uid == 0
+    }
+}
+
+fn user_name(uid: u32) -> Option<String> {
+    let passwd = fs::read_to_string("/etc/passwd").ok()?;
+    passwd.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let entry_uid: u32 = fields.nth(1)?.parse().ok()?;
+        (entry_uid == uid).then(|| name.to_string())
+    })
+}
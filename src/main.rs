@@ -1,57 +1,802 @@
+mod activity;
+mod alert;
+mod cli;
+mod config;
+mod control;
+mod diagnostics;
+mod export;
+#[cfg(feature = "dbus")]
+mod dbus;
+mod i18n;
+mod identity;
+mod palette;
+#[cfg(feature = "serve")]
+mod serve;
+mod snapshot;
+mod style;
 mod systemd;
+mod unit_watch;
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 use iced::{
-    theme,
-    widget::{text_input, Button, Column, Container, Row, Scrollable, Text},
-    Alignment, Application, Command, Element, Length, Settings, Theme,
+    clipboard, event, keyboard, theme, window,
+    widget::{
+        checkbox, mouse_area, pick_list, progress_bar, radio, scrollable, Space, slider, text_editor, text_input, tooltip, Button,
+        Column, Container, Row, Scrollable, Text,
+    },
+    font, Alignment, Application, Color, Command, Element, Event, Font, Length, Point, Settings,
+    Size,
+    Subscription, Theme,
+};
+use config::{
+    matches_pattern, Config, CustomAction, FilterScope, ListColumn, NameStyle, Profile, ProfileState, SavedFilter, ScrollOnRefresh, StatusFilter, WindowGeometry, MAX_ZOOM, MIN_ZOOM, ZOOM_STEP,
+};
+use activity::ActionRecord;
+use control::ControlCommand;
+use export::{export_files, ExportReport};
+use i18n::{t, t_args, LOCALES};
+use identity::Identity;
+use snapshot::{Baseline, Change, Snapshot};
+use style::{
+    accent_color, builtin_theme, high_contrast_theme, AccentButton, ChangeHighlight, OutlinedButton,
+    StripedRow, ACCENT_PRESETS,
+};
+use systemd::{
+    alias_targets, analyze_blame, custom_command, run_custom_action, daemon_reexec, daemon_reload, get_environment, get_properties, set_property, validate_limit, restart_and_wait, preview_unit_file_change, get_reverse_dependencies, get_service_log_lines, get_service_logs, get_stop_dependents, get_statuses, import_services, reload_all, search_logs, recent_errors, reload_and_restart_service, restart_services, session_units, is_active, is_failed, last_log_times, list_jobs, list_services, override_path, read_override, reload_service, reset_failed_all, reset_failed_service, set_units_active,
+    restart_service, start_service, enable_service, disable_service, stop_and_disable_service, enable_and_start_service, template_name, stop_service, unescape_unit_name, SystemctlCommand,
+    remote_target, unit_name, set_remote_target, try_restart_service, validate_unit_snippet, write_override, ActionResult, EnvVar, ExtraArgs, JobInfo, LogFilter, LogMatch, LogPriority, RemoteTarget, ServiceInfo, ServiceScope, ServiceStatus, SymlinkChange, UnitEnvironment, UnitFileState,
+    EXTRA_FLAGS, EXTRA_FLAG_VERBS, LIMIT_PROPERTIES, LAST_LOG_WINDOW_DAYS, LOG_SEARCH_LINES,
 };
-use systemd::{list_services, ServiceInfo, start_service, stop_service, restart_service};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum StatusFilter {
-    Running,
-    Exited,
-    Dead,
-    Active,
-    Inactive,
-}
+/// Entries listed in the command palette.
+const PALETTE_MATCHES: usize = 8;
+
+/// Journal lines fetched per failed unit for its explanation.
+const FAILURE_ERROR_LINES: usize = 3;
+
+/// Journal lines shown by a row's "Logs" view.
+const FULL_LOG_LINES: usize = 1000;
+
+/// Width of the pin (star) toggle leading each row.
+const PIN_WIDTH: Length = Length::Fixed(30.0);
+
+/// How far instances are indented under their template's group row.
+const INDENT_WIDTH: f32 = 20.0;
+
+/// Rough width of a character in a list cell, for cutting long names and
+/// descriptions to one line. On the wide side, so the cut text fits.
+const CELL_CHAR_WIDTH: f32 = 8.5;
+
+/// Choices offered for `Config::state_poll_seconds`.
+const STATE_POLL_CHOICES: [u64; 4] = [0, 2, 5, 10];
+
+/// Auto-refresh intervals offered in the header, besides off.
+const AUTO_REFRESH_CHOICES: [u64; 3] = [2, 5, 10];
+
+/// Choices offered for `Config::restart_wait_seconds`.
+const RESTART_WAIT_CHOICES: [u64; 4] = [10, 30, 60, 120];
+
+/// Extra scale applied in high-contrast mode for larger text.
+const HIGH_CONTRAST_SCALE: f64 = 1.2;
+
+/// After this long without a reload the "updated ... ago" label turns orange.
+const STALE_AFTER: Duration = Duration::from_secs(60);
+
+/// How long a row stays highlighted after its state changes on refresh.
+const CHANGE_HIGHLIGHT: Duration = Duration::from_secs(4);
+
+/// How often the watched unit's live state is checked.
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone)]
 enum Message {
     RefreshServices,
+    RefreshStates,
+    StatesPolled(Result<Vec<ServiceStatus>, String>),
+    SetStatePoll(u64),
+    SetAutoRefresh(Option<Duration>),
+    SetRestartWait(u64),
+    RestartAndWait(String),
+    RestartWaitFinished(String, ActionResult, Duration),
+    ToggleWatch(String),
+    WatchTick,
+    WatchPolled(String, Result<LiveState, String>),
+    ToggleFileWatch(String),
+    UnitFileChanged(Result<(), String>),
     FilterChanged(String),
     ToggleStatusFilter(StatusFilter),
+    ToggleSavedFilter(String),
+    SavedFilterNameChanged(String),
+    SaveFilter,
+    RemoveSavedFilter(String),
+    RunProfile(String),
+    ProfileFinished(String, Vec<(String, Result<bool, String>)>),
+    ProfileNameChanged(String),
+    ProfileUnitsChanged(String),
+    SetProfileState(ProfileState),
+    SaveProfile,
+    RemoveProfile(String),
+    RunCustomAction(String, String),
+    CustomActionFinished(String, String, Result<String, String>),
+    CloseCustomOutput,
+    CustomActionNameChanged(String),
+    CustomActionCommandChanged(String),
+    SaveCustomAction,
+    RemoveCustomAction(String),
+    ToggleLocationFilter(String),
+    ToggleOverridesOnly,
+    ToggleSliceFilter(String),
+    ToggleGroupBySlice,
+    ToggleTemplateGroup(String),
+    TogglePinned(String),
+    SetHidden(String, bool),
+    ToggleShowHidden,
+    ClearFilters,
+    SortBy(ListColumn),
+    SelectService(String),
+    ToggleScope,
+    ToggleRowMenu(String),
+    DependentsLoaded(String, Result<Vec<String>, String>),
+    StopDependentsLoaded(String, Result<Vec<String>, String>),
+    UnitFilePreviewLoaded(String, UnitFileAction, Result<Option<Vec<SymlinkChange>>, String>),
+    ToggleLogs,
+    SetLogBoot(bool),
+    SetLogPriority(Option<LogPriority>),
+    LogsLoaded(String, LogFilter, Result<String, String>),
+    ToggleProperties,
+    ToggleEnvironment,
+    EnvironmentLoaded(String, Result<UnitEnvironment, String>),
+    ToggleRevealSecrets,
+    PropertyFilterChanged(String),
+    LimitPropertyChosen(&'static str),
+    LimitValueChanged(String),
+    SetLimitRuntime(bool),
+    ApplyLimit(String),
+    PropertiesLoaded(String, Result<BTreeMap<String, String>, String>),
+    CloseDetails,
+    ResourceTick,
+    ResourcesLoaded(Result<Vec<ServiceStatus>, String>),
+    GoToNextFailed,
+    ListScrolled(scrollable::Viewport),
+    ToggleAuditView,
+    ToggleSettings,
+    ToggleLegend,
+    ToggleActivity,
+    ToggleLogSearch,
+    LogSearchChanged(String),
+    SearchLogs,
+    LogSearchFinished(String, Result<Vec<LogMatch>, String>),
+    ToggleBootAnalysis,
+    BootAnalysisLoaded(Result<Vec<(String, Duration)>, String>),
+    OpenExport(Vec<String>),
+    ExportDirChanged(String),
+    ExportUnits,
+    ExportFinished(ExportReport),
+    CloseExport,
+    OpenPalette,
+    ClosePalette,
+    PaletteChanged(String),
+    PaletteSubmit,
+    PaletteRun(Box<Message>),
+    SetLogActivity(bool),
+    SetNotifyFailures(bool),
+    SetConfirmStopRestart(bool),
+    ProtectedUnitChanged(String),
+    SetProtected(String, bool),
+    SaveSnapshot,
+    SnapshotSaved(Result<PathBuf, String>),
+    ToggleCompare,
+    SnapshotLoaded(Result<Snapshot, String>),
+    SetColumnVisible(ListColumn, bool),
+    MoveColumn(ListColumn, isize),
+    SetAccent(Option<[u8; 3]>),
+    SetLanguage(Option<String>),
+    SetHighContrast(bool),
+    SetTheme(Theme),
+    SetScrollOnRefresh(ScrollOnRefresh),
+    SetNameStyle(NameStyle),
+    SetFilterScope(FilterScope),
+    SetExtraFlag(&'static str, &'static str, bool),
+    ZoomChanged(u16),
+    ZoomBy(i32),
+    ZoomReset,
+    AccentChannelChanged(usize, u8),
+    SaveConfig,
     StartService(String),
     StopService(String),
     RestartService(String),
-    ServicesLoaded(Result<Vec<ServiceInfo>, String>),
+    TryRestartService(String),
+    ReloadService(String),
+    StopAndDisableService(String),
+    EnableService(String),
+    DisableService(String),
+    EnableAndStartService(String),
+    ResetFailedService(String),
+    ResetAllFailed,
+    DaemonReload,
+    RequestConfirmation(Confirmation),
+    Confirm,
+    CancelConfirmation,
+    ConfirmationTyped(String),
+    SessionUnitsLoaded(Vec<String>),
+    CopyCommand(String),
+    CopyDiagnostics,
+    DiagnosticsCollected(String),
+    OpenDocumentation(String),
+    DocumentationOpened(Result<(), String>),
+    ActionFinished(String, ActionResult),
+    BulkActionFinished(&'static str, ActionResult),
+    RestartMatchingFinished(Vec<String>, ActionResult),
+    ReloadAllFinished(Vec<String>, ActionResult),
+    ActivityLogged(Result<(), String>),
+    ServicesLoaded(u64, Result<Vec<ServiceInfo>, String>),
+    LastLogsLoaded(Result<HashMap<String, SystemTime>, String>),
+    JobsLoaded(Result<Vec<JobInfo>, String>),
+    StatusesLoaded(Result<Vec<ServiceStatus>, String>),
+    FailureErrorsLoaded(HashMap<String, Vec<String>>),
+    WindowResized(u32, u32),
+    WindowMoved(i32, i32),
+    WindowCloseRequested,
+    HighlightTick(Instant),
+    ClockTick,
+    Control(Result<ControlCommand, String>),
+    EditOverride(String),
+    ViewLogs(String),
+    FullLogsLoaded(String, Result<Vec<String>, String>),
+    CloseLogs,
+    OverrideLoaded(String, Result<String, String>),
+    OverrideEdited(text_editor::Action),
+    SaveOverride,
+    OverrideSaved(Result<(), String>),
+    CloseOverrideEditor,
+}
+
+/// A line of the service list: a single unit, or the header of a collapsible
+/// group of template instances (`getty@tty1.service`, `getty@tty2.service`, ...).
+enum ListRow<'a> {
+    Heading(String),
+    Group {
+        template: String,
+        instances: Vec<&'a ServiceInfo>,
+        expanded: bool,
+    },
+    Unit {
+        service: &'a ServiceInfo,
+        indented: bool,
+    },
+}
+
+/// The latest resource sample of the selected unit, for the detail panel's
+/// dashboard. CPU usage needs two samples, so it is empty after the first.
+struct ResourceSample {
+    unit: String,
+    taken_at: Instant,
+    status: ServiceStatus,
+    cpu_percent: Option<f64>,
+}
+
+/// A unit's live state from `systemctl is-active` / `is-failed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LiveState {
+    active: bool,
+    failed: bool,
+}
+
+impl LiveState {
+    fn label(self) -> &'static str {
+        if self.failed {
+            "failed"
+        } else if self.active {
+            "active"
+        } else {
+            "inactive"
+        }
+    }
+}
+
+/// Actions disruptive enough to ask before running them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Confirmation {
+    DaemonReexec,
+    /// Offered when the watched unit file of this unit changes.
+    ReloadAndRestart(String),
+    /// Restarting every loaded unit matching the name filter at once.
+    RestartMatching(Vec<String>),
+    /// daemon-reload, then reloading every active unit that supports it.
+    ReloadAll(Vec<String>),
+    /// Stopping a unit, with the active units that `Requires=` or are bound
+    /// to it and so stop too, or the error if they couldn't be looked up.
+    Stop(String, Result<Vec<String>, String>),
+    /// Restarting a unit with `UnitAction::Restart`, `TryRestart` or
+    /// `RestartAndWait`, asked when Settings say so or the unit is protected.
+    Restart(String, UnitAction),
+    /// Enabling or disabling a unit, with the symlinks `--dry-run` says that
+    /// touches or why they couldn't be previewed.
+    UnitFileChange(String, UnitFileAction, Result<Vec<SymlinkChange>, String>),
+    /// Stopping a unit the current session runs under; the unit name has to
+    /// be typed to confirm.
+    SessionUnit(String, SessionAction),
+    /// Running a "stopped" profile with units that need confirming, and
+    /// those among them the current session runs under; if there are any,
+    /// the profile name has to be typed to confirm.
+    StopProfile(String, Vec<String>, Vec<String>),
+}
+
+/// Actions that take a session unit down, see `Confirmation::SessionUnit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionAction {
+    Stop,
+    Restart,
+    TryRestart,
+    RestartAndWait,
+    StopAndDisable,
+}
+
+impl SessionAction {
+    fn label(self) -> &'static str {
+        match self {
+            SessionAction::Stop => "stop",
+            SessionAction::Restart => "restart",
+            SessionAction::TryRestart => "try-restart",
+            SessionAction::RestartAndWait => "restart + wait",
+            SessionAction::StopAndDisable => "stop + disable",
+        }
+    }
+
+    /// The call to run, or `None` for `RestartAndWait`, which reports back
+    /// through `Message::RestartWaitFinished` instead.
+    fn run(self) -> Option<fn(&str, &ExtraArgs) -> ActionResult> {
+        match self {
+            SessionAction::Stop => {
+                Some(|name, extra| stop_service(name, extra, ServiceScope::System))
+            }
+            SessionAction::Restart => {
+                Some(|name, extra| restart_service(name, extra, ServiceScope::System))
+            }
+            SessionAction::TryRestart => Some(try_restart_service),
+            SessionAction::RestartAndWait => None,
+            SessionAction::StopAndDisable => Some(stop_and_disable_service),
+        }
+    }
+}
+
+/// The row actions that enable or disable a unit, previewed before running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnitFileAction {
+    Enable,
+    Disable,
+    EnableAndStart,
+    StopAndDisable,
+}
+
+impl UnitFileAction {
+    /// The `systemctl` verb whose `--dry-run` previews this action.
+    fn verb(self) -> &'static str {
+        match self {
+            UnitFileAction::Enable | UnitFileAction::EnableAndStart => "enable",
+            UnitFileAction::Disable | UnitFileAction::StopAndDisable => "disable",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            UnitFileAction::Enable => "enable",
+            UnitFileAction::Disable => "disable",
+            UnitFileAction::EnableAndStart => "enable + start",
+            UnitFileAction::StopAndDisable => "stop + disable",
+        }
+    }
+
+    fn run(self) -> fn(&str, &ExtraArgs) -> ActionResult {
+        match self {
+            UnitFileAction::Enable => enable_service,
+            UnitFileAction::Disable => disable_service,
+            UnitFileAction::EnableAndStart => enable_and_start_service,
+            UnitFileAction::StopAndDisable => stop_and_disable_service,
+        }
+    }
+}
+
+/// An entry of a row's action menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnitAction {
+    Start,
+    Stop,
+    Restart,
+    TryRestart,
+    RestartAndWait,
+    Reload,
+    ResetFailed,
+    EnableAndStart,
+    StopAndDisable,
+    Enable,
+    Disable,
+}
+
+impl UnitAction {
+    /// The actions that make sense for `service` in its current state, in
+    /// menu order: nothing that starts a masked or missing unit, no stop for
+    /// an inactive one, no reload without `CanReload`, and so on. User units
+    /// only get the ones that can be sent to the user's manager.
+    fn available(service: &ServiceInfo, scope: ServiceScope) -> Vec<UnitAction> {
+        let file_state = service.unit_file_state();
+        let masked = matches!(file_state, UnitFileState::Masked | UnitFileState::MaskedRuntime);
+        let startable = !masked && !service.is_not_found();
+        let active = service.is_active();
+
+        let mut actions = Vec::new();
+        if startable && !active {
+            actions.push(UnitAction::Start);
+        }
+        if active {
+            actions.push(UnitAction::Stop);
+        }
+        if startable {
+            actions.extend([UnitAction::Restart, UnitAction::RestartAndWait]);
+        }
+        if startable && active {
+            actions.push(UnitAction::TryRestart);
+        }
+        if active && service.can_reload {
+            actions.push(UnitAction::Reload);
+        }
+        if service.is_failed() {
+            actions.push(UnitAction::ResetFailed);
+        }
+        if file_state.is_toggleable() {
+            actions.extend(if file_state.is_enabled() {
+                [UnitAction::StopAndDisable, UnitAction::Disable]
+            } else {
+                [UnitAction::EnableAndStart, UnitAction::Enable]
+            });
+        }
+        if scope == ServiceScope::User {
+            actions.retain(UnitAction::is_scoped);
+        }
+        actions
+    }
+
+    /// Whether the action is sent to the manager of the current scope, see
+    /// `ServiceScope`; the others always act on system units.
+    fn is_scoped(&self) -> bool {
+        matches!(
+            self,
+            UnitAction::Start | UnitAction::Stop | UnitAction::Restart | UnitAction::Reload
+        )
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            UnitAction::Start => t("start"),
+            UnitAction::Stop => t("stop"),
+            UnitAction::Restart => t("restart"),
+            UnitAction::TryRestart => t("try_restart"),
+            UnitAction::RestartAndWait => t("restart_and_wait"),
+            UnitAction::Reload => t("reload"),
+            UnitAction::ResetFailed => t("reset_failed"),
+            UnitAction::EnableAndStart => t("enable_and_start"),
+            UnitAction::StopAndDisable => t("stop_and_disable"),
+            UnitAction::Enable => t("enable"),
+            UnitAction::Disable => t("disable"),
+        }
+    }
+
+    /// The systemctl verbs run, for the copyable command line.
+    fn verbs(self) -> &'static [&'static str] {
+        match self {
+            UnitAction::Start => &["start"],
+            UnitAction::Stop => &["stop"],
+            UnitAction::Restart | UnitAction::RestartAndWait => &["restart"],
+            UnitAction::TryRestart => &["try-restart"],
+            UnitAction::Reload => &["reload"],
+            UnitAction::ResetFailed => &["reset-failed"],
+            UnitAction::EnableAndStart => &["enable", "start"],
+            UnitAction::StopAndDisable => &["stop", "disable"],
+            UnitAction::Enable => &["enable"],
+            UnitAction::Disable => &["disable"],
+        }
+    }
+
+    fn message(self) -> fn(String) -> Message {
+        match self {
+            UnitAction::Start => Message::StartService,
+            UnitAction::Stop => Message::StopService,
+            UnitAction::Restart => Message::RestartService,
+            UnitAction::TryRestart => Message::TryRestartService,
+            UnitAction::RestartAndWait => Message::RestartAndWait,
+            UnitAction::Reload => Message::ReloadService,
+            UnitAction::ResetFailed => Message::ResetFailedService,
+            UnitAction::EnableAndStart => Message::EnableAndStartService,
+            UnitAction::StopAndDisable => Message::StopAndDisableService,
+            UnitAction::Enable => Message::EnableService,
+            UnitAction::Disable => Message::DisableService,
+        }
+    }
+}
+
+/// A custom action that was started from a row menu, and its output once it
+/// finished.
+struct CustomRun {
+    action: String,
+    unit: String,
+    result: Option<Result<String, String>>,
+}
+
+/// State of the panel exporting the shown units' unit files and drop-ins.
+struct ExportPanel {
+    units: Vec<String>,
+    dir: String,
+    exporting: bool,
+    report: Option<ExportReport>,
+}
+
+/// State of the drop-in override editor panel.
+struct OverrideEditor {
+    unit: String,
+    content: text_editor::Content,
+    error: Option<String>,
+    saving: bool,
+}
+
+struct Flags {
+    options: cli::Options,
+    config: Config,
 }
 
 struct SystemdServiceGui {
+    config: Config,
+    /// The built-in theme picked in Settings, kept as a `Theme` so `theme()`
+    /// doesn't look it up by name on every frame.
+    current_theme: Theme,
     services: Vec<ServiceInfo>,
+    /// Extra names of units listed more than once, to the name they are
+    /// shown under; see `alias_targets`.
+    aliases: HashMap<String, String>,
+    jobs: HashMap<String, JobInfo>,
+    in_flight: HashMap<String, &'static str>,
+    /// Units whose active/sub state changed on the last refresh, and when.
+    changed_at: HashMap<String, Instant>,
+    selected: Option<String>,
+    /// The unit whose action menu is open, from its "⋯" button or a right
+    /// click on the row.
+    row_menu: Option<String>,
+    /// Reverse dependencies of the selected unit, for the detail panel.
+    dependents: Option<Result<Vec<String>, String>>,
+    /// Whether the detail panel shows the journal, with which scope, and the
+    /// lines loaded for the selected unit.
+    show_logs: bool,
+    log_filter: LogFilter,
+    logs: Option<Result<String, String>>,
+    /// Whether the detail panel lists every `systemctl show` property of the
+    /// selected unit, the name filter over them, and the properties loaded.
+    show_properties: bool,
+    property_filter: String,
+    properties: Option<Result<BTreeMap<String, String>, String>>,
+    /// Whether the detail panel shows the selected unit's environment, the
+    /// environment loaded, and whether credential-like values are revealed.
+    show_environment: bool,
+    environment: Option<Result<UnitEnvironment, String>>,
+    reveal_secrets: bool,
+    /// The resource limit form in the detail panel: which property, the value
+    /// typed, whether it only lasts until reboot, and why the value was refused.
+    limit_property: &'static str,
+    limit_value: String,
+    limit_runtime: bool,
+    limit_error: Option<String>,
+    resources: Option<ResourceSample>,
+    list_viewport: Option<scrollable::Viewport>,
+    control_socket: Option<PathBuf>,
+    /// The window is closing: subscriptions are dropped so the control
+    /// socket and watchers are closed before the process exits.
+    shutting_down: bool,
+    /// File the units were imported from (`--import`); the view is read-only.
+    imported: Option<PathBuf>,
+    /// Whether the list shows system units or the user's own, toggled in
+    /// the header.
+    scope: ServiceScope,
+    identity: Option<Identity>,
     name_filter: String,
     status_filter: Option<StatusFilter>,
+    /// Name of the saved filter chip that is switched on, if any.
+    saved_filter: Option<String>,
+    /// Name typed for saving the current filters as a chip.
+    new_filter_name: String,
+    /// The "new profile" form in Settings.
+    new_profile_name: String,
+    new_profile_units: String,
+    new_profile_state: ProfileState,
+    /// The "new custom action" form in Settings, and why its command was
+    /// refused.
+    new_custom_name: String,
+    new_custom_command: String,
+    custom_error: Option<String>,
+    custom_run: Option<CustomRun>,
+    /// The "protect a unit" field in Settings.
+    new_protected_unit: String,
+    /// Directory a unit's fragment (unit file) must live in, e.g. `/etc/systemd/system`.
+    location_filter: Option<String>,
+    /// Only list units with drop-in overrides.
+    overrides_only: bool,
+    /// Slice a unit must run in, e.g. `machine.slice`.
+    slice_filter: Option<String>,
+    /// List units under a heading per slice.
+    group_by_slice: bool,
+    /// The list was loaded with only failed units (`--state=failed`), so it
+    /// has to be reloaded in full once the failed filter is switched off.
+    loaded_failed_only: bool,
+    /// List the units in `config.hidden` anyway.
+    show_hidden: bool,
+    /// Templates whose instance group is expanded in the list.
+    expanded_templates: HashSet<String>,
+    /// Column the list is sorted by and whether descending; by name,
+    /// ascending, until a column header is clicked.
+    sort: (ListColumn, bool),
+    audit_view: bool,
+    show_settings: bool,
+    /// Last error-priority journal lines of each failed unit, for the
+    /// explanation under its row.
+    failure_errors: HashMap<String, Vec<String>>,
+    /// Query of the Ctrl+K command palette while it is open.
+    palette: Option<String>,
+    /// Actions taken this session, oldest first, and whether they're shown.
+    activity: Vec<ActionRecord>,
+    show_activity: bool,
+    /// The journal search across all services: whether it's shown, the term,
+    /// whether a search is running, and the lines found for the last term.
+    show_log_search: bool,
+    log_search: String,
+    searching_logs: bool,
+    log_matches: Option<Result<Vec<LogMatch>, String>>,
+    /// The boot analysis panel and the `systemd-analyze blame` result.
+    show_boot_analysis: bool,
+    boot_blame: Option<Result<Vec<(String, Duration)>, String>>,
+    export: Option<ExportPanel>,
+    show_legend: bool,
+    comparison: Option<Baseline>,
+    override_editor: Option<OverrideEditor>,
+    /// How often the whole list reloads on its own, picked in the header;
+    /// `None` is off.
+    refresh_interval: Option<Duration>,
+    /// A unit's journal shown in place of the list ("Logs" in a row's menu).
+    viewing_logs: Option<(String, Vec<String>)>,
+    pending_confirmation: Option<Confirmation>,
+    /// What has been typed into a confirmation that asks for the unit name.
+    confirmation_text: String,
+    /// Units whose stop would end the desktop session, see `session_units`.
+    session_units: HashSet<String>,
+    last_logs_loaded: bool,
+    /// A journal scan for the "Last log" column is running.
+    loading_last_logs: bool,
+    /// Lookups a list load starts once it is in (jobs, statuses, journal
+    /// times, failure errors) that haven't returned yet; `refresh` waits
+    /// for them so auto-refresh can't pile them up.
+    follow_ups: usize,
+    /// Bumped for every service list load; only the newest result is applied,
+    /// so a slow reload can't overwrite a later one.
+    load_generation: u64,
     loading: bool,
+    /// When the service list was last loaded successfully.
+    last_refresh: Option<Instant>,
+    /// A state-only refresh is running; further polls are skipped until it returns.
+    polling_states: bool,
+    /// Unit whose live state is checked every `WATCH_INTERVAL`, and its last result.
+    watched: Option<String>,
+    watch_state: Option<LiveState>,
+    /// Unit whose fragment file is watched for edits, and that file.
+    file_watch: Option<(String, PathBuf)>,
     error: Option<String>,
+    /// systemctl's complaint about the last action when it didn't fail outright.
+    warning: Option<String>,
+    notice: Option<String>,
 }
 
 impl Application for SystemdServiceGui {
     type Message = Message;
     type Theme = Theme;
     type Executor = iced::executor::Default;
-    type Flags = ();
+    type Flags = Flags;
 
-    fn new(_flags: ()) -> (Self, Command<Message>) {
-        let app = SystemdServiceGui {
+    fn new(flags: Flags) -> (Self, Command<Message>) {
+        let Flags { options, config } = flags;
+        let mut app = SystemdServiceGui {
+            current_theme: builtin_theme(config.theme.as_deref()),
+            config,
             services: Vec::new(),
-            name_filter: String::new(),
-            status_filter: None,
+            aliases: HashMap::new(),
+            jobs: HashMap::new(),
+            in_flight: HashMap::new(),
+            changed_at: HashMap::new(),
+            selected: None,
+            row_menu: None,
+            dependents: None,
+            show_logs: false,
+            show_properties: false,
+            property_filter: String::new(),
+            properties: None,
+            show_environment: false,
+            environment: None,
+            reveal_secrets: false,
+            limit_property: LIMIT_PROPERTIES[0],
+            limit_value: String::new(),
+            limit_runtime: true,
+            limit_error: None,
+            log_filter: LogFilter::default(),
+            logs: None,
+            resources: None,
+            list_viewport: None,
+            control_socket: options.control_socket,
+            shutting_down: false,
+            imported: options.import,
+            scope: ServiceScope::default(),
+            identity: Identity::current(),
+            name_filter: options.name_filter.unwrap_or_default(),
+            status_filter: options.failed_only.then_some(StatusFilter::Failed),
+            saved_filter: None,
+            new_filter_name: String::new(),
+            new_profile_name: String::new(),
+            new_profile_units: String::new(),
+            new_profile_state: ProfileState::default(),
+            new_custom_name: String::new(),
+            new_protected_unit: String::new(),
+            new_custom_command: String::new(),
+            custom_error: None,
+            custom_run: None,
+            location_filter: None,
+            overrides_only: false,
+            slice_filter: None,
+            group_by_slice: false,
+            show_hidden: false,
+            loaded_failed_only: false,
+            expanded_templates: HashSet::new(),
+            sort: (ListColumn::Name, false),
+            audit_view: false,
+            show_settings: false,
+            failure_errors: HashMap::new(),
+            palette: None,
+            activity: Vec::new(),
+            show_activity: false,
+            show_log_search: false,
+            log_search: String::new(),
+            searching_logs: false,
+            log_matches: None,
+            show_boot_analysis: false,
+            boot_blame: None,
+            export: None,
+            show_legend: false,
+            comparison: None,
+            override_editor: None,
+            viewing_logs: None,
+            refresh_interval: None,
+            pending_confirmation: None,
+            confirmation_text: String::new(),
+            session_units: HashSet::new(),
+            last_logs_loaded: false,
+            loading_last_logs: false,
+            follow_ups: 0,
+            load_generation: 0,
             loading: false,
+            last_refresh: None,
+            polling_states: false,
+            watched: None,
+            watch_state: None,
+            file_watch: None,
             error: None,
+            warning: None,
+            notice: None,
         };
 
         let command = app.load_services();
-        (app, command)
+        if app.offline() {
+            return (app, command);
+        }
+        let uid = app.identity.as_ref().map(|identity| identity.uid);
+        let session = Command::perform(blocking(move || session_units(uid)), Message::SessionUnitsLoaded);
+        (app, Command::batch([command, session]))
     }
 
     fn title(&self) -> String {
@@ -59,8 +804,111 @@ impl Application for SystemdServiceGui {
     }
 
     fn update(&mut self, message: Message) -> Command<Message> {
+        tracing::trace!(?message, "update");
         match message {
             Message::RefreshServices => self.refresh(),
+            Message::RefreshStates => self.refresh_states(),
+            Message::StatesPolled(result) => {
+                self.polling_states = false;
+                match result {
+                    Ok(statuses) => self.apply_statuses(statuses, true),
+                    Err(e) => self.error = Some(e),
+                }
+                Command::none()
+            }
+            Message::SetAutoRefresh(interval) => {
+                self.refresh_interval = interval;
+                Command::none()
+            }
+            Message::SetStatePoll(seconds) => {
+                self.config.state_poll_seconds = seconds;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetRestartWait(seconds) => {
+                self.config.restart_wait_seconds = seconds;
+                self.save_config();
+                Command::none()
+            }
+            Message::RestartAndWait(name)
+                if self.scope == ServiceScope::System && self.session_units.contains(&name) =>
+            {
+                self.confirm_session_action(name, SessionAction::RestartAndWait)
+            }
+            Message::RestartAndWait(name) if self.needs_confirmation(&name) => {
+                self.pending_confirmation =
+                    Some(Confirmation::Restart(name, UnitAction::RestartAndWait));
+                Command::none()
+            }
+            Message::RestartAndWait(name) => self.restart_and_wait_for(name),
+            Message::RestartWaitFinished(name, result, elapsed) => {
+                self.in_flight.remove(&name);
+                if matches!(result, Ok(None)) {
+                    let seconds = format!("{:.1}", elapsed.as_secs_f32());
+                    self.notice = Some(t_args("came_up", &[&name, &seconds]));
+                }
+                let logged = self.record_action(Some(name), "restart + wait", &result);
+                Command::batch([logged, self.finish_action(result)])
+            }
+            Message::ToggleWatch(name) => {
+                if self.watched.as_deref() == Some(name.as_str()) {
+                    self.watched = None;
+                } else {
+                    self.watched = Some(name);
+                }
+                self.watch_state = None;
+                Command::none()
+            }
+            Message::WatchTick => {
+                let Some(name) = self.watched.clone() else {
+                    return Command::none();
+                };
+                Command::perform(
+                    blocking(move || {
+                        let state = is_active(&name).and_then(|active| {
+                            is_failed(&name).map(|failed| LiveState { active, failed })
+                        });
+                        (name, state)
+                    }),
+                    |(name, state)| Message::WatchPolled(name, state),
+                )
+            }
+            Message::WatchPolled(name, _) if self.watched.as_deref() != Some(name.as_str()) => {
+                Command::none()
+            }
+            Message::WatchPolled(name, result) => match result {
+                Ok(state) => {
+                    self.watch_state = Some(state);
+                    self.refresh_watched(name, state)
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    self.watched = None;
+                    self.watch_state = None;
+                    Command::none()
+                }
+            },
+            Message::ToggleFileWatch(name) => {
+                let watching = self.file_watch.as_ref().is_some_and(|(unit, _)| *unit == name);
+                self.file_watch = match self.services.iter().find(|s| s.name == name) {
+                    Some(service) if !watching && !service.fragment_path.is_empty() => {
+                        Some((name, PathBuf::from(&service.fragment_path)))
+                    }
+                    _ => None,
+                };
+                Command::none()
+            }
+            Message::UnitFileChanged(Ok(())) => {
+                if let Some((unit, _)) = &self.file_watch {
+                    self.pending_confirmation = Some(Confirmation::ReloadAndRestart(unit.clone()));
+                }
+                Command::none()
+            }
+            Message::UnitFileChanged(Err(e)) => {
+                self.error = Some(e);
+                self.file_watch = None;
+                Command::none()
+            }
             Message::FilterChanged(value) => {
                 self.name_filter = value;
                 Command::none()
@@ -70,237 +918,5028 @@ impl Application for SystemdServiceGui {
                     Some(selected) if selected == filter => None,
                     _ => Some(filter),
                 };
+                self.reload_if_partial()
+            }
+            Message::ToggleSavedFilter(name) => {
+                self.saved_filter = match self.saved_filter.take() {
+                    Some(selected) if selected == name => None,
+                    _ => Some(name),
+                };
                 Command::none()
             }
-            Message::StartService(name) => self.start(name),
-            Message::StopService(name) => self.stop(name),
-            Message::RestartService(name) => self.restart(name),
-            Message::ServicesLoaded(result) => {
-                self.loading = false;
-                match result {
-                    Ok(services) => {
-                        self.services = services;
-                        self.error = None;
+            Message::SavedFilterNameChanged(name) => {
+                self.new_filter_name = name;
+                Command::none()
+            }
+            Message::SaveFilter => {
+                let name = self.new_filter_name.trim().to_string();
+                if name.is_empty() {
+                    return Command::none();
+                }
+                self.config.save_filter(SavedFilter {
+                    name: name.clone(),
+                    pattern: self.name_filter.trim().to_string(),
+                    status: self.status_filter,
+                });
+                self.save_config();
+                self.new_filter_name.clear();
+                self.name_filter.clear();
+                self.status_filter = None;
+                self.saved_filter = Some(name);
+                Command::none()
+            }
+            Message::RemoveSavedFilter(name) => {
+                self.config.saved_filters.retain(|filter| filter.name != name);
+                self.save_config();
+                if self.saved_filter.as_ref() == Some(&name) {
+                    self.saved_filter = None;
+                }
+                Command::none()
+            }
+            Message::RunProfile(name) => {
+                let Some(profile) = self.config.profiles.iter().find(|p| p.name == name) else {
+                    return Command::none();
+                };
+                if profile.state == ProfileState::Stopped {
+                    let session: Vec<String> = profile
+                        .units
+                        .iter()
+                        .filter(|unit| self.session_units.contains(unit_name(unit).as_ref()))
+                        .cloned()
+                        .collect();
+                    let confirm: Vec<String> = profile
+                        .units
+                        .iter()
+                        .filter(|unit| {
+                            session.contains(unit) || self.needs_confirmation(&unit_name(unit))
+                        })
+                        .cloned()
+                        .collect();
+                    if !confirm.is_empty() {
+                        self.confirmation_text.clear();
+                        self.pending_confirmation =
+                            Some(Confirmation::StopProfile(name, confirm, session));
+                        return Command::none();
                     }
-                    Err(e) => {
-                        self.error = Some(e);
+                }
+                self.run_profile(name)
+            }
+            Message::ProfileFinished(name, results) => {
+                let state = self
+                    .config
+                    .profiles
+                    .iter()
+                    .find(|p| p.name == name)
+                    .map_or(ProfileState::Started, |p| p.state);
+                let mut changed = Vec::new();
+                let mut unchanged = 0;
+                let mut failed = Vec::new();
+                for (unit, result) in results {
+                    self.in_flight.remove(&unit);
+                    match result {
+                        Ok(true) => changed.push(unit),
+                        Ok(false) => unchanged += 1,
+                        Err(e) => failed.push(e),
                     }
                 }
+                self.notice = Some(t_args(
+                    "profile_done",
+                    &[&name, &changed.len(), &unchanged, &failed.len()],
+                ));
+
+                let result = if failed.is_empty() { Ok(None) } else { Err(failed.join("\n")) };
+                let logged = if changed.is_empty() && failed.is_empty() {
+                    Command::none()
+                } else {
+                    self.record_action(Some(changed.join(" ")), state.verb(), &result)
+                };
+                Command::batch([logged, self.finish_action(result)])
+            }
+            Message::ProfileNameChanged(name) => {
+                self.new_profile_name = name;
                 Command::none()
             }
-        }
-    }
-
-    fn view(&self) -> Element<'_, Message> {
-        let title = Text::new("Systemd Service GUI")
-            .size(40)
-            .width(Length::Fill);
-
-        let refresh_button = Button::new(
-            Text::new("Refresh"),
-        )
-        .on_press(Message::RefreshServices);
-
-        let header = Row::new()
-            .push(title)
-            .push(refresh_button)
-            .align_items(Alignment::Center)
-            .spacing(10)
-            .width(Length::Fill);
-
-        let name_filter_input = text_input("Filter services by name...", &self.name_filter)
-            .on_input(Message::FilterChanged)
-            .padding(10)
-            .size(16)
-            .width(Length::Fill);
-
-        let status_filter_row = Row::new()
-            .push(Text::new("Status:"))
-            .push(self.status_filter_button("running", StatusFilter::Running))
-            .push(self.status_filter_button("exited", StatusFilter::Exited))
-            .push(self.status_filter_button("dead", StatusFilter::Dead))
-            .push(self.status_filter_button("active", StatusFilter::Active))
-            .push(self.status_filter_button("inactive", StatusFilter::Inactive))
-            .spacing(10)
-            .align_items(Alignment::Center)
-            .width(Length::Fill);
-
-        let mut content = Column::new().spacing(10);
-        let filtered_services = self.filtered_services();
-
-        if let Some(error) = &self.error {
-            content = content.push(
-                Text::new(format!("Error: {}", error))
-                    .size(16),
-            );
-        }
-
-        if self.loading {
-            content = content.push(Text::new("Loading services...").size(16));
-        } else if self.services.is_empty() {
-            content = content.push(Text::new("No services found or unable to load services.").size(16));
-        } else if filtered_services.is_empty() {
-            content = content.push(Text::new("No services match the current filters.").size(16));
-        } else {
-            for service in filtered_services {
-                let service_row = Row::new()
-                    .push(
-                        Text::new(format!("{}", service.name))
-                            .width(Length::Fixed(250.0))
-                    )
-                    .push(
-                        Text::new(format!("{}", service.description))
-                            .width(Length::Fixed(300.0))
-                    )
-                    .push(
-                        Text::new(format!("{}", service.active_state))
-                            .width(Length::Fixed(100.0))
-                    )
-                    .push(
-                        Text::new(format!("{}", service.sub_state))
-                            .width(Length::Fixed(100.0))
-                    )
-                    .push(
-                        Button::new(
-                            Text::new("Start"),
-                        )
-                        .on_press(Message::StartService(service.name.clone()))
-                    )
-                    .push(
-                        Button::new(
-                            Text::new("Stop"),
-                        )
-                        .on_press(Message::StopService(service.name.clone()))
-                    )
-                    .push(
-                        Button::new(
-                            Text::new("Restart"),
-                        )
-                        .on_press(Message::RestartService(service.name.clone()))
-                    )
-                    .spacing(10)
-                    .align_items(Alignment::Center);
-
-                content = content.push(service_row);
+            Message::ProfileUnitsChanged(units) => {
+                self.new_profile_units = units;
+                Command::none()
             }
-        }
-
-        let scroll_content = Scrollable::new(content)
-            .width(Length::Fill)
-            .height(Length::Fill);
-
-        Container::new(
-            Column::new()
-                .push(header)
-                .push(name_filter_input)
-                .push(status_filter_row)
-                .push(scroll_content)
-                .spacing(20)
-                .padding(20)
-                .width(Length::Fill)
-                .height(Length::Fill)
-        )
-        .width(Length::Fill)
-        .height(Length::Fill)
-        .into()
-    }
-}
-
-impl SystemdServiceGui {
-    fn status_filter_button<'a>(&self, label: &'a str, filter: StatusFilter) -> Button<'a, Message> {
-        let is_selected = self.status_filter == Some(filter);
-        Button::new(Text::new(label))
-            .on_press(Message::ToggleStatusFilter(filter))
-            .style(if is_selected {
-                theme::Button::Primary
-            } else {
-                theme::Button::Secondary
-            })
-    }
-
-    fn filtered_services(&self) -> Vec<&ServiceInfo> {
-        let needle = self.name_filter.trim().to_ascii_lowercase();
-        self.services
-            .iter()
-            .filter(|service| {
-                let name_ok = needle.is_empty()
-                    || service.name.to_ascii_lowercase().contains(&needle);
-                let status_ok = self
-                    .status_filter
+            Message::SetProfileState(state) => {
+                self.new_profile_state = state;
+                Command::none()
+            }
+            Message::SaveProfile => {
+                let name = self.new_profile_name.trim().to_string();
+                let units: Vec<String> =
+                    self.new_profile_units.split_whitespace().map(str::to_string).collect();
+                if name.is_empty() || units.is_empty() {
+                    return Command::none();
+                }
+                self.config.save_profile(Profile {
+                    name,
+                    units,
+                    state: self.new_profile_state,
+                });
+                self.save_config();
+                self.new_profile_name.clear();
+                self.new_profile_units.clear();
+                Command::none()
+            }
+            Message::RemoveProfile(name) => {
+                self.config.profiles.retain(|profile| profile.name != name);
+                self.save_config();
+                Command::none()
+            }
+            Message::RunCustomAction(action, unit) => {
+                let Some(template) = self
+                    .config
+                    .custom_actions
+                    .iter()
+                    .find(|a| a.name == action)
+                    .map(|a| a.command.clone())
+                else {
+                    return Command::none();
+                };
+                self.custom_run = Some(CustomRun {
+                    action: action.clone(),
+                    unit: unit.clone(),
+                    result: None,
+                });
+                Command::perform(
+                    blocking(move || {
+                        let result = run_custom_action(&template, &unit);
+                        (action, unit, result)
+                    }),
+                    |(action, unit, result)| Message::CustomActionFinished(action, unit, result),
+                )
+            }
+            Message::CustomActionFinished(action, unit, result) => {
+                let recorded = result.as_ref().map(|_| None).map_err(Clone::clone);
+                let logged = self.record_action(Some(unit.clone()), "custom action", &recorded);
+                // A later run (or a closed panel) takes precedence.
+                if let Some(run) = self
+                    .custom_run
+                    .as_mut()
+                    .filter(|run| run.action == action && run.unit == unit)
+                {
+                    run.result = Some(result);
+                }
+                logged
+            }
+            Message::CloseCustomOutput => {
+                self.custom_run = None;
+                Command::none()
+            }
+            Message::CustomActionNameChanged(name) => {
+                self.new_custom_name = name;
+                Command::none()
+            }
+            Message::CustomActionCommandChanged(command) => {
+                self.new_custom_command = command;
+                self.custom_error = None;
+                Command::none()
+            }
+            Message::SaveCustomAction => {
+                let name = self.new_custom_name.trim().to_string();
+                let command = self.new_custom_command.trim().to_string();
+                if name.is_empty() {
+                    return Command::none();
+                }
+                if let Err(e) = custom_command(&command, "example.service") {
+                    self.custom_error = Some(e);
+                    return Command::none();
+                }
+                self.config.save_custom_action(CustomAction { name, command });
+                self.save_config();
+                self.new_custom_name.clear();
+                self.new_custom_command.clear();
+                Command::none()
+            }
+            Message::RemoveCustomAction(name) => {
+                self.config.custom_actions.retain(|action| action.name != name);
+                self.save_config();
+                Command::none()
+            }
+            Message::ToggleLocationFilter(dir) => {
+                self.location_filter = match self.location_filter.take() {
+                    Some(selected) if selected == dir => None,
+                    _ => Some(dir),
+                };
+                Command::none()
+            }
+            Message::ToggleSliceFilter(slice) => {
+                self.slice_filter = match self.slice_filter.take() {
+                    Some(selected) if selected == slice => None,
+                    _ => Some(slice),
+                };
+                Command::none()
+            }
+            Message::ToggleGroupBySlice => {
+                self.group_by_slice = !self.group_by_slice;
+                Command::none()
+            }
+            Message::ToggleOverridesOnly => {
+                self.overrides_only = !self.overrides_only;
+                Command::none()
+            }
+            Message::ToggleTemplateGroup(template) => {
+                if !self.expanded_templates.remove(&template) {
+                    self.expanded_templates.insert(template);
+                }
+                Command::none()
+            }
+            Message::TogglePinned(name) => {
+                self.config.toggle_pinned(&name);
+                self.save_config();
+                Command::none()
+            }
+            Message::SetHidden(name, hidden) => {
+                if hidden {
+                    self.config.hidden.insert(name);
+                } else {
+                    self.config.hidden.remove(&name);
+                }
+                self.save_config();
+                Command::none()
+            }
+            Message::ToggleShowHidden => {
+                self.show_hidden = !self.show_hidden;
+                Command::none()
+            }
+            Message::ClearFilters => {
+                self.name_filter.clear();
+                self.status_filter = None;
+                self.saved_filter = None;
+                self.location_filter = None;
+                self.slice_filter = None;
+                self.overrides_only = false;
+                self.reload_if_partial()
+            }
+            Message::SortBy(column) => {
+                self.sort = match self.sort {
+                    (sorted, descending) if sorted == column => (column, !descending),
+                    _ => (column, false),
+                };
+                Command::none()
+            }
+            Message::SelectService(name) => self.select(name),
+            Message::DependentsLoaded(name, _) if self.selected.as_ref() != Some(&name) => {
+                Command::none()
+            }
+            Message::DependentsLoaded(_, result) => {
+                self.dependents = Some(result);
+                Command::none()
+            }
+            Message::ToggleLogs => {
+                self.show_logs = !self.show_logs;
+                self.load_logs()
+            }
+            Message::SetLogBoot(current_boot) => {
+                self.log_filter.current_boot = current_boot;
+                self.load_logs()
+            }
+            Message::SetLogPriority(priority) => {
+                self.log_filter.priority = priority;
+                self.load_logs()
+            }
+            Message::LogsLoaded(name, filter, _)
+                if self.selected.as_ref() != Some(&name) || self.log_filter != filter =>
+            {
+                Command::none()
+            }
+            Message::LogsLoaded(_, _, result) => {
+                self.logs = Some(result);
+                Command::none()
+            }
+            Message::ToggleProperties => {
+                self.show_properties = !self.show_properties;
+                self.load_properties()
+            }
+            Message::ToggleEnvironment => {
+                self.show_environment = !self.show_environment;
+                self.load_environment()
+            }
+            Message::EnvironmentLoaded(name, _) if self.selected.as_ref() != Some(&name) => {
+                Command::none()
+            }
+            Message::EnvironmentLoaded(_, result) => {
+                self.environment = Some(result);
+                Command::none()
+            }
+            Message::ToggleRevealSecrets => {
+                self.reveal_secrets = !self.reveal_secrets;
+                Command::none()
+            }
+            Message::PropertyFilterChanged(filter) => {
+                self.property_filter = filter;
+                Command::none()
+            }
+            Message::LimitPropertyChosen(property) => {
+                self.limit_property = property;
+                self.limit_error = None;
+                Command::none()
+            }
+            Message::LimitValueChanged(value) => {
+                self.limit_value = value;
+                self.limit_error = None;
+                Command::none()
+            }
+            Message::SetLimitRuntime(runtime) => {
+                self.limit_runtime = runtime;
+                Command::none()
+            }
+            // Checked here too so a typo is reported next to the form rather
+            // than as a failed action.
+            Message::ApplyLimit(name) => {
+                let (property, value) = (self.limit_property, self.limit_value.trim().to_string());
+                if let Err(e) = validate_limit(property, &value) {
+                    self.limit_error = Some(e);
+                    return Command::none();
+                }
+                let runtime = self.limit_runtime;
+                self.run_unit_action(name, "set-property", move |name| {
+                    set_property(name, property, &value, runtime)
+                })
+            }
+            Message::PropertiesLoaded(name, _) if self.selected.as_ref() != Some(&name) => {
+                Command::none()
+            }
+            Message::PropertiesLoaded(_, result) => {
+                self.properties = Some(result);
+                Command::none()
+            }
+            Message::CloseDetails => {
+                self.selected = None;
+                self.dependents = None;
+                self.logs = None;
+                self.properties = None;
+                self.environment = None;
+                self.resources = None;
+                Command::none()
+            }
+            Message::ResourceTick => {
+                let Some(name) = self.selected.clone() else {
+                    return Command::none();
+                };
+                let scope = self.scope;
+                Command::perform(
+                    blocking(move || get_statuses(&[&name], scope)),
+                    Message::ResourcesLoaded,
+                )
+            }
+            Message::ResourcesLoaded(result) => {
+                match result {
+                    Ok(statuses) => self.record_resources(statuses),
+                    // Transient while the unit stops or restarts; the dashboard
+                    // just keeps its last sample.
+                    Err(e) => tracing::debug!(error = %e, "resource poll failed"),
+                }
+                Command::none()
+            }
+            Message::GoToNextFailed => self.go_to_next_failed(),
+            Message::ListScrolled(viewport) => {
+                self.list_viewport = Some(viewport);
+                Command::none()
+            }
+            Message::ToggleAuditView => {
+                self.audit_view = !self.audit_view;
+                Command::none()
+            }
+            Message::OpenPalette => {
+                self.palette = Some(String::new());
+                text_input::focus(palette_input_id())
+            }
+            Message::ClosePalette => {
+                self.palette = None;
+                Command::none()
+            }
+            Message::PaletteChanged(query) => {
+                self.palette = Some(query);
+                Command::none()
+            }
+            Message::PaletteSubmit => {
+                let query = self.palette.clone().unwrap_or_default();
+                match palette::rank(&query, self.palette_entries(), 1).pop() {
+                    Some((_, message)) => self.update(Message::PaletteRun(Box::new(message))),
+                    None => Command::none(),
+                }
+            }
+            Message::PaletteRun(message) => {
+                self.palette = None;
+                self.update(*message)
+            }
+            Message::ToggleActivity => {
+                self.show_activity = !self.show_activity;
+                Command::none()
+            }
+            Message::ToggleLogSearch => {
+                self.show_log_search = !self.show_log_search;
+                if self.show_log_search {
+                    text_input::focus(log_search_input_id())
+                } else {
+                    Command::none()
+                }
+            }
+            Message::LogSearchChanged(term) => {
+                self.log_search = term;
+                Command::none()
+            }
+            Message::SearchLogs => {
+                let term = self.log_search.trim().to_string();
+                if term.is_empty() || !self.system_live() {
+                    return Command::none();
+                }
+                self.searching_logs = true;
+                Command::perform(
+                    blocking(move || {
+                        let result = search_logs(&term);
+                        (term, result)
+                    }),
+                    |(term, result)| Message::LogSearchFinished(term, result),
+                )
+            }
+            Message::LogSearchFinished(term, _) if term != self.log_search.trim() => {
+                self.searching_logs = false;
+                Command::none()
+            }
+            Message::LogSearchFinished(_, result) => {
+                self.searching_logs = false;
+                self.log_matches = Some(result);
+                Command::none()
+            }
+            // Startup times don't change until the next boot, so they are
+            // only loaded the first time the panel opens.
+            // systemd-analyze only reads the local system's boot.
+            Message::ToggleBootAnalysis if !self.system_live() => Command::none(),
+            Message::ToggleBootAnalysis => {
+                self.show_boot_analysis = !self.show_boot_analysis;
+                if !self.show_boot_analysis || self.boot_blame.is_some() {
+                    return Command::none();
+                }
+                Command::perform(blocking(analyze_blame), Message::BootAnalysisLoaded)
+            }
+            Message::BootAnalysisLoaded(result) => {
+                self.boot_blame = Some(result);
+                Command::none()
+            }
+            Message::OpenExport(units) => {
+                let dir = std::env::var_os("HOME")
+                    .map(|home| PathBuf::from(home).join("unit-export"))
+                    .unwrap_or_else(|| PathBuf::from("unit-export"));
+                self.export = Some(ExportPanel {
+                    units,
+                    dir: dir.display().to_string(),
+                    exporting: false,
+                    report: None,
+                });
+                Command::none()
+            }
+            Message::ExportDirChanged(dir) => {
+                if let Some(export) = &mut self.export {
+                    export.dir = dir;
+                }
+                Command::none()
+            }
+            Message::ExportUnits => {
+                let Some(export) = &mut self.export else {
+                    return Command::none();
+                };
+                let paths: Vec<PathBuf> = self
+                    .services
+                    .iter()
+                    .filter(|service| export.units.contains(&service.name))
+                    .flat_map(|service| {
+                        let fragment = Some(&service.fragment_path).filter(|path| !path.is_empty());
+                        fragment.into_iter().chain(&service.drop_in_paths).map(PathBuf::from)
+                    })
+                    .collect();
+                let dir = PathBuf::from(export.dir.trim());
+                export.exporting = true;
+                Command::perform(
+                    blocking(move || export_files(&paths, &dir)),
+                    Message::ExportFinished,
+                )
+            }
+            Message::ExportFinished(report) => {
+                if let Some(export) = &mut self.export {
+                    export.exporting = false;
+                    export.report = Some(report);
+                }
+                Command::none()
+            }
+            Message::CloseExport => {
+                self.export = None;
+                Command::none()
+            }
+            Message::SetLogActivity(enabled) => {
+                self.config.log_activity = enabled;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetNotifyFailures(enabled) => {
+                self.config.notify_failures = enabled;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetConfirmStopRestart(enabled) => {
+                self.config.confirm_stop_restart = enabled;
+                self.save_config();
+                Command::none()
+            }
+            Message::ProtectedUnitChanged(name) => {
+                self.new_protected_unit = name;
+                Command::none()
+            }
+            Message::SetProtected(name, protected) => {
+                if protected {
+                    self.config.protected_units.insert(name.trim().to_string());
+                    self.new_protected_unit.clear();
+                } else {
+                    self.config.protected_units.remove(&name);
+                }
+                self.save_config();
+                Command::none()
+            }
+            Message::ToggleLegend => {
+                self.show_legend = !self.show_legend;
+                Command::none()
+            }
+            Message::ToggleSettings => {
+                self.show_settings = !self.show_settings;
+                Command::none()
+            }
+            Message::SaveSnapshot => {
+                let Some(path) = snapshot::default_path() else {
+                    self.error = Some(t("no_data_dir").to_string());
+                    return Command::none();
+                };
+                let services = self.services.clone();
+                Command::perform(
+                    blocking(move || snapshot::save(&path, &services).map(|()| path)),
+                    Message::SnapshotSaved,
+                )
+            }
+            Message::SnapshotSaved(result) => {
+                match result {
+                    Ok(path) => {
+                        self.notice = Some(t_args("snapshot_saved", &[&path.display()]))
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                Command::none()
+            }
+            Message::ToggleCompare => {
+                if self.comparison.take().is_some() {
+                    return Command::none();
+                }
+                let Some(path) = snapshot::default_path() else {
+                    self.error = Some(t("no_data_dir").to_string());
+                    return Command::none();
+                };
+                Command::perform(
+                    blocking(move || snapshot::load(&path)),
+                    Message::SnapshotLoaded,
+                )
+            }
+            Message::SnapshotLoaded(result) => {
+                match result {
+                    Ok(snapshot) => self.comparison = Some(Baseline::from(snapshot)),
+                    Err(e) => self.error = Some(e),
+                }
+                Command::none()
+            }
+            Message::SetColumnVisible(column, visible) => {
+                self.config.set_column_visible(column, visible);
+                self.save_config();
+                // The journal times are only read while the column is shown.
+                if column == ListColumn::LastLog && visible && !self.last_logs_loaded {
+                    return self.load_last_logs();
+                }
+                Command::none()
+            }
+            Message::MoveColumn(column, offset) => {
+                self.config.move_column(column, offset);
+                self.save_config();
+                Command::none()
+            }
+            Message::SetAccent(accent) => {
+                self.config.accent = accent;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetLanguage(language) => {
+                i18n::set_locale(language.as_deref());
+                self.config.language = language;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetHighContrast(enabled) => {
+                self.config.high_contrast = enabled;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetTheme(theme) => {
+                self.config.theme = Some(theme.to_string());
+                self.current_theme = theme;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetFilterScope(scope) => {
+                self.config.filter_scope = scope;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetScrollOnRefresh(behavior) => {
+                self.config.scroll_on_refresh = behavior;
+                self.save_config();
+                Command::none()
+            }
+            Message::SetNameStyle(style) => {
+                self.config.name_style = style;
+                self.save_config();
+                Command::none()
+            }
+            Message::ZoomChanged(percent) => {
+                // Saved on slider release, like the accent sliders.
+                self.config.set_zoom(percent as i32);
+                Command::none()
+            }
+            Message::ZoomBy(delta) => {
+                self.config.set_zoom(self.config.zoom_percent as i32 + delta);
+                self.save_config();
+                Command::none()
+            }
+            Message::ZoomReset => {
+                self.config.set_zoom(100);
+                self.save_config();
+                Command::none()
+            }
+            Message::AccentChannelChanged(channel, value) => {
+                // Saved on slider release (`SaveConfig`) rather than per step.
+                let mut rgb = self.config.accent.unwrap_or(ACCENT_PRESETS[0].1);
+                rgb[channel] = value;
+                self.config.accent = Some(rgb);
+                Command::none()
+            }
+            Message::SetExtraFlag(verb, flag, enabled) => {
+                self.config.systemctl_args.set(verb, flag, enabled);
+                self.save_config();
+                Command::none()
+            }
+            Message::SaveConfig => {
+                self.save_config();
+                Command::none()
+            }
+            Message::ToggleScope => {
+                self.scope = match self.scope {
+                    ServiceScope::System => ServiceScope::User,
+                    ServiceScope::User => ServiceScope::System,
+                };
+                self.selected = None;
+                self.row_menu = None;
+                self.viewing_logs = None;
+                // Watches and confirmations are about units of the other scope.
+                self.watched = None;
+                self.watch_state = None;
+                self.file_watch = None;
+                self.pending_confirmation = None;
+                self.services.clear();
+                self.jobs.clear();
+                self.load_services()
+            }
+            Message::StartService(name) => self.run_scoped_action(name, "start", start_service),
+            // The dependency check and the session units are about system units.
+            Message::StopService(name) if self.scope == ServiceScope::User => {
+                self.confirm_stop(name, Ok(Vec::new()))
+            }
+            Message::StopService(name) if self.session_units.contains(&name) => {
+                self.confirm_session_action(name, SessionAction::Stop)
+            }
+            Message::RestartService(name)
+                if self.scope == ServiceScope::System && self.session_units.contains(&name) =>
+            {
+                self.confirm_session_action(name, SessionAction::Restart)
+            }
+            Message::StopAndDisableService(name) if self.session_units.contains(&name) => {
+                self.confirm_session_action(name, SessionAction::StopAndDisable)
+            }
+            Message::RestartService(name) if self.needs_confirmation(&name) => {
+                self.pending_confirmation = Some(Confirmation::Restart(name, UnitAction::Restart));
+                Command::none()
+            }
+            // Stopping takes down everything that requires the unit, so look
+            // that up first and only ask when there is something to lose.
+            Message::StopService(name) => Command::perform(
+                blocking(move || {
+                    let result = get_stop_dependents(&name);
+                    (name, result)
+                }),
+                |(name, result)| Message::StopDependentsLoaded(name, result),
+            ),
+            Message::StopDependentsLoaded(name, result) => self.confirm_stop(name, result),
+            Message::RestartService(name) => {
+                self.run_scoped_action(name, "restart", restart_service)
+            }
+            Message::TryRestartService(name)
+                if self.scope == ServiceScope::System && self.session_units.contains(&name) =>
+            {
+                self.confirm_session_action(name, SessionAction::TryRestart)
+            }
+            Message::TryRestartService(name) if self.needs_confirmation(&name) => {
+                self.pending_confirmation =
+                    Some(Confirmation::Restart(name, UnitAction::TryRestart));
+                Command::none()
+            }
+            Message::TryRestartService(name) => self.run_restart(name, UnitAction::TryRestart),
+            Message::ReloadService(name) => {
+                let scope = self.scope;
+                self.run_unit_action(name, "reload", move |name| reload_service(name, scope))
+            }
+            Message::ToggleRowMenu(name) => {
+                self.row_menu = match self.row_menu.take() {
+                    Some(open) if open == name => None,
+                    _ => Some(name),
+                };
+                Command::none()
+            }
+            Message::StopAndDisableService(name) => {
+                self.preview_unit_file_change(name, UnitFileAction::StopAndDisable)
+            }
+            Message::EnableAndStartService(name) => {
+                self.preview_unit_file_change(name, UnitFileAction::EnableAndStart)
+            }
+            Message::EnableService(name) => {
+                self.preview_unit_file_change(name, UnitFileAction::Enable)
+            }
+            Message::DisableService(name) => {
+                self.preview_unit_file_change(name, UnitFileAction::Disable)
+            }
+            // Nothing to show when this systemd has no --dry-run or the unit
+            // is already in the requested state.
+            Message::UnitFilePreviewLoaded(name, action, Ok(changes))
+                if changes.as_ref().is_none_or(Vec::is_empty) =>
+            {
+                self.run_configured_action(name, action.label(), action.run())
+            }
+            Message::UnitFilePreviewLoaded(name, action, result) => {
+                let changes = result.map(Option::unwrap_or_default);
+                self.pending_confirmation =
+                    Some(Confirmation::UnitFileChange(name, action, changes));
+                Command::none()
+            }
+            Message::ResetFailedService(name) => {
+                self.run_unit_action(name, "reset-failed", reset_failed_service)
+            }
+            Message::ResetAllFailed => self.reset_all_failed(),
+            Message::DaemonReload => {
+                Command::perform(blocking(daemon_reload), |result| {
+                    Message::BulkActionFinished("daemon-reload", result)
+                })
+            }
+            Message::RequestConfirmation(confirmation) => {
+                self.pending_confirmation = Some(confirmation);
+                Command::none()
+            }
+            Message::Confirm => match self.pending_confirmation.take() {
+                Some(Confirmation::DaemonReexec) => {
+                    Command::perform(blocking(daemon_reexec), |result| {
+                        Message::BulkActionFinished("daemon-reexec", result)
+                    })
+                }
+                Some(Confirmation::ReloadAndRestart(name)) => {
+                    self.run_configured_action(
+                        name,
+                        "daemon-reload + restart",
+                        reload_and_restart_service,
+                    )
+                }
+                Some(Confirmation::RestartMatching(names)) => self.restart_matching(names),
+                Some(Confirmation::ReloadAll(names)) => Command::perform(
+                    blocking(move || {
+                        let result = reload_all(&names);
+                        (names, result)
+                    }),
+                    |(names, result)| Message::ReloadAllFinished(names, result),
+                ),
+                Some(Confirmation::Stop(name, _)) => {
+                    self.run_scoped_action(name, "stop", stop_service)
+                }
+                Some(Confirmation::Restart(name, action)) => self.run_restart(name, action),
+                Some(Confirmation::UnitFileChange(name, action, _)) => {
+                    self.run_configured_action(name, action.label(), action.run())
+                }
+                Some(Confirmation::StopProfile(name, _, _)) => self.run_profile(name),
+                Some(Confirmation::SessionUnit(name, action)) => match action.run() {
+                    Some(run) => self.run_configured_action(name, action.label(), run),
+                    None => self.restart_and_wait_for(name),
+                },
+                None => Command::none(),
+            },
+            Message::CancelConfirmation => {
+                self.pending_confirmation = None;
+                Command::none()
+            }
+            Message::ConfirmationTyped(text) => {
+                self.confirmation_text = text;
+                Command::none()
+            }
+            Message::SessionUnitsLoaded(units) => {
+                self.session_units = units.into_iter().collect();
+                Command::none()
+            }
+            Message::CopyCommand(command) => {
+                self.notice = Some(t_args("command_copied", &[&command]));
+                clipboard::write(command)
+            }
+            Message::CopyDiagnostics => {
+                let config = self.config.clone();
+                let identity = self.identity.clone();
+                let error = self.error.clone();
+                Command::perform(
+                    blocking(move || diagnostics::collect(&config, identity.as_ref(), error.as_deref())),
+                    Message::DiagnosticsCollected,
+                )
+            }
+            Message::DiagnosticsCollected(report) => {
+                self.notice = Some(t("diagnostics_copied").to_string());
+                clipboard::write(report)
+            }
+            Message::OpenDocumentation(url) => Command::perform(
+                blocking(move || {
+                    open::that(&url).map_err(|e| format!("Failed to open {}: {}", url, e))
+                }),
+                Message::DocumentationOpened,
+            ),
+            Message::DocumentationOpened(result) => {
+                if let Err(e) = result {
+                    self.error = Some(e);
+                }
+                Command::none()
+            }
+            Message::ActionFinished(name, result) => {
+                let action = self.in_flight.remove(&name).unwrap_or("?");
+                let logged = self.record_action(Some(name), action, &result);
+                Command::batch([logged, self.finish_action(result)])
+            }
+            Message::BulkActionFinished(action, result) => {
+                let logged = self.record_action(None, action, &result);
+                Command::batch([logged, self.finish_action(result)])
+            }
+            Message::RestartMatchingFinished(names, result) => {
+                for name in &names {
+                    self.in_flight.remove(name);
+                }
+                let logged = self.record_action(Some(names.join(" ")), "restart", &result);
+                Command::batch([logged, self.finish_action(result)])
+            }
+            Message::ReloadAllFinished(names, result) => {
+                if matches!(result, Ok(None)) {
+                    self.notice = Some(t_args("reload_all_done", &[&names.len()]));
+                }
+                let logged =
+                    self.record_action(Some(names.join(" ")), "daemon-reload + reload", &result);
+                Command::batch([logged, self.finish_action(result)])
+            }
+            Message::ActivityLogged(result) => {
+                if let Err(e) = result {
+                    self.error = Some(e);
+                }
+                Command::none()
+            }
+            Message::ServicesLoaded(generation, _) if generation != self.load_generation => {
+                Command::none()
+            }
+            Message::ServicesLoaded(_, result) => {
+                self.loading = false;
+                match result {
+                    Ok(services) => {
+                        self.mark_changed(&services);
+                        self.services = services;
+                        self.aliases = alias_targets(&self.services);
+                        self.last_refresh = Some(Instant::now());
+                        self.error = None;
+                        self.last_logs_loaded = false;
+                        // The detail panel follows the selected unit by name. If
+                        // that name is now folded into another row as an alias,
+                        // it moves to that row; if it is gone, the view says so.
+                        let rebind = match self.selected.as_ref() {
+                            Some(name) => match self.aliases.get(name).cloned() {
+                                Some(target) => self.select(target),
+                                None => Command::none(),
+                            },
+                            None => Command::none(),
+                        };
+                        if self.offline() {
+                            return Command::batch([rebind, self.restore_scroll()]);
+                        }
+                        // Jobs and the journal column are read for system units only.
+                        if self.scope == ServiceScope::User {
+                            return Command::batch([
+                                rebind,
+                                self.restore_scroll(),
+                                self.load_statuses(),
+                            ]);
+                        }
+                        Command::batch([
+                            rebind,
+                            self.restore_scroll(),
+                            self.load_jobs(),
+                            self.load_last_logs(),
+                            self.load_statuses(),
+                        ])
+                    }
+                    Err(e) => {
+                        self.error = Some(e);
+                        Command::none()
+                    }
+                }
+            }
+            Message::LastLogsLoaded(result) => {
+                self.follow_ups = self.follow_ups.saturating_sub(1);
+                self.loading_last_logs = false;
+                // The journal is a best-effort extra; without access the column
+                // simply stays blank rather than raising an error.
+                if let Ok(times) = result {
+                    for service in &mut self.services {
+                        service.last_log = times.get(&service.name).copied();
+                    }
+                    self.last_logs_loaded = true;
+                }
+                Command::none()
+            }
+            Message::JobsLoaded(result) => {
+                self.follow_ups = self.follow_ups.saturating_sub(1);
+                // Like the journal column, job badges are best effort.
+                if let Ok(jobs) = result {
+                    self.jobs = jobs.into_iter().map(|job| (job.unit.clone(), job)).collect();
+                }
+                Command::none()
+            }
+            Message::StatusesLoaded(result) => {
+                self.follow_ups = self.follow_ups.saturating_sub(1);
+                if let Ok(statuses) = result {
+                    self.apply_statuses(statuses, false);
+                }
+                self.load_failure_errors()
+            }
+            Message::FailureErrorsLoaded(errors) => {
+                self.follow_ups = self.follow_ups.saturating_sub(1);
+                self.failure_errors = errors;
+                Command::none()
+            }
+            Message::WindowResized(width, height) => {
+                let geometry = self.window_geometry();
+                geometry.width = width;
+                geometry.height = height;
+                Command::none()
+            }
+            Message::WindowMoved(x, y) => {
+                let geometry = self.window_geometry();
+                geometry.x = Some(x);
+                geometry.y = Some(y);
+                Command::none()
+            }
+            Message::WindowCloseRequested => {
+                self.shut_down();
+                window::close(window::Id::MAIN)
+            }
+            Message::HighlightTick(now) => {
+                self.changed_at
+                    .retain(|_, changed| now.duration_since(*changed) < CHANGE_HIGHLIGHT);
+                Command::none()
+            }
+            // Only re-renders, so the "updated ... ago" label keeps counting.
+            Message::ClockTick => Command::none(),
+            Message::Control(Ok(command)) => match command {
+                ControlCommand::Refresh => self.refresh(),
+                ControlCommand::Filter { value } => {
+                    self.name_filter = value;
+                    Command::none()
+                }
+            },
+            Message::Control(Err(e)) => {
+                self.error = Some(e);
+                Command::none()
+            }
+            Message::EditOverride(name) => Command::perform(
+                blocking(move || {
+                    let result = read_override(&name);
+                    (name, result)
+                }),
+                |(name, result)| Message::OverrideLoaded(name, result),
+            ),
+            Message::OverrideLoaded(unit, result) => {
+                match result {
+                    Ok(text) => {
+                        self.override_editor = Some(OverrideEditor {
+                            unit,
+                            content: text_editor::Content::with_text(&text),
+                            error: None,
+                            saving: false,
+                        });
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                Command::none()
+            }
+            Message::OverrideEdited(action) => {
+                if let Some(editor) = &mut self.override_editor {
+                    editor.content.perform(action);
+                }
+                Command::none()
+            }
+            Message::SaveOverride => {
+                let Some(editor) = &mut self.override_editor else {
+                    return Command::none();
+                };
+                let text = editor.content.text();
+                if let Err(e) = validate_unit_snippet(&text) {
+                    editor.error = Some(e);
+                    return Command::none();
+                }
+
+                editor.error = None;
+                editor.saving = true;
+                let unit = editor.unit.clone();
+                Command::perform(
+                    blocking(move || write_override(&unit, &text)),
+                    Message::OverrideSaved,
+                )
+            }
+            Message::OverrideSaved(result) => match result {
+                Ok(()) => {
+                    self.override_editor = None;
+                    self.load_services()
+                }
+                Err(e) => {
+                    if let Some(editor) = &mut self.override_editor {
+                        editor.saving = false;
+                        editor.error = Some(e);
+                    }
+                    Command::none()
+                }
+            },
+            Message::CloseOverrideEditor => {
+                self.override_editor = None;
+                Command::none()
+            }
+            Message::ViewLogs(name) => Command::perform(
+                blocking(move || {
+                    let result = get_service_log_lines(&name, FULL_LOG_LINES);
+                    (name, result)
+                }),
+                |(name, result)| Message::FullLogsLoaded(name, result),
+            ),
+            Message::FullLogsLoaded(name, result) => {
+                match result {
+                    Ok(lines) => {
+                        self.row_menu = None;
+                        self.viewing_logs = Some((name, lines));
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                Command::none()
+            }
+            Message::CloseLogs => {
+                self.viewing_logs = None;
+                Command::none()
+            }
+        }
+    }
+
+    fn theme(&self) -> Theme {
+        if self.config.high_contrast {
+            high_contrast_theme()
+        } else {
+            self.current_theme.clone()
+        }
+    }
+
+    fn scale_factor(&self) -> f64 {
+        let zoom = self.config.zoom_percent as f64 / 100.0;
+        if self.config.high_contrast {
+            zoom * HIGH_CONTRAST_SCALE
+        } else {
+            zoom
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        if self.shutting_down {
+            return Subscription::none();
+        }
+
+        let window_events = event::listen_with(|event, _status| match event {
+            Event::Window(_, window::Event::Resized { width, height }) => {
+                Some(Message::WindowResized(width, height))
+            }
+            Event::Window(_, window::Event::Moved { x, y }) => Some(Message::WindowMoved(x, y)),
+            Event::Window(_, window::Event::CloseRequested) => Some(Message::WindowCloseRequested),
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Character(c),
+                modifiers,
+                ..
+            }) if modifiers.command() => match c.as_str() {
+                "=" | "+" => Some(Message::ZoomBy(ZOOM_STEP as i32)),
+                "-" => Some(Message::ZoomBy(-(ZOOM_STEP as i32))),
+                "0" => Some(Message::ZoomReset),
+                "k" => Some(Message::OpenPalette),
+                _ => None,
+            },
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(keyboard::key::Named::Escape),
+                ..
+            }) => Some(Message::ClosePalette),
+            _ => None,
+        });
+
+        let control = match &self.control_socket {
+            Some(path) => control::listen(path.clone()).map(Message::Control),
+            None => Subscription::none(),
+        };
+
+        // Only tick while something is fading so an idle window stays idle.
+        let highlight = if self.changed_at.is_empty() {
+            Subscription::none()
+        } else {
+            iced::time::every(Duration::from_millis(100)).map(Message::HighlightTick)
+        };
+
+        let state_poll = match self.config.state_poll_seconds {
+            _ if self.offline() => Subscription::none(),
+            0 => Subscription::none(),
+            seconds => iced::time::every(Duration::from_secs(seconds))
+                .map(|_| Message::RefreshStates),
+        };
+
+        // Dropped entirely when off, rather than ticking into nothing.
+        let auto_refresh = match self.refresh_interval {
+            Some(interval) if !self.offline() => {
+                iced::time::every(interval).map(|_| Message::RefreshServices)
+            }
+            _ => Subscription::none(),
+        };
+
+        let watch = if self.watched.is_some() {
+            iced::time::every(WATCH_INTERVAL).map(|_| Message::WatchTick)
+        } else {
+            Subscription::none()
+        };
+
+        let file_watch = match &self.file_watch {
+            Some((_, path)) if !self.offline() => {
+                unit_watch::changes(path.clone()).map(Message::UnitFileChanged)
+            }
+            _ => Subscription::none(),
+        };
+
+        // Only while the detail panel shows a running unit.
+        let resources = if !self.offline()
+            && self.selected_service().is_some_and(ServiceInfo::is_running)
+        {
+            iced::time::every(Duration::from_secs(1)).map(|_| Message::ResourceTick)
+        } else {
+            Subscription::none()
+        };
+
+        let clock = if self.last_refresh.is_some() {
+            iced::time::every(Duration::from_secs(1)).map(|_| Message::ClockTick)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            window_events,
+            control,
+            highlight,
+            state_poll,
+            auto_refresh,
+            watch,
+            file_watch,
+            resources,
+            clock,
+        ])
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let title = Text::new("Systemd Service GUI")
+            .size(40)
+            .width(Length::Fill);
+
+        let refresh_button = Button::new(
+            Text::new(t("refresh")),
+        )
+        .on_press(Message::RefreshServices)
+        .style(self.button_style(theme::Button::Primary));
+
+        let update_states_button = Button::new(Text::new(t("update_states")))
+            .on_press_maybe(
+                (!self.polling_states && !self.offline()).then_some(Message::RefreshStates),
+            )
+            .style(self.button_style(theme::Button::Secondary));
+
+        let audit_button = Button::new(Text::new(t("audit")))
+            .on_press(Message::ToggleAuditView)
+            .style(if self.audit_view {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let activity_button = Button::new(Text::new(t_args("activity", &[&self.activity.len()])))
+            .on_press(Message::ToggleActivity)
+            .style(if self.show_activity {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let scope_button = Button::new(Text::new(t("user_services")))
+            .on_press_maybe((!self.offline()).then_some(Message::ToggleScope))
+            .style(if self.scope == ServiceScope::User {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let log_search_button = Button::new(Text::new(t("log_search")))
+            .on_press_maybe(self.system_live().then_some(Message::ToggleLogSearch))
+            .style(if self.show_log_search {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let boot_analysis_button = Button::new(Text::new(t("boot_analysis")))
+            .on_press_maybe(self.system_live().then_some(Message::ToggleBootAnalysis))
+            .style(if self.show_boot_analysis {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let settings_button = Button::new(Text::new(t("settings")))
+            .on_press(Message::ToggleSettings)
+            .style(if self.show_settings {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let legend_button = tooltip(
+            Button::new(Text::new("?"))
+                .on_press(Message::ToggleLegend)
+                .style(if self.show_legend {
+                    self.primary_style()
+                } else {
+                    self.button_style(theme::Button::Secondary)
+                }),
+            Text::new(t("legend")).size(14),
+            tooltip::Position::Bottom,
+        )
+        .style(theme::Container::Box);
+
+        let save_snapshot_button = Button::new(Text::new(t("save_snapshot")))
+            .on_press_maybe((!self.services.is_empty()).then_some(Message::SaveSnapshot))
+            .style(self.button_style(theme::Button::Secondary));
+
+        let compare_button = Button::new(Text::new(t("compare_snapshot")))
+            .on_press(Message::ToggleCompare)
+            .style(if self.comparison.is_some() {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let any_failed = self.services.iter().any(ServiceInfo::is_failed);
+        let reset_failed_button = mouse_area(
+            Button::new(Text::new(t("reset_failed")))
+                .on_press_maybe(
+                    (any_failed && self.system_live()).then_some(Message::ResetAllFailed),
+                )
+                .style(self.button_style(theme::Button::Secondary)),
+        )
+        .on_right_press(Message::CopyCommand(
+            SystemctlCommand::new("reset-failed")
+                .target(remote_target())
+                .scope(self.scope)
+                .command_line(),
+        ));
+
+        let daemon_reload_button = mouse_area(
+            Button::new(Text::new(t("daemon_reload")))
+                .on_press_maybe(self.system_live().then_some(Message::DaemonReload))
+                .style(self.button_style(theme::Button::Secondary)),
+        )
+        .on_right_press(Message::CopyCommand(
+            SystemctlCommand::new("daemon-reload")
+                .target(remote_target())
+                .scope(self.scope)
+                .command_line(),
+        ));
+
+        let reloadable = self.reloadable_units();
+        let reload_all_button = Button::new(Text::new(t("reload_all")))
+            .on_press_maybe(
+                (!reloadable.is_empty() && self.system_live())
+                    .then_some(Message::RequestConfirmation(Confirmation::ReloadAll(reloadable))),
+            )
+            .style(self.button_style(theme::Button::Secondary));
+
+        let daemon_reexec_button = mouse_area(
+            Button::new(Text::new(t("daemon_reexec")))
+                .on_press_maybe(
+                    self.system_live()
+                        .then_some(Message::RequestConfirmation(Confirmation::DaemonReexec)),
+                )
+                .style(self.button_style(theme::Button::Secondary)),
+        )
+        .on_right_press(Message::CopyCommand(
+            SystemctlCommand::new("daemon-reexec")
+                .target(remote_target())
+                .scope(self.scope)
+                .command_line(),
+        ));
+
+        let visible_failed = self
+            .filtered_services()
+            .into_iter()
+            .filter(|service| service.is_failed())
+            .count();
+        let next_failed_button = Button::new(Text::new(t_args("next_failed", &[&visible_failed])))
+            .on_press_maybe((visible_failed > 0).then_some(Message::GoToNextFailed))
+            .style(self.button_style(theme::Button::Destructive));
+
+        let header = Row::new()
+            .push(title)
+            .push(self.identity_badge())
+            .push(scope_button)
+            .push(next_failed_button)
+            .push(reset_failed_button)
+            .push(daemon_reload_button)
+            .push(reload_all_button)
+            .push(daemon_reexec_button)
+            .push(save_snapshot_button)
+            .push(compare_button)
+            .push(audit_button)
+            .push(activity_button)
+            .push(log_search_button)
+            .push(boot_analysis_button)
+            .push(settings_button)
+            .push(legend_button)
+            .push(update_states_button)
+            .push(self.last_refresh_label())
+            .push(self.auto_refresh_picker())
+            .push(refresh_button)
+            .align_items(Alignment::Center)
+            .spacing(10)
+            .width(Length::Fill);
+
+        let name_filter_input = text_input(t("filter_placeholder"), &self.name_filter)
+            .on_input(Message::FilterChanged)
+            .padding(10)
+            .size(16)
+            .width(Length::Fill);
+
+        let matching = self.matching_units();
+        let mut name_filter_row = Row::new()
+            .push(name_filter_input)
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if !matching.is_empty() && self.system_live() {
+            name_filter_row = name_filter_row.push(
+                Button::new(Text::new(t_args("restart_matching", &[&matching.len()])))
+                    .on_press(Message::RequestConfirmation(Confirmation::RestartMatching(matching)))
+                    .style(self.button_style(theme::Button::Secondary)),
+            );
+        }
+        let exportable = self.exportable_units();
+        if !exportable.is_empty() && self.system_live() {
+            name_filter_row = name_filter_row.push(
+                Button::new(Text::new(t_args("export_shown", &[&exportable.len()])))
+                    .on_press(Message::OpenExport(exportable))
+                    .style(self.button_style(theme::Button::Secondary)),
+            );
+        }
+
+        let status_filter_row = Row::new()
+            .push(Text::new(t("status")))
+            .push(self.status_filter_button("running", StatusFilter::Running))
+            .push(self.status_filter_button("exited", StatusFilter::Exited))
+            .push(self.status_filter_button("dead", StatusFilter::Dead))
+            .push(self.status_filter_button("active", StatusFilter::Active))
+            .push(self.status_filter_button("inactive", StatusFilter::Inactive))
+            .push(self.status_filter_button("failed", StatusFilter::Failed))
+            .push_maybe(self.hidden_toggle())
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .width(Length::Fill);
+
+        let mut content = Column::new().spacing(10);
+        let filtered_services = self.filtered_services();
+
+        if let Some(query) = &self.palette {
+            content = content.push(self.palette_view(query));
+        }
+
+        if let Some(path) = &self.imported {
+            content = content.push(
+                Container::new(
+                    Text::new(t_args("offline_banner", &[&path.display()])).size(16),
+                )
+                .padding(10)
+                .width(Length::Fill)
+                .style(theme::Container::Box),
+            );
+        }
+
+        if let Some(error) = &self.error {
+            content = content.push(
+                Row::new()
+                    .push(Text::new(t_args("error", &[error])).size(16).width(Length::Fill))
+                    .push(
+                        Button::new(Text::new(t("copy_diagnostics")).size(14))
+                            .on_press(Message::CopyDiagnostics)
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            );
+        }
+
+        if let Some(warning) = &self.warning {
+            content = content.push(
+                Text::new(t_args("warning", &[warning]))
+                    .size(14)
+                    .style(Color::from_rgb(0.9, 0.7, 0.1)),
+            );
+        }
+
+        if let Some(notice) = &self.notice {
+            content = content.push(Text::new(notice.as_str()).size(14));
+        }
+
+        if let Some(name) = self.removed_selection() {
+            content = content.push(
+                Row::new()
+                    .push(Text::new(t_args("unit_no_longer_present", &[&name])).size(14))
+                    .push(
+                        Button::new(Text::new(t("close")).size(14))
+                            .on_press(Message::CloseDetails)
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            );
+        }
+
+        if self.show_legend {
+            content = content.push(self.legend_view());
+        }
+
+        if self.show_activity {
+            content = content.push(self.activity_view());
+        }
+
+        if self.show_log_search {
+            content = content.push(self.log_search_view());
+        }
+
+        if self.show_boot_analysis && self.system_live() {
+            content = content.push(self.boot_analysis_view());
+        }
+
+        if let Some(run) = &self.custom_run {
+            content = content.push(self.custom_output_view(run));
+        }
+
+        if let Some(confirmation) = &self.pending_confirmation {
+            content = content.push(self.confirmation_view(confirmation));
+        }
+
+        if let Some(export) = &self.export {
+            content = content.push(self.export_view(export));
+        }
+
+        if let Some(baseline) = &self.comparison {
+            content = content.push(
+                Text::new(t_args(
+                    "comparing_snapshot",
+                    &[&format_relative(baseline.taken_at)],
+                ))
+                .size(14),
+            );
+        }
+
+        // Banners and panels above, and the column header, stay put while
+        // the list below them scrolls.
+        let mut list = Column::new().spacing(10);
+        let mut column_header = None;
+
+        if self.show_settings {
+            list = list.push(self.settings_view());
+        } else if let Some(editor) = &self.override_editor {
+            list = list.push(self.override_view(editor));
+        } else if let Some((unit, lines)) = &self.viewing_logs {
+            list = list.push(self.full_log_view(unit, lines));
+        } else if self.loading && self.services.is_empty() {
+            list = list.push(Text::new(t("loading")).size(16));
+        } else if self.services.is_empty() {
+            list = list.push(
+                Column::new()
+                    .push(Text::new(t("no_services")).size(16))
+                    .push(
+                        Text::new(t("no_services_hint")).size(14),
+                    )
+                    .push(
+                        Button::new(Text::new(t("try_again")))
+                            .on_press(Message::RefreshServices)
+                            .style(self.button_style(theme::Button::Primary)),
+                    )
+                    .spacing(10),
+            );
+        } else if filtered_services.is_empty() && !self.any_pinned_listed() {
+            list = list.push(
+                Row::new()
+                    .push(Text::new(t("no_match")).size(16))
+                    .push(
+                        Button::new(Text::new(t("clear_filters")))
+                            .on_press(Message::ClearFilters)
+                            .style(self.button_style(theme::Button::Primary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            );
+        } else {
+            column_header = Some(self.column_header());
+
+            let columns = self.visible_columns();
+
+            for (index, row) in self.list_rows().into_iter().enumerate() {
+                let striped = index % 2 == 1;
+                list = list.push(match row {
+                    ListRow::Heading(label) => Text::new(label).size(18).into(),
+                    ListRow::Group {
+                        template,
+                        instances,
+                        expanded,
+                    } => self.group_row(template, &instances, expanded),
+                    ListRow::Unit { service, indented }
+                        if self.selected.as_deref() == Some(service.name.as_str()) =>
+                    {
+                        Column::new()
+                            .push(self.service_row(service, &columns, indented, striped))
+                            .push(self.detail_panel(service))
+                            .spacing(4)
+                            .into()
+                    }
+                    ListRow::Unit { service, indented } => {
+                        self.service_row(service, &columns, indented, striped)
+                    }
+                });
+            }
+        }
+
+        if let Some(baseline) = &self.comparison {
+            let removed = baseline.removed(&self.services);
+            if !removed.is_empty() {
+                list = list.push(
+                    Text::new(t_args("removed_since_snapshot", &[&removed.join(", ")]))
+                        .size(14)
+                        .style(Color::from_rgb(0.85, 0.2, 0.2)),
+                );
+            }
+        }
+
+        let scroll_content = Scrollable::new(list)
+            .id(service_list_id())
+            .on_scroll(Message::ListScrolled)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        Container::new(
+            Column::new()
+                .push(header)
+                .push(name_filter_row)
+                .push(status_filter_row)
+                .push(self.saved_filter_row())
+                .push(self.profile_row())
+                .push(self.location_filter_row())
+                .push(self.slice_filter_row())
+                .push(content)
+                .push_maybe(column_header)
+                .push(scroll_content)
+                .spacing(20)
+                .padding(20)
+                .width(Length::Fill)
+                .height(Length::Fill)
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
+
+impl SystemdServiceGui {
+    fn visible_columns(&self) -> Vec<ListColumn> {
+        let mut columns: Vec<ListColumn> = self
+            .config
+            .columns
+            .iter()
+            .filter(|c| c.visible)
+            .map(|c| c.column)
+            .collect();
+
+        // The audit view always needs the enablement columns, whatever the layout.
+        if self.audit_view {
+            for column in [ListColumn::UnitFile, ListColumn::Preset] {
+                if !columns.contains(&column) {
+                    columns.push(column);
+                }
+            }
+        }
+
+        columns
+    }
+
+    fn any_pinned_listed(&self) -> bool {
+        self.services
+            .iter()
+            .any(|service| self.config.pinned.contains(&service.name))
+    }
+
+    fn column_header(&self) -> Row<'_, Message> {
+        self.visible_columns()
+            .into_iter()
+            .fold(Row::new().push(Space::with_width(PIN_WIDTH)), |header, column| {
+                let label = match self.sort {
+                    (sorted, false) if sorted == column => format!("{} ▲", column.label()),
+                    (sorted, true) if sorted == column => format!("{} ▼", column.label()),
+                    _ => column.label().to_string(),
+                };
+                header.push(
+                    Button::new(Text::new(label).size(14))
+                        .padding(0)
+                        .width(Length::Fixed(column.width()))
+                        .on_press(Message::SortBy(column))
+                        .style(self.button_style(theme::Button::Text)),
+                )
+            })
+            .spacing(10)
+            .align_items(Alignment::Center)
+    }
+
+    fn column_cell<'a>(
+        &self,
+        service: &'a ServiceInfo,
+        column: ListColumn,
+        width: f32,
+    ) -> Element<'a, Message> {
+        let cell = match column {
+            ListColumn::Name => {
+                return clipped_cell(display_name(&service.name, self.config.name_style), width)
+            }
+            // Some units set no Description=; say so rather than leave a gap.
+            ListColumn::Description if service.description.trim().is_empty() => {
+                Text::new(t("no_description")).style(Color::from_rgb(0.5, 0.5, 0.5))
+            }
+            ListColumn::Description => {
+                return clipped_cell(Cow::Borrowed(service.description.as_str()), width)
+            }
+            ListColumn::Load => Text::new(service.load_state.as_str()),
+            ListColumn::Active => match self.state_color(service) {
+                Some(color) => Text::new(service.active_state.as_str()).style(color),
+                None => Text::new(service.active_state.as_str()),
+            },
+            ListColumn::Sub => Text::new(service.sub_state.as_str()),
+            ListColumn::UnitFile => Text::new(service.unit_file_state.as_str()),
+            ListColumn::Preset if self.audit_view && service.diverges_from_preset() => {
+                Text::new(service.vendor_preset.as_str()).style(Color::from_rgb(0.9, 0.55, 0.1))
+            }
+            ListColumn::Preset => Text::new(service.vendor_preset.as_str()),
+            ListColumn::LastLog => Text::new(self.last_log_label(service)),
+            ListColumn::Tasks => Text::new(tasks_label(service)),
+            ListColumn::Details => Text::new(details_label(service)),
+        };
+        let cell = cell.width(Length::Fixed(width));
+
+        if column == ListColumn::UnitFile && !service.unit_file_state.is_empty() {
+            tooltip(
+                cell,
+                service.unit_file_state().description(),
+                tooltip::Position::Bottom,
+            )
+            .style(theme::Container::Box)
+            .into()
+        } else {
+            cell.into()
+        }
+    }
+
+    /// Color of the Active column: failed, active, or on its way between the
+    /// two. Inactive units keep the default text color.
+    fn state_color(&self, service: &ServiceInfo) -> Option<Color> {
+        let palette = self.theme().palette();
+        if service.is_failed() {
+            Some(palette.danger)
+        } else if is_transitional(service) {
+            Some(Color::from_rgb(0.9, 0.55, 0.1))
+        } else if service.is_active() {
+            Some(palette.success)
+        } else {
+            None
+        }
+    }
+
+    /// The Ctrl+K palette: a query field and the best matching actions. Enter
+    /// runs the first one, Escape closes it.
+    fn palette_view(&self, query: &str) -> Container<'_, Message> {
+        let matches = palette::rank(query, self.palette_entries(), PALETTE_MATCHES);
+        let mut list = Column::new()
+            .push(
+                text_input(t("palette_placeholder"), query)
+                    .id(palette_input_id())
+                    .on_input(Message::PaletteChanged)
+                    .on_submit(Message::PaletteSubmit)
+                    .padding(8),
+            )
+            .spacing(2);
+        if matches.is_empty() {
+            list = list.push(Text::new(t("palette_no_match")).size(14));
+        }
+        for (index, (label, message)) in matches.into_iter().enumerate() {
+            list = list.push(
+                Button::new(Text::new(label).size(14))
+                    .on_press(Message::PaletteRun(Box::new(message)))
+                    .width(Length::Fill)
+                    .style(if index == 0 {
+                        self.primary_style()
+                    } else {
+                        self.button_style(theme::Button::Text)
+                    }),
+            );
+        }
+
+        Container::new(list)
+            .padding(10)
+            .width(Length::Fill)
+            .style(theme::Container::Box)
+    }
+
+    /// Everything the palette can run: app-wide actions and filters first,
+    /// then selecting and starting/stopping/restarting each unit.
+    fn palette_entries(&self) -> Vec<(String, Message)> {
+        let mut entries = vec![
+            (t("refresh").to_string(), Message::RefreshServices),
+            (t("next_failed").to_string(), Message::GoToNextFailed),
+            (t("clear_filters").to_string(), Message::ClearFilters),
+            (t("settings").to_string(), Message::ToggleSettings),
+            (t("audit").to_string(), Message::ToggleAuditView),
+            (t("legend").to_string(), Message::ToggleLegend),
+        ];
+        if !self.offline() {
+            entries.push((t("update_states").to_string(), Message::RefreshStates));
+        }
+        if self.system_live() {
+            entries.push((t("daemon_reload").to_string(), Message::DaemonReload));
+        }
+        for (label, filter) in [
+            ("running", StatusFilter::Running),
+            ("exited", StatusFilter::Exited),
+            ("dead", StatusFilter::Dead),
+            ("active", StatusFilter::Active),
+            ("inactive", StatusFilter::Inactive),
+            ("failed", StatusFilter::Failed),
+        ] {
+            entries.push((
+                t_args("palette_filter", &[&label]),
+                Message::ToggleStatusFilter(filter),
+            ));
+        }
+
+        for service in &self.services {
+            let name = &service.name;
+            entries.push((
+                t_args("palette_select", &[name]),
+                Message::SelectService(name.clone()),
+            ));
+            // The same actions as the row menu, and none while the row's
+            // buttons are disabled.
+            if self.offline() || self.in_flight.contains_key(name) || self.jobs.contains_key(name)
+            {
+                continue;
+            }
+            for action in UnitAction::available(service, self.scope) {
+                let message = action.message()(name.clone());
+                entries.push((format!("{} {}", action.label(), name), message));
+            }
+        }
+        entries
+    }
+
+    /// Actions taken this session, newest first, with systemctl's error or
+    /// warning where there was one.
+    fn activity_view(&self) -> Container<'_, Message> {
+        let palette = self.theme().palette();
+        let mut entries = Column::new().push(Text::new(t("activity_title")).size(18)).spacing(4);
+        if self.activity.is_empty() {
+            entries = entries.push(
+                Text::new(t("no_activity"))
+                    .size(14)
+                    .style(Color::from_rgb(0.5, 0.5, 0.5)),
+            );
+        }
+
+        for record in self.activity.iter().rev() {
+            let when = format_relative(record.at);
+            let entry = match &record.result {
+                Ok(None) => Text::new(t_args("activity_ok", &[&when, &record.target()])),
+                Ok(Some(warning)) => {
+                    Text::new(t_args("activity_warning", &[&when, &record.target(), warning]))
+                        .style(Color::from_rgb(0.9, 0.7, 0.1))
+                }
+                Err(e) => Text::new(t_args("activity_failed", &[&when, &record.target(), e]))
+                    .style(palette.danger),
+            };
+            entries = entries.push(entry.size(14));
+        }
+
+        Container::new(entries)
+            .padding(10)
+            .width(Length::Fill)
+            .style(theme::Container::Box)
+    }
+
+    /// Search field over the recent journal of all services, and the matching
+    /// lines, newest first, each with the unit that logged it.
+    fn log_search_view(&self) -> Container<'_, Message> {
+        let can_search = !self.searching_logs && !self.log_search.trim().is_empty();
+        let search_row = Row::new()
+            .push(
+                text_input(t("log_search_placeholder"), &self.log_search)
+                    .id(log_search_input_id())
+                    .on_input(Message::LogSearchChanged)
+                    .on_submit(Message::SearchLogs)
+                    .width(Length::Fill),
+            )
+            .push(
+                Button::new(Text::new(if self.searching_logs { t("searching") } else { t("search") }))
+                    .on_press_maybe(can_search.then_some(Message::SearchLogs))
+                    .style(self.primary_style()),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        let mut panel = Column::new()
+            .push(Text::new(t("log_search")).size(18))
+            .push(Text::new(t_args("log_search_hint", &[&LOG_SEARCH_LINES])).size(14))
+            .push(search_row)
+            .spacing(6);
+
+        panel = match &self.log_matches {
+            None => panel,
+            Some(Err(e)) => panel.push(
+                Text::new(e.as_str())
+                    .size(14)
+                    .style(self.theme().palette().danger),
+            ),
+            Some(Ok(matches)) if matches.is_empty() => panel.push(
+                Text::new(t("log_search_no_match"))
+                    .size(14)
+                    .style(Color::from_rgb(0.5, 0.5, 0.5)),
+            ),
+            Some(Ok(matches)) => {
+                let units: HashSet<&str> = matches.iter().map(|m| m.unit.as_str()).collect();
+                let rows = matches.iter().fold(Column::new().spacing(2), |rows, m| {
+                    rows.push(
+                        Row::new()
+                            .push(
+                                Button::new(Text::new(m.unit.as_str()).size(12))
+                                    .padding(0)
+                                    .on_press(Message::SelectService(m.unit.clone()))
+                                    .style(theme::Button::Text)
+                                    .width(Length::Fixed(240.0)),
+                            )
+                            .push(
+                                Text::new(m.line.as_str())
+                                    .size(12)
+                                    .font(Font::MONOSPACE)
+                                    .width(Length::Fill),
+                            )
+                            .spacing(10),
+                    )
+                });
+                let summary = t_args("log_search_found", &[&matches.len(), &units.len()]);
+                panel
+                    .push(Text::new(summary).size(14))
+                    .push(Scrollable::new(rows).height(Length::Fixed(240.0)).width(Length::Fill))
+            }
+        };
+
+        Container::new(panel)
+            .padding(10)
+            .width(Length::Fill)
+            .style(theme::Container::Box)
+    }
+
+    fn export_view<'a>(&self, export: &'a ExportPanel) -> Container<'a, Message> {
+        let can_export = !export.exporting && !export.dir.trim().is_empty();
+        let dir_row = Row::new()
+            .push(
+                text_input(t("export_dir_placeholder"), &export.dir)
+                    .on_input(Message::ExportDirChanged)
+                    .on_submit(Message::ExportUnits)
+                    .width(Length::Fill),
+            )
+            .push(
+                Button::new(Text::new(if export.exporting { t("exporting") } else { t("export") }))
+                    .on_press_maybe(can_export.then_some(Message::ExportUnits))
+                    .style(self.primary_style()),
+            )
+            .push(
+                Button::new(Text::new(t("close")))
+                    .on_press(Message::CloseExport)
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        let mut panel = Column::new()
+            .push(Text::new(t_args("export_units", &[&export.units.len()])).size(18))
+            .push(Text::new(t("export_hint")).size(14))
+            .push(dir_row)
+            .spacing(6);
+
+        if let Some(report) = &export.report {
+            panel = panel.push(
+                Text::new(t_args("export_copied", &[&report.copied.len(), &export.dir.trim()]))
+                    .size(14),
+            );
+            let danger = self.theme().palette().danger;
+            let files = report.failed.iter().fold(Column::new().spacing(2), |files, (path, e)| {
+                let line = t_args("export_failed", &[&path.display(), e]);
+                files.push(Text::new(line).size(12).style(danger))
+            });
+            let files = report.copied.iter().fold(files, |files, path| {
+                files.push(Text::new(path.display().to_string()).size(12).font(Font::MONOSPACE))
+            });
+            panel = panel.push(
+                Scrollable::new(files)
+                    .height(Length::Fixed(160.0))
+                    .width(Length::Fill),
+            );
+        }
+
+        Container::new(panel)
+            .padding(10)
+            .width(Length::Fill)
+            .style(theme::Container::Box)
+    }
+
+    /// Output of the last custom action run from a row menu.
+    fn custom_output_view<'a>(&'a self, run: &'a CustomRun) -> Container<'a, Message> {
+        let title = Row::new()
+            .push(
+                Text::new(t_args("custom_action_output", &[&run.action, &run.unit]))
+                    .size(18)
+                    .width(Length::Fill),
+            )
+            .push(
+                Button::new(Text::new(t("close")))
+                    .on_press(Message::CloseCustomOutput)
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+            .align_items(Alignment::Center);
+
+        let output = match &run.result {
+            None => Text::new(t("loading")).size(14),
+            Some(Ok(output)) if output.trim().is_empty() => Text::new(t("custom_action_no_output"))
+                .size(14)
+                .style(Color::from_rgb(0.5, 0.5, 0.5)),
+            Some(Ok(output)) => Text::new(output.as_str()).size(12).font(Font::MONOSPACE),
+            Some(Err(e)) => Text::new(e.as_str())
+                .size(12)
+                .font(Font::MONOSPACE)
+                .style(self.theme().palette().danger),
+        };
+
+        Container::new(
+            Column::new()
+                .push(title)
+                .push(Scrollable::new(output).height(Length::Fixed(240.0)).width(Length::Fill))
+                .spacing(6),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .style(theme::Container::Box)
+    }
+
+    /// Units by startup time during this boot, each with a bar scaled to the
+    /// slowest one.
+    fn boot_analysis_view(&self) -> Container<'_, Message> {
+        let mut panel = Column::new()
+            .push(Text::new(t("boot_analysis")).size(18))
+            .spacing(6);
+
+        panel = match &self.boot_blame {
+            None => panel.push(Text::new(t("loading")).size(14)),
+            Some(Err(e)) => panel.push(
+                Text::new(e.as_str())
+                    .size(14)
+                    .style(self.theme().palette().danger),
+            ),
+            Some(Ok(units)) => {
+                let slowest = units.first().map_or(1.0, |(_, time)| time.as_secs_f32().max(0.001));
+                let rows = units.iter().fold(Column::new().spacing(2), |rows, (unit, time)| {
+                    rows.push(
+                        Row::new()
+                            .push(
+                                Text::new(format!("{:.2?}", time))
+                                    .size(12)
+                                    .font(Font::MONOSPACE)
+                                    .width(Length::Fixed(90.0)),
+                            )
+                            .push(
+                                progress_bar(0.0..=slowest, time.as_secs_f32())
+                                    .height(Length::Fixed(10.0))
+                                    .width(Length::Fixed(200.0)),
+                            )
+                            .push(
+                                Button::new(Text::new(unit.as_str()).size(12))
+                                    .padding(0)
+                                    .on_press(Message::SelectService(unit.clone()))
+                                    .style(theme::Button::Text),
+                            )
+                            .spacing(10)
+                            .align_items(Alignment::Center),
+                    )
+                });
+                panel
+                    .push(Text::new(t_args("boot_analysis_summary", &[&units.len()])).size(14))
+                    .push(Scrollable::new(rows).height(Length::Fixed(240.0)).width(Length::Fill))
+            }
+        };
+
+        Container::new(panel)
+            .padding(10)
+            .width(Length::Fill)
+            .style(theme::Container::Box)
+    }
+
+    /// Key to the colors and badges in the list, toggled with "?" in the header.
+    fn legend_view(&self) -> Container<'_, Message> {
+        let palette = self.theme().palette();
+        let entry = |sample: Element<'static, Message>, meaning: &'static str| {
+            Row::new()
+                .push(Container::new(sample).width(Length::Fixed(160.0)))
+                .push(Text::new(meaning).size(14))
+                .spacing(10)
+                .align_items(Alignment::Center)
+        };
+        let grey = Color::from_rgb(0.5, 0.5, 0.5);
+
+        Container::new(
+            Column::new()
+                .push(Text::new(t("legend")).size(18))
+                .push(entry(Text::new("active").style(palette.success).into(), t("legend_active")))
+                .push(entry(Text::new("failed").style(palette.danger).into(), t("legend_failed")))
+                .push(entry(
+                    Text::new("activating").style(Color::from_rgb(0.9, 0.55, 0.1)).into(),
+                    t("legend_transitional"),
+                ))
+                .push(entry(Text::new("inactive").into(), t("legend_inactive")))
+                .push(entry(
+                    Text::new(t("static_label")).size(14).style(grey).into(),
+                    t("legend_static"),
+                ))
+                .push(entry(
+                    Text::new(t("masked_label")).size(14).style(palette.danger).into(),
+                    t("legend_masked"),
+                ))
+                .push(entry(Text::new(t("stop_and_disable")).into(), t("legend_enabled")))
+                .push(entry(Text::new(t("enable_and_start")).into(), t("legend_disabled")))
+                .push(entry(
+                    Container::new(Text::new(t("legend_highlight_sample")))
+                        .style(theme::Container::Custom(Box::new(ChangeHighlight(1.0))))
+                        .into(),
+                    t("legend_highlight"),
+                ))
+                .spacing(6),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .style(theme::Container::Box)
+    }
+
+    /// The rows of the service list: pinned units first, whatever the
+    /// filters, then the filtered services in display order (by slice when
+    /// grouped), with template instances folded by `push_unit_rows`.
+    fn list_rows(&self) -> Vec<ListRow<'_>> {
+        let mut rows = Vec::new();
+        let pinned: Vec<&ServiceInfo> = self
+            .services
+            .iter()
+            .filter(|service| self.config.pinned.contains(&service.name))
+            .collect();
+        if !pinned.is_empty() {
+            rows.push(ListRow::Heading(t("pinned").to_string()));
+            rows.extend(pinned.into_iter().map(|service| ListRow::Unit {
+                service,
+                indented: false,
+            }));
+            rows.push(ListRow::Heading(t("all_services").to_string()));
+        }
+
+        let services: Vec<&ServiceInfo> = self
+            .filtered_services()
+            .into_iter()
+            .filter(|service| !self.config.pinned.contains(&service.name))
+            .collect();
+        if !self.group_by_slice {
+            push_unit_rows(&mut rows, services, &self.expanded_templates);
+            return rows;
+        }
+
+        // Each slice under its own heading, in name order; the display order
+        // is kept within a slice.
+        let mut slices: BTreeMap<&str, Vec<&ServiceInfo>> = BTreeMap::new();
+        for service in services {
+            slices.entry(service.slice.as_str()).or_default().push(service);
+        }
+        for (slice, services) in slices {
+            let heading = if slice.is_empty() { t("no_slice") } else { slice };
+            rows.push(ListRow::Heading(heading.to_string()));
+            push_unit_rows(&mut rows, services, &self.expanded_templates);
+        }
+        rows
+    }
+
+    fn group_row(
+        &self,
+        template: String,
+        instances: &[&ServiceInfo],
+        expanded: bool,
+    ) -> Element<'_, Message> {
+        let failed = instances.iter().filter(|service| service.is_failed()).count();
+        let mut label = t_args(
+            "template_instances",
+            &[&if expanded { "▾" } else { "▸" }, &template, &instances.len()],
+        );
+        if failed > 0 {
+            label = t_args("with_failed", &[&label, &failed]);
+        }
+
+        Button::new(Text::new(label))
+            .on_press(Message::ToggleTemplateGroup(template))
+            .style(self.button_style(theme::Button::Text))
+            .into()
+    }
+
+    fn service_row<'a>(
+        &'a self,
+        service: &'a ServiceInfo,
+        columns: &[ListColumn],
+        indented: bool,
+        striped: bool,
+    ) -> Element<'a, Message> {
+        let pinned = self.config.pinned.contains(&service.name);
+        let pin_button = Button::new(Text::new(if pinned { "★" } else { "☆" }))
+            .on_press(Message::TogglePinned(service.name.clone()))
+            .style(self.button_style(theme::Button::Text))
+            .width(PIN_WIDTH);
+
+        // The indent comes out of the first column, so the cells after it
+        // and the buttons stay in line with the other rows.
+        let indent = if indented { INDENT_WIDTH } else { 0.0 };
+        let service_row = columns.iter().enumerate().fold(
+            Row::new()
+                .push(pin_button)
+                .push_maybe(indented.then(|| Space::with_width(Length::Fixed(indent)))),
+            |row, (index, &column)| {
+                let width = if index == 0 { column.width() - indent } else { column.width() };
+                row.push(self.column_cell(service, column, width))
+            },
+        );
+
+        let service_row = match self.comparison.as_ref().and_then(|b| b.compare(service)) {
+            Some(change) => service_row.push(
+                Text::new(change.label())
+                    .size(14)
+                    .style(change_color(&change))
+                    .width(Length::Fixed(200.0)),
+            ),
+            None => service_row,
+        };
+
+        let service_row = match self.jobs.get(&service.name) {
+            Some(job) => service_row.push(
+                Text::new(format!("{} job {}", job.job_type, job.state))
+                    .size(14)
+                    .style(Color::from_rgb(0.2, 0.5, 0.9))
+                    .width(Length::Fixed(120.0))
+            ),
+            None => service_row,
+        };
+
+        let menu_open = self.row_menu.as_deref() == Some(service.name.as_str());
+        let menu_button =
+            Button::new(Text::new(if self.in_flight.contains_key(&service.name) {
+                "…"
+            } else {
+                "⋯"
+            }))
+            .on_press(Message::ToggleRowMenu(service.name.clone()))
+            .style(if menu_open {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+        let mut service_row = service_row.push(
+            tooltip(menu_button, Text::new(t("row_menu_hint")).size(14), tooltip::Position::Top)
+                .style(theme::Container::Box),
+        );
+
+        if service.is_not_found() {
+            service_row = service_row.push(
+                tooltip(
+                    Button::new(Text::new(t("reload_and_refresh")))
+                        .on_press_maybe(self.system_live().then_some(Message::DaemonReload))
+                        .style(self.button_style(theme::Button::Secondary)),
+                    Text::new(t("not_found_hint")).size(14),
+                    tooltip::Position::Top,
+                )
+                .style(theme::Container::Box),
+            );
+        }
+
+        if let Some(enablement) = self.enablement_cell(service) {
+            service_row = service_row.push(enablement);
+        }
+
+        let explanation = if service.is_failed() {
+            Some(
+                Text::new(failure_summary(service, self.failure_errors.get(&service.name)))
+                    .size(14)
+                    .style(self.theme().palette().danger),
+            )
+        } else {
+            skip_reason(service)
+                .map(str::to_string)
+                .or_else(|| trigger_note(service))
+                .map(|note| Text::new(note).size(14))
+        };
+
+        let is_selected = self.selected.as_deref() == Some(service.name.as_str());
+        mouse_area(
+            Container::new(
+                Column::new()
+                    .push(service_row.spacing(10).align_items(Alignment::Center))
+                    .push_maybe(explanation.map(|text| {
+                        Row::new().push(Space::with_width(PIN_WIDTH)).push(text).spacing(10)
+                    }))
+                    .push_maybe(self.alias_note(&service.name).map(|text| {
+                        Row::new().push(Space::with_width(PIN_WIDTH)).push(text).spacing(10)
+                    }))
+                    .push_maybe(menu_open.then(|| self.row_menu_view(service)))
+                    .spacing(2),
+            )
+            .style(match self.highlight_strength(&service.name) {
+                Some(strength) => {
+                    theme::Container::Custom(Box::new(ChangeHighlight(strength)))
+                }
+                None if is_selected => theme::Container::Box,
+                None if striped => theme::Container::Custom(Box::new(StripedRow)),
+                None => theme::Container::Transparent,
+            }),
+        )
+        .on_press(Message::SelectService(service.name.clone()))
+        .on_right_press(Message::ToggleRowMenu(service.name.clone()))
+        .into()
+    }
+
+    /// The open action menu under a row: the actions `UnitAction::available`
+    /// allows in the unit's state, the user's custom actions, then the
+    /// journal, the override editor and hiding.
+    fn row_menu_view<'a>(&'a self, service: &'a ServiceInfo) -> Row<'a, Message> {
+        let actions = UnitAction::available(service, self.scope).into_iter().fold(
+            Row::new().push(Space::with_width(PIN_WIDTH)),
+            |row, action| {
+                let style = match action {
+                    UnitAction::Start | UnitAction::Stop | UnitAction::Restart => {
+                        self.primary_style()
+                    }
+                    _ => self.button_style(theme::Button::Secondary),
+                };
+                row.push(self.action_button(
+                    action.label(),
+                    action.verbs(),
+                    service,
+                    action.message(),
+                    style,
+                ))
+            },
+        );
+
+        let actions = self.config.custom_actions.iter().fold(actions, |row, action| {
+            row.push(
+                Button::new(Text::new(action.name.as_str()))
+                    .on_press_maybe((!self.offline()).then(|| {
+                        Message::RunCustomAction(action.name.clone(), service.name.clone())
+                    }))
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+        });
+
+        let hidden = self.config.hidden.contains(&service.name);
+        actions
+            .push(
+                Button::new(Text::new(t("logs")))
+                    .on_press_maybe(
+                        self.system_live().then(|| Message::ViewLogs(service.name.clone())),
+                    )
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+            .push(
+                Button::new(Text::new(t("override")))
+                    .on_press_maybe(
+                        self.system_live().then(|| Message::EditOverride(service.name.clone())),
+                    )
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+            .push(
+                Button::new(Text::new(if hidden { t("unhide") } else { t("hide") }))
+                    .on_press(Message::SetHidden(service.name.clone(), !hidden))
+                    .style(self.button_style(theme::Button::Text)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center)
+    }
+
+    /// The other names of a unit whose aliases were folded into its row.
+    fn alias_note(&self, name: &str) -> Option<Text<'_>> {
+        let mut aliases: Vec<&str> = self
+            .aliases
+            .iter()
+            .filter(|(_, target)| *target == name)
+            .map(|(alias, _)| alias.as_str())
+            .collect();
+        if aliases.is_empty() {
+            return None;
+        }
+        aliases.sort_unstable();
+        let note = t_args("also_known_as", &[&aliases.join(", ")]);
+        Some(Text::new(note).size(14).style(Color::from_rgb(0.5, 0.5, 0.5)))
+    }
+
+    /// Details of the selected unit, shown under its row: the unit file and
+    /// its `Documentation=` entries. Web links open in the browser; `man:`
+    /// and other entries are plain text to look up by hand.
+    fn detail_panel<'a>(&'a self, service: &'a ServiceInfo) -> Element<'a, Message> {
+        let watching = self.watched.as_deref() == Some(service.name.as_str());
+        let mut watch_row = Row::new()
+            .push(
+                Button::new(Text::new(if watching { t("stop_watching") } else { t("watch") }))
+                    .on_press_maybe(
+                        self.system_live().then(|| Message::ToggleWatch(service.name.clone())),
+                    )
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if let Some(state) = self.watch_state.filter(|_| watching) {
+            watch_row = watch_row.push(Text::new(t_args("live_state", &[&state.label()])).size(14));
+        }
+        let waiting = self.in_flight.get(&service.name) == Some(&"restart + wait");
+        let can_restart = self.system_live() && !self.in_flight.contains_key(&service.name);
+        watch_row = watch_row.push(
+            Button::new(Text::new(if waiting {
+                t_args("waiting_until_active", &[&self.config.restart_wait_seconds])
+            } else {
+                t("restart_and_wait").to_string()
+            }))
+            .on_press_maybe(can_restart.then(|| Message::RestartAndWait(service.name.clone())))
+            .style(self.button_style(theme::Button::Secondary)),
+        );
+        if !service.fragment_path.is_empty() {
+            let watching_file = self
+                .file_watch
+                .as_ref()
+                .is_some_and(|(unit, _)| *unit == service.name);
+            watch_row = watch_row.push(
+                Button::new(Text::new(if watching_file {
+                    t("stop_watching_file")
+                } else {
+                    t("watch_file")
+                }))
+                .on_press_maybe(
+                    self.system_live().then(|| Message::ToggleFileWatch(service.name.clone())),
+                )
+                .style(self.button_style(theme::Button::Secondary)),
+            );
+        }
+
+        watch_row = watch_row.push(Space::with_width(Length::Fill)).push(
+            Button::new(Text::new(t("close")))
+                .on_press(Message::CloseDetails)
+                .style(self.button_style(theme::Button::Secondary)),
+        );
+
+        let mut panel = Column::new().push(watch_row).spacing(4);
+        if service.is_running() && !self.offline() {
+            panel = panel.push(self.resource_dashboard());
+        }
+
+        if !service.fragment_path.is_empty() {
+            panel = panel.push(
+                Text::new(t_args("unit_file", &[&service.fragment_path])).size(14),
+            );
+        }
+
+        for path in &service.drop_in_paths {
+            panel = panel.push(Text::new(t_args("drop_in", &[path])).size(14));
+        }
+
+        if !service.slice.is_empty() {
+            let cgroup = if service.control_group.is_empty() {
+                t("not_running")
+            } else {
+                service.control_group.as_str()
+            };
+            let label = t_args("slice_cgroup", &[&service.slice, &cgroup]);
+            panel = panel.push(Text::new(label).size(14));
+        }
+
+        if !service.triggered_by.is_empty() {
+            let label = t_args("triggered_by", &[&service.triggered_by.join(", ")]);
+            panel = panel.push(Text::new(label).size(14));
+        }
+
+        if service.documentation.is_empty() {
+            panel = panel.push(
+                Text::new(t("no_documentation"))
+                    .size(14)
+                    .style(Color::from_rgb(0.5, 0.5, 0.5)),
+            );
+        } else {
+            panel = panel.push(Text::new(t("documentation")).size(14));
+            for entry in &service.documentation {
+                panel = panel.push(if is_web_link(entry) {
+                    Element::from(
+                        Button::new(
+                            Text::new(entry.as_str())
+                                .size(14)
+                                .style(Color::from_rgb(0.2, 0.5, 0.9)),
+                        )
+                        .padding(0)
+                        .on_press(Message::OpenDocumentation(entry.clone()))
+                        .style(theme::Button::Text),
+                    )
+                } else {
+                    Text::new(entry.as_str()).size(14).into()
+                });
+            }
+        }
+
+        // Dependencies, journal, properties and the rest are looked up on the
+        // system manager only.
+        if !self.system_live() {
+            if self.scope == ServiceScope::User {
+                panel = panel.push(
+                    Text::new(t("user_unit_details"))
+                        .size(14)
+                        .style(Color::from_rgb(0.5, 0.5, 0.5)),
+                );
+            }
+            return Container::new(panel)
+                .padding([6, 12])
+                .width(Length::Fill)
+                .style(theme::Container::Box)
+                .into();
+        }
+
+        panel = panel.push(Text::new(t("dependents")).size(14));
+        panel = match &self.dependents {
+            None => panel.push(Text::new(t("loading")).size(14)),
+            Some(Err(e)) => panel.push(
+                Text::new(e.as_str())
+                    .size(14)
+                    .style(Color::from_rgb(0.85, 0.2, 0.2)),
+            ),
+            Some(Ok(units)) if units.is_empty() => panel.push(
+                Text::new(t("no_dependents"))
+                    .size(14)
+                    .style(Color::from_rgb(0.5, 0.5, 0.5)),
+            ),
+            Some(Ok(units)) => panel.push(Text::new(units.join(", ")).size(14)),
+        };
+
+        panel = panel
+            .push(self.log_section())
+            .push(self.property_section())
+            .push(self.environment_section())
+            .push(self.limit_section(service));
+
+        Container::new(panel)
+            .padding([6, 12])
+            .width(Length::Fill)
+            .style(theme::Container::Box)
+            .into()
+    }
+
+    /// Journal of the selected unit, scoped by boot and priority from the
+    /// controls above it.
+    fn log_section(&self) -> Column<'_, Message> {
+        let mut controls = Row::new()
+            .push(
+                Button::new(Text::new(if self.show_logs { t("hide_logs") } else { t("show_logs") }))
+                    .on_press(Message::ToggleLogs)
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if !self.show_logs {
+            return Column::new().push(controls);
+        }
+
+        controls = controls.push(
+            checkbox(t("current_boot_only"), self.log_filter.current_boot)
+                .on_toggle(Message::SetLogBoot),
+        );
+        let priorities = std::iter::once((t("all_priorities"), None))
+            .chain(LogPriority::ALL.into_iter().map(|p| (p.as_str(), Some(p))));
+        for (label, priority) in priorities {
+            controls = controls.push(
+                Button::new(Text::new(label).size(14))
+                    .on_press(Message::SetLogPriority(priority))
+                    .style(if self.log_filter.priority == priority {
+                        self.primary_style()
+                    } else {
+                        self.button_style(theme::Button::Secondary)
+                    }),
+            );
+        }
+
+        let logs: Element<'_, Message> = match &self.logs {
+            None => Text::new(t("loading")).size(14).into(),
+            Some(Err(e)) => Text::new(e.as_str())
+                .size(14)
+                .style(Color::from_rgb(0.85, 0.2, 0.2))
+                .into(),
+            Some(Ok(lines)) => Scrollable::new(
+                Text::new(lines.as_str()).size(12).font(Font::MONOSPACE),
+            )
+            .height(Length::Fixed(240.0))
+            .width(Length::Fill)
+            .into(),
+        };
+
+        Column::new().push(controls).push(logs).spacing(6)
+    }
+
+    /// Everything `systemctl show` reports for the selected unit as a name/value
+    /// table, narrowed by a case-insensitive filter on the names.
+    /// Form for changing a resource limit with `systemctl set-property`.
+    fn limit_section(&self, service: &ServiceInfo) -> Column<'_, Message> {
+        let can_apply = self.system_live()
+            && !self.limit_value.trim().is_empty()
+            && !self.in_flight.contains_key(&service.name);
+        let form = Row::new()
+            .push(Text::new(t("set_limit")).size(14))
+            .push(pick_list(
+                LIMIT_PROPERTIES,
+                Some(self.limit_property),
+                Message::LimitPropertyChosen,
+            ))
+            .push(
+                text_input(t("limit_value_placeholder"), &self.limit_value)
+                    .on_input(Message::LimitValueChanged)
+                    .width(Length::Fixed(160.0)),
+            )
+            .push(
+                checkbox(t("limit_runtime"), self.limit_runtime)
+                    .on_toggle(Message::SetLimitRuntime),
+            )
+            .push(
+                Button::new(Text::new(t("apply")))
+                    .on_press_maybe(can_apply.then(|| Message::ApplyLimit(service.name.clone())))
+                    .style(self.button_style(theme::Button::Secondary)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        Column::new()
+            .push(form)
+            .push_maybe(self.limit_error.as_deref().map(|e| {
+                Text::new(e).size(14).style(self.theme().palette().danger)
+            }))
+            .spacing(4)
+    }
+
+    fn property_section(&self) -> Column<'_, Message> {
+        let mut controls = Row::new()
+            .push(
+                Button::new(Text::new(if self.show_properties {
+                    t("hide_properties")
+                } else {
+                    t("show_properties")
+                }))
+                .on_press(Message::ToggleProperties)
+                .style(self.button_style(theme::Button::Secondary)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if !self.show_properties {
+            return Column::new().push(controls);
+        }
+
+        controls = controls.push(
+            text_input(t("property_filter_placeholder"), &self.property_filter)
+                .on_input(Message::PropertyFilterChanged)
+                .width(Length::Fixed(260.0)),
+        );
+
+        let table: Element<'_, Message> = match &self.properties {
+            None => Text::new(t("loading")).size(14).into(),
+            Some(Err(e)) => Text::new(e.as_str())
+                .size(14)
+                .style(Color::from_rgb(0.85, 0.2, 0.2))
+                .into(),
+            Some(Ok(properties)) => {
+                let needle = self.property_filter.trim().to_lowercase();
+                let rows = properties
+                    .iter()
+                    .filter(|(name, _)| name.to_lowercase().contains(&needle))
+                    .fold(Column::new().spacing(2), |rows, (name, value)| {
+                        rows.push(
+                            Row::new()
+                                .push(
+                                    Text::new(name.as_str())
+                                        .size(12)
+                                        .font(Font::MONOSPACE)
+                                        .width(Length::Fixed(260.0)),
+                                )
+                                .push(
+                                    Text::new(value.as_str())
+                                        .size(12)
+                                        .font(Font::MONOSPACE)
+                                        .width(Length::Fill),
+                                )
+                                .spacing(10),
+                        )
+                    });
+                Scrollable::new(rows)
+                    .height(Length::Fixed(320.0))
+                    .width(Length::Fill)
+                    .into()
+            }
+        };
+
+        Column::new().push(controls).push(table).spacing(6)
+    }
+
+    /// `Environment=` and environment file variables of the selected unit.
+    /// A variable set again further down is marked as overridden, and
+    /// credential-like values stay masked until revealed.
+    fn environment_section(&self) -> Column<'_, Message> {
+        let mut controls = Row::new()
+            .push(
+                Button::new(Text::new(if self.show_environment {
+                    t("hide_environment")
+                } else {
+                    t("show_environment")
+                }))
+                .on_press(Message::ToggleEnvironment)
+                .style(self.button_style(theme::Button::Secondary)),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center);
+        if !self.show_environment {
+            return Column::new().push(controls);
+        }
+
+        let gray = Color::from_rgb(0.5, 0.5, 0.5);
+        let danger = self.theme().palette().danger;
+        let body: Element<'_, Message> = match &self.environment {
+            None => Text::new(t("loading")).size(14).into(),
+            Some(Err(e)) => Text::new(e.as_str()).size(14).style(danger).into(),
+            Some(Ok(environment)) => {
+                if environment.vars.iter().any(EnvVar::is_sensitive) {
+                    controls = controls.push(
+                        checkbox(t("reveal_secrets"), self.reveal_secrets)
+                            .on_toggle(|_| Message::ToggleRevealSecrets),
+                    );
+                }
+
+                let mut rows = Column::new().spacing(2);
+                if environment.vars.is_empty() && environment.unreadable.is_empty() {
+                    rows = rows.push(Text::new(t("no_environment")).size(14).style(gray));
+                }
+                for (i, var) in environment.vars.iter().enumerate() {
+                    let overridden =
+                        environment.vars[i + 1..].iter().any(|later| later.key == var.key);
+                    let value = if var.is_sensitive() && !self.reveal_secrets {
+                        "••••••••".to_string()
+                    } else {
+                        var.value.clone()
+                    };
+                    let source = var.file.as_deref().unwrap_or("Environment=");
+                    let line = Row::new()
+                        .push(
+                            Text::new(var.key.as_str())
+                                .size(12)
+                                .font(Font::MONOSPACE)
+                                .width(Length::Fixed(260.0)),
+                        )
+                        .push(Text::new(value).size(12).font(Font::MONOSPACE).width(Length::Fill))
+                        .push(
+                            Text::new(if overridden {
+                                t_args("env_overridden", &[&source])
+                            } else {
+                                source.to_string()
+                            })
+                            .size(12)
+                            .style(gray),
+                        )
+                        .spacing(10);
+                    rows = rows.push(line);
+                }
+                for (path, e) in &environment.unreadable {
+                    rows = rows.push(
+                        Text::new(t_args("env_file_unreadable", &[path, e]))
+                            .size(12)
+                            .style(danger),
+                    );
+                }
+                Scrollable::new(rows)
+                    .height(Length::Fixed(200.0))
+                    .width(Length::Fill)
+                    .into()
+            }
+        };
+
+        Column::new().push(controls).push(body).spacing(6)
+    }
+
+    /// A per-row action button. It is disabled while the unit has a queued
+    /// systemd job (a new one would be rejected or reordered) or while one of
+    /// our own commands for it is still running. Hovering shows the equivalent
+    /// `systemctl <verb> <unit>` commands, one per verb, and right-clicking
+    /// copies them.
+    fn action_button<'a>(
+        &self,
+        label: &'a str,
+        verbs: &[&str],
+        service: &ServiceInfo,
+        message: fn(String) -> Message,
+        style: theme::Button,
+    ) -> Element<'a, Message> {
+        let busy = self.in_flight.contains_key(&service.name);
+        let available = !busy && !self.offline() && !self.jobs.contains_key(&service.name);
+        let command = verbs
+            .iter()
+            .map(|verb| {
+                SystemctlCommand::new(verb)
+                    .target(remote_target())
+                    .scope(self.scope)
+                    .extra(&self.config.systemctl_args)
+                    .unit(&service.name)
+                    .command_line()
+            })
+            .collect::<Vec<_>>()
+            .join(" && ");
+
+        let button = Button::new(Text::new(if busy { "…" } else { label }))
+            .on_press_maybe(available.then(|| message(service.name.clone())))
+            .style(style);
+
+        tooltip(
+            mouse_area(button).on_right_press(Message::CopyCommand(command.clone())),
+            Text::new(t_args("command_hint", &[&command])).size(14),
+            tooltip::Position::Top,
+        )
+        .style(theme::Container::Box)
+        .into()
+    }
+
+    /// A label for units that can't be enabled or disabled: static units have
+    /// no [Install] section and masked ones can't be started at all. The
+    /// controls for the others are in the row's action menu.
+    fn enablement_cell(&self, service: &ServiceInfo) -> Option<Element<'_, Message>> {
+        match service.unit_file_state() {
+            UnitFileState::Static => Some(
+                tooltip(
+                    Text::new(t("static_label"))
+                        .size(14)
+                        .style(Color::from_rgb(0.5, 0.5, 0.5)),
+                    Text::new(t("static_tooltip")).size(14),
+                    tooltip::Position::Top,
+                )
+                .style(theme::Container::Box)
+                .into(),
+            ),
+            UnitFileState::Masked | UnitFileState::MaskedRuntime => Some(
+                tooltip(
+                    Text::new(t("masked_label"))
+                        .size(14)
+                        .style(self.theme().palette().danger),
+                    Text::new(service.unit_file_state().description()).size(14),
+                    tooltip::Position::Top,
+                )
+                .style(theme::Container::Box)
+                .into(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn primary_style(&self) -> theme::Button {
+        // The high-contrast palette's primary wins over a custom accent.
+        match self.config.accent {
+            Some(rgb) if !self.config.high_contrast => {
+                theme::Button::custom(AccentButton(accent_color(rgb)))
+            }
+            _ => self.button_style(theme::Button::Primary),
+        }
+    }
+
+    fn button_style(&self, base: theme::Button) -> theme::Button {
+        if self.config.high_contrast {
+            theme::Button::custom(OutlinedButton(base))
+        } else {
+            base
+        }
+    }
+
+    /// "updated 15s ago", orange once the list is older than `STALE_AFTER`.
+    fn last_refresh_label(&self) -> Element<'_, Message> {
+        let Some(loaded) = self.last_refresh else {
+            return Row::new().into();
+        };
+
+        let elapsed = loaded.elapsed();
+        let label = Text::new(t_args("updated_ago", &[&format_elapsed(elapsed.as_secs())])).size(14);
+        if elapsed > STALE_AFTER {
+            label.style(Color::from_rgb(0.9, 0.55, 0.1)).into()
+        } else {
+            label.into()
+        }
+    }
+
+    /// "running as root" / "running as <user>"; for non-root users the tooltip
+    /// warns that acting on system units needs polkit authorization.
+    fn identity_badge(&self) -> Element<'_, Message> {
+        let Some(identity) = &self.identity else {
+            return Row::new().into();
+        };
+
+        let badge = Container::new(
+            Text::new(if identity.is_root() {
+                t("running_as_root").to_string()
+            } else {
+                t_args("running_as_user", &[&identity.name])
+            })
+            .size(14),
+        )
+        .padding([2, 8])
+        .style(theme::Container::Box);
+
+        if identity.is_root() {
+            badge.into()
+        } else {
+            tooltip(
+                badge,
+                Text::new(t("escalation_hint")).size(14),
+                tooltip::Position::Bottom,
+            )
+            .style(theme::Container::Box)
+            .into()
+        }
+    }
+
+    fn confirmation_view(&self, confirmation: &Confirmation) -> Container<'_, Message> {
+        let (prompt, confirm) = match confirmation {
+            Confirmation::DaemonReexec => (t("confirm_reexec").to_string(), t("daemon_reexec")),
+            Confirmation::ReloadAndRestart(unit) => {
+                (t_args("confirm_reload_restart", &[unit]), t("reload_and_restart"))
+            }
+            Confirmation::RestartMatching(units) => (
+                t_args("confirm_restart_matching", &[&units.len(), &units.join(", ")]),
+                t("restart"),
+            ),
+            Confirmation::ReloadAll(units) => (
+                t_args("confirm_reload_all", &[&units.len(), &units.join(", ")]),
+                t("reload_all"),
+            ),
+            Confirmation::Stop(unit, Ok(dependents)) if dependents.is_empty() => {
+                (t_args("confirm_stop", &[unit]), t("stop"))
+            }
+            Confirmation::Stop(unit, Ok(dependents)) => (
+                t_args("confirm_stop_dependents", &[unit, &dependents.join(", ")]),
+                t("stop"),
+            ),
+            Confirmation::Stop(unit, Err(e)) => {
+                (t_args("confirm_stop_unchecked", &[unit, e]), t("stop"))
+            }
+            Confirmation::Restart(unit, action) => {
+                (t_args("confirm_restart", &[unit]), action.label())
+            }
+            Confirmation::UnitFileChange(unit, action, changes) => {
+                let prompt = match changes {
+                    Ok(changes) => {
+                        let lines: Vec<String> = changes.iter().map(ToString::to_string).collect();
+                        t_args("confirm_symlink_changes", &[unit, &lines.join("\n")])
+                    }
+                    Err(e) => t_args("confirm_symlinks_unchecked", &[unit, e]),
+                };
+                let confirm = match action {
+                    UnitFileAction::Enable => t("enable"),
+                    UnitFileAction::Disable => t("disable"),
+                    UnitFileAction::EnableAndStart => t("enable_and_start"),
+                    UnitFileAction::StopAndDisable => t("stop_and_disable"),
+                };
+                (prompt, confirm)
+            }
+            Confirmation::SessionUnit(unit, action) => (
+                t_args("confirm_session_unit", &[unit]),
+                match action {
+                    SessionAction::Stop => t("stop"),
+                    SessionAction::Restart => t("restart"),
+                    SessionAction::TryRestart => t("try_restart"),
+                    SessionAction::RestartAndWait => t("restart_and_wait"),
+                    SessionAction::StopAndDisable => t("stop_and_disable"),
+                },
+            ),
+            Confirmation::StopProfile(profile, units, session) if session.is_empty() => {
+                (t_args("confirm_stop_profile", &[profile, &units.join(", ")]), t("stop"))
+            }
+            Confirmation::StopProfile(profile, units, session) => (
+                t_args(
+                    "confirm_stop_profile_session",
+                    &[profile, &units.join(", "), &session.join(", ")],
+                ),
+                t("stop"),
+            ),
+        };
+
+        let mut row = Row::new().push(Text::new(prompt).width(Length::Fill));
+        let confirmed = match confirmation {
+            Confirmation::SessionUnit(unit, _) => {
+                row = row.push(
+                    text_input(unit, &self.confirmation_text)
+                        .on_input(Message::ConfirmationTyped)
+                        .width(Length::Fixed(220.0)),
+                );
+                self.confirmation_text.trim() == unit
+            }
+            Confirmation::StopProfile(profile, _, session) if !session.is_empty() => {
+                row = row.push(
+                    text_input(profile, &self.confirmation_text)
+                        .on_input(Message::ConfirmationTyped)
+                        .width(Length::Fixed(220.0)),
+                );
+                self.confirmation_text.trim() == profile
+            }
+            _ => true,
+        };
+
+        Container::new(
+            row
+                .push(
+                    Button::new(Text::new(confirm))
+                        .on_press_maybe(confirmed.then_some(Message::Confirm))
+                        .style(self.button_style(theme::Button::Destructive)),
+                )
+                .push(
+                    Button::new(Text::new(t("cancel")))
+                        .on_press(Message::CancelConfirmation)
+                        .style(self.button_style(theme::Button::Secondary)),
+                )
+                .spacing(10)
+                .align_items(Alignment::Center),
+        )
+        .padding(10)
+        .style(theme::Container::Box)
+    }
+
+    fn override_view<'a>(&self, editor: &'a OverrideEditor) -> Column<'a, Message> {
+        let mut panel = Column::new()
+            .push(Text::new(t_args("override_for", &[&editor.unit])).size(20))
+            .push(Text::new(override_path(&editor.unit).display().to_string()).size(14))
+            .push(
+                text_editor(&editor.content)
+                    .on_action(Message::OverrideEdited)
+                    .height(Length::Fixed(320.0)),
+            )
+            .spacing(10);
+
+        if let Some(error) = &editor.error {
+            panel = panel.push(Text::new(error.as_str()).style(Color::from_rgb(0.85, 0.2, 0.2)));
+        }
+
+        panel.push(
+            Row::new()
+                .push(
+                    Button::new(Text::new(if editor.saving { t("saving") } else { t("save_and_reload") }))
+                        .on_press_maybe((!editor.saving).then_some(Message::SaveOverride))
+                        .style(self.primary_style()),
+                )
+                .push(
+                    Button::new(Text::new(t("cancel")))
+                        .on_press(Message::CloseOverrideEditor)
+                        .style(self.button_style(theme::Button::Secondary)),
+                )
+                .spacing(10),
+        )
+    }
+
+    /// A unit's journal in place of the list, newest line last.
+    fn full_log_view<'a>(&self, unit: &'a str, lines: &'a [String]) -> Column<'a, Message> {
+        let entries = lines.iter().fold(Column::new(), |entries, line| {
+            entries.push(Text::new(line.as_str()).size(12).font(Font::MONOSPACE))
+        });
+
+        Column::new()
+            .push(
+                Row::new()
+                    .push(
+                        Button::new(Text::new(t("back")))
+                            .on_press(Message::CloseLogs)
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .push(Text::new(t_args("logs_for", &[&unit])).size(20).width(Length::Fill))
+                    .push(
+                        Button::new(Text::new(t("refresh")))
+                            .on_press(Message::ViewLogs(unit.to_string()))
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+            .push_maybe(lines.is_empty().then(|| Text::new(t("no_log_entries")).size(14)))
+            .push(entries)
+            .spacing(10)
+    }
+
+    fn settings_view(&self) -> Column<'_, Message> {
+        Column::new()
+            .push(self.column_settings())
+            .push(self.theme_settings())
+            .push(self.accent_settings())
+            .push(self.language_settings())
+            .push(self.refresh_settings())
+            .push(self.filter_settings())
+            .push(self.name_settings())
+            .push(self.zoom_settings())
+            .push(self.accessibility_settings())
+            .push(self.systemctl_settings())
+            .push(self.activity_settings())
+            .push(
+                Column::new()
+                    .push(Text::new(t("alerts")).size(20))
+                    .push(
+                        checkbox(t("notify_failures"), self.config.notify_failures)
+                            .on_toggle(Message::SetNotifyFailures),
+                    )
+                    .push(Text::new(t("notify_failures_hint")).size(14))
+                    .spacing(10),
+            )
+            .push(self.confirmation_settings())
+            .push(self.profile_settings())
+            .push(self.custom_action_settings())
+            .push(self.hidden_settings())
+            .push(
+                Column::new()
+                    .push(Text::new(t("bug_reports")).size(20))
+                    .push(Text::new(t("bug_reports_hint")).size(14))
+                    .push(
+                        Button::new(Text::new(t("copy_diagnostics")))
+                            .on_press(Message::CopyDiagnostics)
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10),
+            )
+            .push(
+                Button::new(Text::new(t("close")))
+                    .on_press(Message::ToggleSettings)
+                    .style(self.button_style(theme::Button::Primary)),
+            )
+            .spacing(30)
+    }
+
+    fn theme_settings(&self) -> Column<'_, Message> {
+        let mut settings = Column::new()
+            .push(Text::new(t("theme")).size(20))
+            .push(
+                pick_list(Theme::ALL, Some(self.current_theme.clone()), Message::SetTheme)
+                    .width(Length::Fixed(220.0)),
+            )
+            .spacing(10);
+        if self.config.high_contrast {
+            settings = settings.push(Text::new(t("theme_high_contrast_hint")).size(14));
+        }
+        settings
+    }
+
+    fn accent_settings(&self) -> Column<'_, Message> {
+        let presets = ACCENT_PRESETS.iter().fold(
+            Row::new().push(
+                Button::new(Text::new(t("theme_default")))
+                    .on_press(Message::SetAccent(None))
+                    .style(self.button_style(theme::Button::Secondary)),
+            ),
+            |row, &(name, rgb)| {
+                row.push(
+                    Button::new(Text::new(t(name)))
+                        .on_press(Message::SetAccent(Some(rgb)))
+                        .style(theme::Button::custom(AccentButton(accent_color(rgb)))),
+                )
+            },
+        );
+
+        let rgb = self.config.accent.unwrap_or(ACCENT_PRESETS[0].1);
+        let sliders = ["color_red", "color_green", "color_blue"].iter().enumerate().fold(
+            Column::new().spacing(5),
+            |sliders, (channel, &label)| {
+                sliders.push(
+                    Row::new()
+                        .push(Text::new(t(label)).width(Length::Fixed(60.0)))
+                        .push(
+                            slider(0..=255, rgb[channel], move |value| {
+                                Message::AccentChannelChanged(channel, value)
+                            })
+                            .on_release(Message::SaveConfig)
+                            .width(Length::Fixed(255.0)),
+                        )
+                        .push(Text::new(rgb[channel].to_string()))
+                        .spacing(10)
+                        .align_items(Alignment::Center),
+                )
+            },
+        );
+
+        Column::new()
+            .push(Text::new(t("accent_color")).size(20))
+            .push(Text::new(t("accent_hint")).size(14))
+            .push(presets.spacing(10))
+            .push(Text::new(t("custom")).size(16))
+            .push(sliders)
+            .push(Button::new(Text::new(t("preview"))).style(self.primary_style()))
+            .spacing(10)
+    }
+
+    fn refresh_settings(&self) -> Column<'_, Message> {
+        let selected = Some(self.config.scroll_on_refresh);
+        let poll = STATE_POLL_CHOICES.iter().fold(
+            Row::new().push(Text::new(t("state_poll"))),
+            |row, &seconds| {
+                let label = match seconds {
+                    0 => t("off").to_string(),
+                    seconds => t_args("every_seconds", &[&seconds]),
+                };
+                row.push(radio(
+                    label,
+                    seconds,
+                    Some(self.config.state_poll_seconds),
+                    Message::SetStatePoll,
+                ))
+            },
+        );
+
+        Column::new()
+            .push(Text::new(t("after_refresh")).size(20))
+            .push(Text::new(t("state_poll_hint")).size(14))
+            .push(poll.spacing(15).align_items(Alignment::Center))
+            .push(radio(
+                t("scroll_preserve"),
+                ScrollOnRefresh::Preserve,
+                selected,
+                Message::SetScrollOnRefresh,
+            ))
+            .push(radio(
+                t("scroll_top"),
+                ScrollOnRefresh::Top,
+                selected,
+                Message::SetScrollOnRefresh,
+            ))
+            .spacing(10)
+    }
+
+    fn filter_settings(&self) -> Column<'_, Message> {
+        FilterScope::ALL.iter().fold(
+            Column::new()
+                .push(Text::new(t("filter_scope")).size(20))
+                .spacing(10),
+            |settings, &scope| {
+                settings.push(radio(
+                    scope.label(),
+                    scope,
+                    Some(self.config.filter_scope),
+                    Message::SetFilterScope,
+                ))
+            },
+        )
+    }
+
+    fn name_settings(&self) -> Column<'_, Message> {
+        NameStyle::ALL.iter().fold(
+            Column::new()
+                .push(Text::new(t("name_style")).size(20))
+                .spacing(10),
+            |settings, &style| {
+                settings.push(radio(
+                    style.label(),
+                    style,
+                    Some(self.config.name_style),
+                    Message::SetNameStyle,
+                ))
+            },
+        )
+    }
+
+    fn zoom_settings(&self) -> Column<'_, Message> {
+        Column::new()
+            .push(Text::new(t("zoom")).size(20))
+            .push(Text::new(t("zoom_hint")).size(14))
+            .push(
+                Row::new()
+                    .push(
+                        slider(MIN_ZOOM..=MAX_ZOOM, self.config.zoom_percent, Message::ZoomChanged)
+                            .step(ZOOM_STEP)
+                            .on_release(Message::SaveConfig)
+                            .width(Length::Fixed(255.0)),
+                    )
+                    .push(Text::new(format!("{}%", self.config.zoom_percent)))
+                    .push(
+                        Button::new(Text::new(t("zoom_reset")))
+                            .on_press(Message::ZoomReset)
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+            .spacing(10)
+    }
+
+    fn accessibility_settings(&self) -> Column<'_, Message> {
+        Column::new()
+            .push(Text::new(t("accessibility")).size(20))
+            .push(
+                checkbox(t("high_contrast"), self.config.high_contrast)
+                    .on_toggle(Message::SetHighContrast),
+            )
+            .push(Text::new(t("high_contrast_hint")).size(14))
+            .spacing(10)
+    }
+
+    fn activity_settings(&self) -> Column<'_, Message> {
+        let mut settings = Column::new()
+            .push(Text::new(t("activity_log")).size(20))
+            .push(
+                checkbox(t("log_activity"), self.config.log_activity)
+                    .on_toggle(Message::SetLogActivity),
+            )
+            .spacing(10);
+        if let Some(path) = activity::default_path() {
+            settings = settings.push(Text::new(t_args("log_activity_hint", &[&path.display()])).size(14));
+        }
+        settings
+    }
+
+    /// The hidden units, each with a button to bring it back into the list.
+    /// The saved profiles, each with a remove button, and a form to add one.
+    fn profile_settings(&self) -> Column<'_, Message> {
+        let profiles = self.config.profiles.iter().fold(Column::new().spacing(5), |rows, profile| {
+            rows.push(
+                Row::new()
+                    .push(Text::new(profile.name.as_str()).width(Length::Fixed(150.0)))
+                    .push(Text::new(profile.state.label()).width(Length::Fixed(100.0)))
+                    .push(Text::new(profile.units.join(" ")).size(14).width(Length::Fill))
+                    .push(
+                        Button::new(Text::new("×"))
+                            .on_press(Message::RemoveProfile(profile.name.clone()))
+                            .style(self.button_style(theme::Button::Text)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+        });
+
+        let states = ProfileState::ALL.iter().fold(Row::new().spacing(15), |row, &state| {
+            row.push(radio(
+                state.label(),
+                state,
+                Some(self.new_profile_state),
+                Message::SetProfileState,
+            ))
+        });
+        let can_save = !self.new_profile_name.trim().is_empty()
+            && !self.new_profile_units.trim().is_empty();
+
+        Column::new()
+            .push(Text::new(t("profiles")).size(20))
+            .push(Text::new(t("profiles_hint")).size(14))
+            .push(profiles)
+            .push(
+                Row::new()
+                    .push(
+                        text_input(t("profile_name_placeholder"), &self.new_profile_name)
+                            .on_input(Message::ProfileNameChanged)
+                            .width(Length::Fixed(150.0)),
+                    )
+                    .push(
+                        text_input(t("profile_units_placeholder"), &self.new_profile_units)
+                            .on_input(Message::ProfileUnitsChanged)
+                            .on_submit(Message::SaveProfile),
+                    )
+                    .push(states)
+                    .push(
+                        Button::new(Text::new(t("save_profile")))
+                            .on_press_maybe(can_save.then_some(Message::SaveProfile))
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+            .spacing(10)
+    }
+
+    /// The saved custom actions, each with a remove button, and a form to add
+    /// one.
+    fn custom_action_settings(&self) -> Column<'_, Message> {
+        let saved = &self.config.custom_actions;
+        let actions = saved.iter().fold(Column::new().spacing(5), |rows, action| {
+            rows.push(
+                Row::new()
+                    .push(Text::new(action.name.as_str()).width(Length::Fixed(150.0)))
+                    .push(
+                        Text::new(action.command.as_str())
+                            .size(14)
+                            .font(Font::MONOSPACE)
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        Button::new(Text::new("×"))
+                            .on_press(Message::RemoveCustomAction(action.name.clone()))
+                            .style(self.button_style(theme::Button::Text)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+        });
+
+        let can_save = !self.new_custom_name.trim().is_empty()
+            && !self.new_custom_command.trim().is_empty();
+
+        Column::new()
+            .push(Text::new(t("custom_actions")).size(20))
+            .push(Text::new(t("custom_actions_hint")).size(14))
+            .push(actions)
+            .push(
+                Row::new()
+                    .push(
+                        text_input(t("custom_action_name_placeholder"), &self.new_custom_name)
+                            .on_input(Message::CustomActionNameChanged)
+                            .width(Length::Fixed(150.0)),
+                    )
+                    .push(
+                        text_input(t("custom_action_command_placeholder"), &self.new_custom_command)
+                            .on_input(Message::CustomActionCommandChanged)
+                            .on_submit(Message::SaveCustomAction)
+                            .font(Font::MONOSPACE),
+                    )
+                    .push(
+                        Button::new(Text::new(t("save_custom_action")))
+                            .on_press_maybe(can_save.then_some(Message::SaveCustomAction))
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+            .push_maybe(self.custom_error.as_deref().map(|e| {
+                Text::new(e).size(14).style(self.theme().palette().danger)
+            }))
+            .spacing(10)
+    }
+
+    fn confirmation_settings(&self) -> Column<'_, Message> {
+        let protected =
+            self.config.protected_units.iter().fold(Column::new().spacing(5), |rows, name| {
+                rows.push(
+                    Row::new()
+                        .push(Text::new(name.as_str()).width(Length::Fixed(300.0)))
+                        .push(
+                            Button::new(Text::new("×"))
+                                .on_press(Message::SetProtected(name.clone(), false))
+                                .style(self.button_style(theme::Button::Text)),
+                        )
+                        .spacing(10)
+                        .align_items(Alignment::Center),
+                )
+            });
+        let new_unit = self.new_protected_unit.trim();
+        let can_add = !new_unit.is_empty() && !self.config.protected_units.contains(new_unit);
+        let add = can_add.then(|| Message::SetProtected(new_unit.to_string(), true));
+
+        Column::new()
+            .push(Text::new(t("confirmations")).size(20))
+            .push(
+                checkbox(t("confirm_stop_restart"), self.config.confirm_stop_restart)
+                    .on_toggle(Message::SetConfirmStopRestart),
+            )
+            .push(Text::new(t("protected_units_hint")).size(14))
+            .push(protected)
+            .push(
+                Row::new()
+                    .push(
+                        text_input(t("protected_unit_placeholder"), &self.new_protected_unit)
+                            .on_input(Message::ProtectedUnitChanged)
+                            .width(Length::Fixed(300.0)),
+                    )
+                    .push(
+                        Button::new(Text::new(t("protect_unit")))
+                            .on_press_maybe(add)
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            )
+            .spacing(10)
+    }
+
+    fn hidden_settings(&self) -> Column<'_, Message> {
+        let mut settings = Column::new()
+            .push(Text::new(t("hidden_units")).size(20))
+            .spacing(10);
+        if self.config.hidden.is_empty() {
+            return settings.push(Text::new(t("hidden_units_empty")).size(14));
+        }
+        for name in &self.config.hidden {
+            settings = settings.push(
+                Row::new()
+                    .push(Text::new(name.as_str()).width(Length::Fixed(300.0)))
+                    .push(
+                        Button::new(Text::new(t("unhide")))
+                            .on_press(Message::SetHidden(name.clone(), false))
+                            .style(self.button_style(theme::Button::Secondary)),
+                    )
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            );
+        }
+        settings
+    }
+
+    /// A checkbox per verb for each flag from `EXTRA_FLAGS` that applies to it.
+    fn systemctl_settings(&self) -> Column<'_, Message> {
+        let rows = EXTRA_FLAG_VERBS.iter().fold(Column::new().spacing(5), |rows, &verb| {
+            let flags = EXTRA_FLAGS
+                .iter()
+                .filter(|(_, verbs)| verbs.contains(&verb))
+                .fold(Row::new().spacing(15), |row, &(flag, _)| {
+                    row.push(
+                        checkbox(flag, self.config.systemctl_args.contains(verb, flag))
+                            .on_toggle(move |enabled| Message::SetExtraFlag(verb, flag, enabled)),
+                    )
+                });
+            rows.push(
+                Row::new()
+                    .push(Text::new(verb).width(Length::Fixed(100.0)))
+                    .push(flags)
+                    .align_items(Alignment::Center),
+            )
+        });
+
+        let restart_wait = RESTART_WAIT_CHOICES.iter().fold(
+            Row::new().push(Text::new(t("restart_wait"))),
+            |row, &seconds| {
+                row.push(radio(
+                    t_args("seconds", &[&seconds]),
+                    seconds,
+                    Some(self.config.restart_wait_seconds),
+                    Message::SetRestartWait,
+                ))
+            },
+        );
+
+        Column::new()
+            .push(Text::new(t("systemctl_options")).size(20))
+            .push(Text::new(t("systemctl_options_hint")).size(14))
+            .push(rows)
+            .push(restart_wait.spacing(15).align_items(Alignment::Center))
+            .spacing(10)
+    }
+
+    fn language_settings(&self) -> Column<'_, Message> {
+        let selected = |language: Option<&str>| self.config.language.as_deref() == language;
+        let auto = Button::new(Text::new(t("language_auto")))
+            .on_press(Message::SetLanguage(None))
+            .style(if selected(None) {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            });
+
+        let languages = LOCALES.iter().fold(Row::new().push(auto), |row, locale| {
+            row.push(
+                Button::new(Text::new(locale.name))
+                    .on_press(Message::SetLanguage(Some(locale.code.to_string())))
+                    .style(if selected(Some(locale.code)) {
+                        self.primary_style()
+                    } else {
+                        self.button_style(theme::Button::Secondary)
+                    }),
+            )
+        });
+
+        Column::new()
+            .push(Text::new(t("language")).size(20))
+            .push(languages.spacing(10))
+            .spacing(10)
+    }
+
+    fn column_settings(&self) -> Column<'_, Message> {
+        let mut columns = Column::new()
+            .push(Text::new(t("columns")).size(20))
+            .push(Text::new(t("columns_hint")).size(14))
+            .spacing(10);
+
+        let last = self.config.columns.len().saturating_sub(1);
+        for (index, entry) in self.config.columns.iter().enumerate() {
+            let column = entry.column;
+            let up = Button::new(Text::new("↑"))
+                .on_press_maybe((index > 0).then_some(Message::MoveColumn(column, -1)))
+                .style(self.button_style(theme::Button::Primary));
+            let down = Button::new(Text::new("↓"))
+                .on_press_maybe((index < last).then_some(Message::MoveColumn(column, 1)))
+                .style(self.button_style(theme::Button::Primary));
+
+            columns = columns.push(
+                Row::new()
+                    .push(
+                        checkbox(column.label(), entry.visible)
+                            .on_toggle(move |visible| Message::SetColumnVisible(column, visible))
+                            .width(Length::Fixed(200.0)),
+                    )
+                    .push(up)
+                    .push(down)
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+            );
+        }
+
+        columns
+    }
+
+    fn window_geometry(&mut self) -> &mut WindowGeometry {
+        let default_size = window::Settings::default().size;
+        self.config.window.get_or_insert(WindowGeometry {
+            width: default_size.width as u32,
+            height: default_size.height as u32,
+            x: None,
+            y: None,
+        })
+    }
+
+    fn save_config(&mut self) {
+        if let Err(e) = self.config.save() {
+            self.error = Some(e);
+        }
+    }
+
+    fn status_filter_button<'a>(&self, label: &'a str, filter: StatusFilter) -> Button<'a, Message> {
+        let is_selected = self.status_filter == Some(filter);
+        Button::new(Text::new(label))
+            .on_press(Message::ToggleStatusFilter(filter))
+            .style(if is_selected {
+                self.primary_style()
+            } else {
+                self.button_style(theme::Button::Secondary)
+            })
+    }
+
+    /// "N hidden", switching the hidden units back into the list; `None` when
+    /// none of the listed units is hidden.
+    fn hidden_toggle(&self) -> Option<Button<'_, Message>> {
+        let count = self
+            .services
+            .iter()
+            .filter(|service| self.config.hidden.contains(&service.name))
+            .count();
+        (count > 0).then(|| {
+            Button::new(Text::new(t_args("hidden_count", &[&count])))
+                .on_press(Message::ToggleShowHidden)
+                .style(if self.show_hidden {
+                    self.primary_style()
+                } else {
+                    self.button_style(theme::Button::Secondary)
+                })
+        })
+    }
+
+    /// The user's saved filter chips, each with a remove button, and a field
+    /// to save the current name and status filters as a new one.
+    fn saved_filter_row(&self) -> Row<'_, Message> {
+        let can_save = !self.name_filter.trim().is_empty() || self.status_filter.is_some();
+        if self.config.saved_filters.is_empty() && !can_save {
+            return Row::new();
+        }
+
+        let mut row = self.config.saved_filters.iter().fold(
+            Row::new().push(Text::new(t("saved_filters"))),
+            |row, filter| {
+                let is_selected = self.saved_filter.as_ref() == Some(&filter.name);
+                row.push(
+                    Row::new()
+                        .push(
+                            Button::new(Text::new(filter.name.as_str()))
+                                .on_press(Message::ToggleSavedFilter(filter.name.clone()))
+                                .style(if is_selected {
+                                    self.primary_style()
+                                } else {
+                                    self.button_style(theme::Button::Secondary)
+                                }),
+                        )
+                        .push(
+                            Button::new(Text::new("×"))
+                                .on_press(Message::RemoveSavedFilter(filter.name.clone()))
+                                .style(self.button_style(theme::Button::Text)),
+                        ),
+                )
+            },
+        );
+
+        if can_save {
+            row = row
+                .push(
+                    text_input(t("filter_name_placeholder"), &self.new_filter_name)
+                        .on_input(Message::SavedFilterNameChanged)
+                        .on_submit(Message::SaveFilter)
+                        .width(Length::Fixed(180.0)),
+                )
+                .push(
+                    Button::new(Text::new(t("save_filter")))
+                        .on_press_maybe(
+                            (!self.new_filter_name.trim().is_empty()).then_some(Message::SaveFilter),
+                        )
+                        .style(self.button_style(theme::Button::Secondary)),
+                );
+        }
+
+        row.spacing(10).align_items(Alignment::Center).width(Length::Fill)
+    }
+
+    /// A button per profile that brings its units to the profile's state.
+    /// Empty until a profile has been added in Settings.
+    fn profile_row(&self) -> Row<'_, Message> {
+        self.config
+            .profiles
+            .iter()
+            .fold(Row::new(), |row, profile| {
+                let busy = profile.units.iter().any(|unit| self.in_flight.contains_key(unit));
+                row.push(
+                    Button::new(Text::new(t_args(
+                        "profile_button",
+                        &[&profile.name, &profile.state.label()],
+                    )))
+                    .on_press_maybe(
+                        (!busy && self.system_live())
+                            .then(|| Message::RunProfile(profile.name.clone())),
+                    )
+                    .style(self.button_style(theme::Button::Secondary)),
+                )
+            })
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .width(Length::Fill)
+    }
+
+    /// One toggle per directory that unit files were loaded from, so local
+    /// units (`/etc/systemd/system`) can be told apart from vendor ones.
+    fn location_filter_row(&self) -> Row<'_, Message> {
+        let dirs: BTreeSet<&str> = self.services.iter().filter_map(fragment_dir).collect();
+        if dirs.is_empty() {
+            return Row::new();
+        }
+
+        dirs.into_iter()
+            .fold(Row::new().push(Text::new(t("location"))), |row, dir| {
+                let is_selected = self.location_filter.as_deref() == Some(dir);
+                row.push(
+                    Button::new(Text::new(dir))
+                        .on_press(Message::ToggleLocationFilter(dir.to_string()))
+                        .style(if is_selected {
+                            self.primary_style()
+                        } else {
+                            self.button_style(theme::Button::Secondary)
+                        }),
+                )
+            })
+            .push(
+                Button::new(Text::new(t("has_overrides")))
+                    .on_press(Message::ToggleOverridesOnly)
+                    .style(if self.overrides_only {
+                        self.primary_style()
+                    } else {
+                        self.button_style(theme::Button::Secondary)
+                    }),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .width(Length::Fill)
+    }
+
+    /// One toggle per slice the units run in, to find everything in a custom
+    /// resource slice, and a switch to group the list by slice.
+    fn slice_filter_row(&self) -> Row<'_, Message> {
+        let slices: BTreeSet<&str> = self
+            .services
+            .iter()
+            .map(|service| service.slice.as_str())
+            .filter(|slice| !slice.is_empty())
+            .collect();
+        if slices.is_empty() {
+            return Row::new();
+        }
+
+        slices
+            .into_iter()
+            .fold(Row::new().push(Text::new(t("slice"))), |row, slice| {
+                let is_selected = self.slice_filter.as_deref() == Some(slice);
+                row.push(
+                    Button::new(Text::new(slice))
+                        .on_press(Message::ToggleSliceFilter(slice.to_string()))
+                        .style(if is_selected {
+                            self.primary_style()
+                        } else {
+                            self.button_style(theme::Button::Secondary)
+                        }),
+                )
+            })
+            .push(
+                Button::new(Text::new(t("group_by_slice")))
+                    .on_press(Message::ToggleGroupBySlice)
+                    .style(if self.group_by_slice {
+                        self.primary_style()
+                    } else {
+                        self.button_style(theme::Button::Secondary)
+                    }),
+            )
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .width(Length::Fill)
+    }
+
+    fn filtered_services(&self) -> Vec<&ServiceInfo> {
+        let needle = self.name_filter.trim().to_ascii_lowercase();
+        let mut services: Vec<&ServiceInfo> = self
+            .services
+            .iter()
+            .filter(|service| {
+                let name_ok = self
+                    .filter_fields(service)
+                    .iter()
+                    .any(|field| matches_pattern(&needle, field));
+                let status_ok = self
+                    .status_filter
                     .map(|status| matches_status_filter(service, status))
                     .unwrap_or(true);
+                let location_ok = self
+                    .location_filter
+                    .as_deref()
+                    .is_none_or(|dir| fragment_dir(service) == Some(dir));
+                let overrides_ok = !self.overrides_only || service.has_drop_ins();
+                let slice_ok =
+                    self.slice_filter.as_ref().is_none_or(|slice| service.slice == *slice);
+                let hidden_ok = self.show_hidden || !self.config.hidden.contains(&service.name);
+                let alias_ok = !self.aliases.contains_key(&service.name);
+                let saved_ok = self
+                    .saved_filter
+                    .as_ref()
+                    .and_then(|name| self.config.saved_filters.iter().find(|f| f.name == *name))
+                    .is_none_or(|filter| {
+                        filter.matches_name(&service.name)
+                            && filter.status.is_none_or(|status| matches_status_filter(service, status))
+                    });
+
+                name_ok
+                    && status_ok
+                    && location_ok
+                    && overrides_ok
+                    && slice_ok
+                    && saved_ok
+                    && hidden_ok
+                    && alias_ok
+            })
+            .collect();
+
+        // Ties are broken by name in either direction, so units with equal
+        // values keep their places across refreshes.
+        let (column, descending) = self.sort;
+        services.sort_by(|a, b| {
+            let order = compare_by(column, a, b);
+            let order = if descending { order.reverse() } else { order };
+            order.then_with(|| a.name.cmp(&b.name))
+        });
+        services
+    }
+
+    /// The fields of `service` the name filter is matched against, per the
+    /// scope chosen in Settings.
+    fn filter_fields<'a>(&self, service: &'a ServiceInfo) -> Vec<&'a str> {
+        match self.config.filter_scope {
+            FilterScope::Name => vec![&service.name],
+            FilterScope::NameDescription => vec![&service.name, &service.description],
+            FilterScope::All => vec![&service.name, &service.description, &service.fragment_path],
+        }
+    }
+
+    fn last_log_label(&self, service: &ServiceInfo) -> String {
+        if !self.last_logs_loaded {
+            return String::new();
+        }
+
+        match service.last_log {
+            Some(time) => format_relative(time),
+            None => t_args("older_than_days", &[&LAST_LOG_WINDOW_DAYS]),
+        }
+    }
+
+    /// Merges `systemctl show` results into the list. Units missing from
+    /// `statuses` are left untouched, so a poll of only the shown units
+    /// doesn't blank out the rest. With `update_states`, active/sub states
+    /// are refreshed too and changes get highlighted like on a full reload.
+    fn apply_statuses(&mut self, statuses: Vec<ServiceStatus>, update_states: bool) {
+        let mut statuses: HashMap<String, ServiceStatus> = statuses
+            .into_iter()
+            .map(|status| (status.name.clone(), status))
+            .collect();
+        let now = Instant::now();
+        let mut newly_failed = Vec::new();
+
+        for service in &mut self.services {
+            let Some(status) = statuses.remove(&service.name) else {
+                continue;
+            };
+            service.tasks = status.tasks;
+            service.tasks_max = status.tasks_max;
+            service.main_pid = status.pid;
+            service.memory_current = status.memory;
+            service.cpu_usage_nsec = status.cpu_usage_nsec;
+            service.fragment_path = status.fragment_path;
+            service.documentation = status.documentation;
+            service.drop_in_paths = status.drop_in_paths;
+            service.result = status.result;
+            service.restarts = status.restarts;
+            service.exit_status = status.exit_status;
+            service.condition_met = status.condition_met;
+            service.assert_met = status.assert_met;
+            service.can_reload = status.can_reload;
+            service.slice = status.slice;
+            service.control_group = status.control_group;
+            service.triggered_by = status.triggered_by;
+
+            let reported = !status.active_state.is_empty();
+            let changed = service.active_state != status.active_state
+                || service.sub_state != status.sub_state;
+            if update_states && reported && changed {
+                if status.active_state == "failed" && !service.is_failed() {
+                    newly_failed.push(service.name.clone());
+                }
+                service.active_state = status.active_state;
+                service.sub_state = status.sub_state;
+                self.changed_at.insert(service.name.clone(), now);
+            }
+        }
+        self.aliases = alias_targets(&self.services);
+        self.alert_failures(newly_failed);
+    }
+
+    /// Records units whose state differs from the currently shown list. The
+    /// first load has nothing to compare against and highlights nothing.
+    fn mark_changed(&mut self, services: &[ServiceInfo]) {
+        let previous: HashMap<&str, &ServiceInfo> = self
+            .services
+            .iter()
+            .map(|service| (service.name.as_str(), service))
+            .collect();
+        let now = Instant::now();
+        let mut newly_failed = Vec::new();
+
+        for service in services {
+            let Some(before) = previous.get(service.name.as_str()) else {
+                continue;
+            };
+            if before.active_state != service.active_state || before.sub_state != service.sub_state {
+                self.changed_at.insert(service.name.clone(), now);
+            }
+            if service.is_failed() && !before.is_failed() {
+                newly_failed.push(service.name.clone());
+            }
+        }
+        self.alert_failures(newly_failed);
+    }
+
+    /// Sends a desktop notification for units that went from any other state
+    /// to failed since the last refresh or poll, if enabled in Settings.
+    /// Units that were already failed, or first seen failed, don't alert.
+    fn alert_failures(&self, units: Vec<String>) {
+        if units.is_empty() || !self.config.notify_failures || self.offline() {
+            return;
+        }
+        let summary = match units.as_slice() {
+            [unit] => t_args("alert_unit_failed", &[unit]),
+            units => t_args("alert_units_failed", &[&units.len()]),
+        };
+        // The notification server may take a moment to answer; don't hold
+        // up the update loop for it.
+        std::thread::spawn(move || alert::units_failed(&summary, &units));
+    }
+
+    /// 1.0 right after a change, fading linearly to 0.0.
+    fn highlight_strength(&self, name: &str) -> Option<f32> {
+        let elapsed = self.changed_at.get(name)?.elapsed();
+        let remaining = 1.0 - elapsed.as_secs_f32() / CHANGE_HIGHLIGHT.as_secs_f32();
+        (remaining > 0.0).then_some(remaining)
+    }
+
+    /// Reads when each unit last logged, for the "Last log" column. This
+    /// scans up to a week of journal, so it is skipped while the column is
+    /// hidden or a scan is still running.
+    fn load_last_logs(&mut self) -> Command<Message> {
+        if self.loading_last_logs
+            || !self.system_live()
+            || !self.visible_columns().contains(&ListColumn::LastLog)
+        {
+            return Command::none();
+        }
+        self.loading_last_logs = true;
+        self.follow_ups += 1;
+        let names: Vec<String> = self.services.iter().map(|s| s.name.clone()).collect();
+        Command::perform(
+            blocking(move || last_log_times(&names)),
+            Message::LastLogsLoaded,
+        )
+    }
+
+    fn load_services(&mut self) -> Command<Message> {
+        self.load_generation += 1;
+        self.loading = true;
+        let generation = self.load_generation;
+        let imported = self.imported.clone();
+        // Triage of failed units is the common case; let systemctl do the
+        // filtering instead of listing every unit on the machine.
+        let failed_only = self.status_filter == Some(StatusFilter::Failed);
+        self.loaded_failed_only = failed_only && imported.is_none();
+        let scope = self.scope;
+        Command::perform(
+            blocking(move || match imported {
+                Some(path) => import_services(&path),
+                None => list_services(failed_only.then_some("failed"), scope),
+            }),
+            move |result| Message::ServicesLoaded(generation, result),
+        )
+    }
+
+    /// Reloads the full list if only failed units were loaded and the failed
+    /// filter is no longer on.
+    fn reload_if_partial(&mut self) -> Command<Message> {
+        if self.loaded_failed_only && self.status_filter != Some(StatusFilter::Failed) {
+            self.load_services()
+        } else {
+            Command::none()
+        }
+    }
+
+    /// Viewing an imported file rather than the live system: nothing can be
+    /// acted on, and nothing beyond the file can be looked up.
+    fn offline(&self) -> bool {
+        self.imported.is_some()
+    }
+
+    /// Live and showing local system units. Most lookups (journal,
+    /// dependencies, drop-ins) and manager-wide actions only know the local
+    /// system manager, so they are off for user units, for `--host` and
+    /// `--machine`, and offline.
+    fn system_live(&self) -> bool {
+        !self.offline() && self.scope == ServiceScope::System && remote_target().is_none()
+    }
+
+    fn load_statuses(&mut self) -> Command<Message> {
+        self.follow_ups += 1;
+        let names: Vec<String> = self.services.iter().map(|s| s.name.clone()).collect();
+        let scope = self.scope;
+        Command::perform(
+            blocking(move || {
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                get_statuses(&names, scope)
+            }),
+            Message::StatusesLoaded,
+        )
+    }
+
+    fn load_jobs(&mut self) -> Command<Message> {
+        self.follow_ups += 1;
+        Command::perform(
+            blocking(list_jobs),
+            Message::JobsLoaded,
+        )
+    }
+
+    /// Cheap alternative to `refresh`: re-reads the states of the units
+    /// currently shown with one `systemctl show`, without re-enumerating.
+    fn refresh_states(&mut self) -> Command<Message> {
+        // An imported snapshot must not be overwritten with this host's states.
+        if self.offline() || self.polling_states || self.services.is_empty() {
+            return Command::none();
+        }
+        self.polling_states = true;
+
+        let names: Vec<String> = self
+            .filtered_services()
+            .into_iter()
+            .map(|service| service.name.clone())
+            .collect();
+        let scope = self.scope;
+        Command::perform(
+            blocking(move || {
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                get_statuses(&names, scope)
+            }),
+            Message::StatesPolled,
+        )
+    }
+
+    /// The quick checks only tell active/failed apart, so when they disagree
+    /// with the list the unit's full state is fetched with `show`.
+    fn refresh_watched(&mut self, name: String, state: LiveState) -> Command<Message> {
+        let stale = self.services.iter().any(|service| {
+            service.name == name
+                && (service.is_active() != state.active || service.is_failed() != state.failed)
+        });
+        if !stale || self.polling_states {
+            return Command::none();
+        }
+        self.polling_states = true;
+
+        let scope = self.scope;
+        Command::perform(blocking(move || get_statuses(&[&name], scope)), Message::StatesPolled)
+    }
+
+    /// Reloads the list unless a load or its follow-up lookups are still
+    /// running, so auto-refresh ticks or repeated clicks don't stack up
+    /// systemctl and journalctl calls.
+    fn refresh(&mut self) -> Command<Message> {
+        if self.loading || self.follow_ups > 0 {
+            return Command::none();
+        }
+        self.load_services()
+    }
 
-                name_ok && status_ok
+    /// Off and `AUTO_REFRESH_CHOICES` as a row of small toggle buttons.
+    fn auto_refresh_picker(&self) -> Row<'_, Message> {
+        let choices = std::iter::once((t("off").to_string(), None)).chain(
+            AUTO_REFRESH_CHOICES.iter().map(|&seconds| {
+                (t_args("seconds_short", &[&seconds]), Some(Duration::from_secs(seconds)))
+            }),
+        );
+        let title = Row::new().push(Text::new(t("auto_refresh")).size(14));
+        choices
+            .fold(title, |row, (label, interval)| {
+                row.push(
+                    Button::new(Text::new(label).size(14))
+                        .on_press_maybe(
+                            (!self.offline()).then_some(Message::SetAutoRefresh(interval)),
+                        )
+                        .style(if self.refresh_interval == interval {
+                            self.primary_style()
+                        } else {
+                            self.button_style(theme::Button::Secondary)
+                        }),
+                )
             })
+            .spacing(4)
+            .align_items(Alignment::Center)
+    }
+
+    /// Applies the configured scroll behavior once a reloaded list is in place.
+    /// Preserving re-applies the last offset explicitly, since the new list
+    /// may be laid out differently from the one it replaces.
+    fn restore_scroll(&self) -> Command<Message> {
+        match (self.config.scroll_on_refresh, self.list_viewport) {
+            (ScrollOnRefresh::Top, _) => {
+                scrollable::snap_to(service_list_id(), scrollable::RelativeOffset::START)
+            }
+            (ScrollOnRefresh::Preserve, Some(viewport)) => {
+                scrollable::scroll_to(service_list_id(), viewport.absolute_offset())
+            }
+            (ScrollOnRefresh::Preserve, None) => Command::none(),
+        }
+    }
+
+    /// Selects the next failed unit in the visible list (wrapping around) and
+    /// scrolls it into view.
+    fn go_to_next_failed(&mut self) -> Command<Message> {
+        let rows_before = self.list_rows().len();
+        let visible = self.filtered_services();
+        let failed: Vec<usize> = visible
+            .iter()
+            .enumerate()
+            .filter(|(_, service)| service.is_failed())
+            .map(|(index, _)| index)
+            .collect();
+        if failed.is_empty() {
+            return Command::none();
+        }
+
+        let current = self
+            .selected
+            .as_deref()
+            .and_then(|name| visible.iter().position(|service| service.name == name));
+        let target = failed
+            .iter()
+            .copied()
+            .find(|&index| current.is_none_or(|current| index > current))
+            .unwrap_or(failed[0]);
+        let name = visible[target].name.clone();
+        if let Some(template) = template_name(&name) {
+            self.expanded_templates.insert(template);
+        }
+
+        // Expanding a group may have added rows; find the unit's final line.
+        let rows = self.list_rows();
+        let total = rows.len();
+        let target = rows
+            .iter()
+            .position(|row| matches!(row, ListRow::Unit { service, .. } if service.name == name))
+            .unwrap_or(0);
+        let load_dependents = self.select(name);
+
+        let scroll = match self.list_viewport {
+            // Rows are roughly uniform, so estimate their height from the
+            // content size last reported by the scrollable.
+            Some(viewport) => {
+                let row_height = viewport.content_bounds().height / rows_before.max(1) as f32;
+                let content_height = row_height * total as f32;
+                let max_offset = (content_height - viewport.bounds().height).max(0.0);
+                let y = (row_height * target as f32).min(max_offset);
+                scrollable::scroll_to(service_list_id(), scrollable::AbsoluteOffset { x: 0.0, y })
+            }
+            None => scrollable::snap_to(
+                service_list_id(),
+                scrollable::RelativeOffset {
+                    x: 0.0,
+                    y: target as f32 / total.saturating_sub(1).max(1) as f32,
+                },
+            ),
+        };
+        Command::batch([load_dependents, scroll])
+    }
+
+    fn selected_service(&self) -> Option<&ServiceInfo> {
+        let name = self.selected.as_ref()?;
+        self.services.iter().find(|service| &service.name == name)
+    }
+
+    /// The selected unit's name when a refresh no longer lists it, e.g. after
+    /// its unit file was deleted and the manager reloaded. It stays selected
+    /// so the panel comes back if the unit does. A list of only failed units
+    /// says nothing about the others, so it doesn't count.
+    fn removed_selection(&self) -> Option<&str> {
+        let name = self.selected.as_deref()?;
+        let listed_all = self.last_refresh.is_some() && !self.loaded_failed_only;
+        (!self.loading && listed_all && self.selected_service().is_none()).then_some(name)
+    }
+
+    /// Stores a new resource sample for the selected unit, deriving CPU usage
+    /// from the CPU time consumed since the previous one.
+    fn record_resources(&mut self, statuses: Vec<ServiceStatus>) {
+        let Some(status) = statuses.into_iter().next() else {
+            return;
+        };
+        if self.selected.as_ref() != Some(&status.name) {
+            return;
+        }
+
+        let now = Instant::now();
+        let cpu_percent = self
+            .resources
+            .as_ref()
+            .filter(|previous| previous.unit == status.name)
+            .and_then(|previous| {
+                let used = status.cpu_usage_nsec?.checked_sub(previous.status.cpu_usage_nsec?)?;
+                let elapsed = now.duration_since(previous.taken_at).as_nanos();
+                (elapsed > 0).then(|| used as f64 * 100.0 / elapsed as f64)
+            });
+
+        self.resources = Some(ResourceSample {
+            unit: status.name.clone(),
+            taken_at: now,
+            status,
+            cpu_percent,
+        });
+    }
+
+    /// Memory, CPU and tasks of the selected running unit, refreshed every second.
+    fn resource_dashboard(&self) -> Row<'_, Message> {
+        let sample = self.resources.as_ref();
+        let status = sample.map(|sample| &sample.status);
+        let memory = status
+            .and_then(|status| status.memory)
+            .map(format_bytes)
+            .unwrap_or_else(|| "–".to_string());
+        let cpu = sample
+            .and_then(|sample| sample.cpu_percent)
+            .map(|percent| format!("{:.1} %", percent))
+            .unwrap_or_else(|| "–".to_string());
+        let tasks = match status.map(|status| (status.tasks, status.tasks_max)) {
+            Some((Some(current), Some(max))) => format!("{} / {}", current, max),
+            Some((Some(current), None)) => current.to_string(),
+            _ => "–".to_string(),
+        };
+
+        let card = |label: &'static str, value: String| {
+            Container::new(
+                Column::new()
+                    .push(Text::new(label).size(12))
+                    .push(Text::new(value).size(22))
+                    .spacing(2),
+            )
+            .padding([6, 12])
+            .width(Length::Fixed(140.0))
+            .style(theme::Container::Box)
+        };
+
+        Row::new()
+            .push(card(t("memory"), memory))
+            .push(card(t("cpu"), cpu))
+            .push(card(t("column_tasks"), tasks))
+            .spacing(10)
+    }
+
+    /// Selects a unit, opening its detail panel, and looks up what depends on it.
+    fn select(&mut self, name: String) -> Command<Message> {
+        if self.selected.as_ref() == Some(&name) {
+            return Command::none();
+        }
+        self.selected = Some(name.clone());
+        self.dependents = None;
+        self.resources = None;
+        if !self.system_live() {
+            return Command::none();
+        }
+
+        Command::batch([
+            Command::perform(
+                blocking(move || {
+                    let result = get_reverse_dependencies(&name);
+                    (name, result)
+                }),
+                |(name, result)| Message::DependentsLoaded(name, result),
+            ),
+            self.load_logs(),
+            self.load_properties(),
+            self.load_environment(),
+        ])
+    }
+
+    /// Loaded units in the filtered list when a name filter is set; the ones
+    /// "Restart matching" acts on. Session units are left out, since they
+    /// need their own typed confirmation.
+    fn matching_units(&self) -> Vec<String> {
+        if self.name_filter.trim().is_empty() {
+            return Vec::new();
+        }
+        self.filtered_services()
+            .into_iter()
+            .filter(|service| service.load_state == "loaded")
+            .filter(|service| !self.session_units.contains(&service.name))
+            .map(|service| service.name.clone())
+            .collect()
+    }
+
+    /// Units in the filtered list that have a unit file, for "Export N shown".
+    fn exportable_units(&self) -> Vec<String> {
+        self.filtered_services()
+            .into_iter()
+            .filter(|service| !service.fragment_path.is_empty())
+            .map(|service| service.name.clone())
             .collect()
     }
 
-    fn load_services(&self) -> Command<Message> {
+    fn restart_matching(&mut self, names: Vec<String>) -> Command<Message> {
+        let names: Vec<String> = names
+            .into_iter()
+            .filter(|name| !self.in_flight.contains_key(name))
+            .collect();
+        if names.is_empty() {
+            return Command::none();
+        }
+        for name in &names {
+            self.in_flight.insert(name.clone(), "restart");
+        }
+
+        let extra = self.config.systemctl_args.clone();
         Command::perform(
-            async {
-                list_services()
-            },
-            Message::ServicesLoaded,
+            blocking(move || {
+                let result = restart_services(&names, &extra);
+                (names, result)
+            }),
+            |(names, result)| Message::RestartMatchingFinished(names, result),
         )
     }
 
-    fn refresh(&self) -> Command<Message> {
-        self.load_services()
+    /// Teardown before the window closes: ends every subscription (control
+    /// socket listener, unit and file watchers, polling), removes the socket
+    /// file so the next run doesn't find a stale one, and writes the config.
+    /// Geometry is only written here rather than on every resize/move event
+    /// while the user drags the window.
+    fn shut_down(&mut self) {
+        self.shutting_down = true;
+        if let Some(path) = &self.control_socket {
+            control::remove_socket(path);
+        }
+        self.save_config();
+    }
+
+    /// Active units that support `reload`, the ones "Reload all" acts on.
+    fn reloadable_units(&self) -> Vec<String> {
+        self.services
+            .iter()
+            .filter(|service| service.can_reload && service.is_active())
+            .map(|service| service.name.clone())
+            .collect()
+    }
+
+    /// Runs one of the restart flavours of `Confirmation::Restart` without
+    /// asking again.
+    fn run_restart(&mut self, name: String, action: UnitAction) -> Command<Message> {
+        match action {
+            UnitAction::TryRestart => {
+                self.run_configured_action(name, "try-restart", try_restart_service)
+            }
+            UnitAction::RestartAndWait => self.restart_and_wait_for(name),
+            _ => self.run_scoped_action(name, "restart", restart_service),
+        }
     }
 
-    fn start(&self, name: String) -> Command<Message> {
+    /// Restarts `name` and waits up to `Config::restart_wait_seconds` for it
+    /// to come up, see `Message::RestartWaitFinished`.
+    fn restart_and_wait_for(&mut self, name: String) -> Command<Message> {
+        if self.in_flight.contains_key(&name) {
+            return Command::none();
+        }
+        self.in_flight.insert(name.clone(), "restart + wait");
+        let extra = self.config.systemctl_args.clone();
+        let timeout = Duration::from_secs(self.config.restart_wait_seconds);
         Command::perform(
-            async move {
-                start_service(&name).map(|_| ())
-            },
-            |result| {
-                match result {
-                    Ok(_) => Message::ServicesLoaded(list_services()),
-                    Err(e) => Message::ServicesLoaded(Err(e)),
-                }
-            },
+            blocking(move || {
+                let started = Instant::now();
+                let result = restart_and_wait(&name, &extra, timeout);
+                (name, result, started.elapsed())
+            }),
+            |(name, result, elapsed)| Message::RestartWaitFinished(name, result, elapsed),
         )
     }
 
-    fn stop(&self, name: String) -> Command<Message> {
+    /// Brings the units of profile `name` to its state, once any
+    /// confirmation is out of the way.
+    fn run_profile(&mut self, name: String) -> Command<Message> {
+        let Some(profile) = self.config.profiles.iter().find(|p| p.name == name) else {
+            return Command::none();
+        };
+        let units = profile.units.clone();
+        let state = profile.state;
+        if units.iter().any(|unit| self.in_flight.contains_key(unit)) {
+            return Command::none();
+        }
+        for unit in &units {
+            self.in_flight.insert(unit.clone(), state.verb());
+        }
+        self.notice = Some(t_args("profile_running", &[&name, &units.len()]));
+
+        let extra = self.config.systemctl_args.clone();
+        let active = state == ProfileState::Started;
         Command::perform(
-            async move {
-                stop_service(&name).map(|_| ())
-            },
-            |result| {
-                match result {
-                    Ok(_) => Message::ServicesLoaded(list_services()),
-                    Err(e) => Message::ServicesLoaded(Err(e)),
-                }
-            },
+            blocking(move || set_units_active(&units, active, &extra)),
+            move |results| Message::ProfileFinished(name, results),
         )
     }
 
-    fn restart(&self, name: String) -> Command<Message> {
+    /// Whether a stop or restart of `name` has to be confirmed first.
+    fn needs_confirmation(&self, name: &str) -> bool {
+        self.config.confirm_stop_restart || self.config.is_protected(name)
+    }
+
+    /// Stops `name` right away if nothing else goes down with it and no
+    /// confirmation is needed, and asks first otherwise.
+    fn confirm_stop(
+        &mut self,
+        name: String,
+        dependents: Result<Vec<String>, String>,
+    ) -> Command<Message> {
+        if dependents.as_ref().is_ok_and(Vec::is_empty) && !self.needs_confirmation(&name) {
+            return self.run_scoped_action(name, "stop", stop_service);
+        }
+        self.pending_confirmation = Some(Confirmation::Stop(name, dependents));
+        Command::none()
+    }
+
+    fn confirm_session_action(&mut self, name: String, action: SessionAction) -> Command<Message> {
+        self.confirmation_text.clear();
+        self.pending_confirmation = Some(Confirmation::SessionUnit(name, action));
+        Command::none()
+    }
+
+    /// Fetches the last few error lines of every failed unit, best effort:
+    /// units whose journal can't be read just go without.
+    fn load_failure_errors(&mut self) -> Command<Message> {
+        let failed: Vec<String> = self
+            .services
+            .iter()
+            .filter(|service| service.is_failed())
+            .map(|service| service.name.clone())
+            .collect();
+        if failed.is_empty() || !self.system_live() {
+            return Command::none();
+        }
+
+        self.follow_ups += 1;
         Command::perform(
-            async move {
-                restart_service(&name).map(|_| ())
-            },
-            |result| {
-                match result {
-                    Ok(_) => Message::ServicesLoaded(list_services()),
-                    Err(e) => Message::ServicesLoaded(Err(e)),
-                }
-            },
+            blocking(move || {
+                failed
+                    .into_iter()
+                    .filter_map(|name| {
+                        let errors = recent_errors(&name, FAILURE_ERROR_LINES).ok()?;
+                        Some((name, errors))
+                    })
+                    .collect()
+            }),
+            Message::FailureErrorsLoaded,
+        )
+    }
+
+    /// Reloads the selected unit's journal with the current scope, if the
+    /// log section is open.
+    fn load_logs(&mut self) -> Command<Message> {
+        self.logs = None;
+        let Some(name) = self.selected.clone().filter(|_| self.show_logs && self.system_live())
+        else {
+            return Command::none();
+        };
+        let filter = self.log_filter;
+
+        Command::perform(
+            blocking(move || {
+                let result = get_service_logs(&name, filter);
+                (name, result)
+            }),
+            move |(name, result)| Message::LogsLoaded(name, filter, result),
+        )
+    }
+
+    /// Reloads all properties of the selected unit, if the property table is
+    /// open.
+    fn load_properties(&mut self) -> Command<Message> {
+        self.properties = None;
+        let Some(name) =
+            self.selected.clone().filter(|_| self.show_properties && self.system_live())
+        else {
+            return Command::none();
+        };
+
+        Command::perform(
+            blocking(move || {
+                let result = get_properties(&name);
+                (name, result)
+            }),
+            |(name, result)| Message::PropertiesLoaded(name, result),
+        )
+    }
+
+    /// Reloads the selected unit's environment, if that section is open.
+    /// Values are hidden again for each load.
+    fn load_environment(&mut self) -> Command<Message> {
+        self.environment = None;
+        self.reveal_secrets = false;
+        let Some(name) =
+            self.selected.clone().filter(|_| self.show_environment && self.system_live())
+        else {
+            return Command::none();
+        };
+
+        Command::perform(
+            blocking(move || {
+                let result = get_environment(&name);
+                (name, result)
+            }),
+            |(name, result)| Message::EnvironmentLoaded(name, result),
         )
     }
+
+    /// Runs a per-unit systemctl action in the background. The unit stays in
+    /// `in_flight` (and its buttons disabled) until `ActionFinished` arrives.
+    fn run_unit_action(
+        &mut self,
+        name: String,
+        label: &'static str,
+        action: impl FnOnce(&str) -> ActionResult + Send + 'static,
+    ) -> Command<Message> {
+        if self.in_flight.contains_key(&name) {
+            return Command::none();
+        }
+        self.in_flight.insert(name.clone(), label);
+
+        Command::perform(
+            blocking(move || {
+                let result = action(&name);
+                (name, result)
+            }),
+            |(name, result)| Message::ActionFinished(name, result),
+        )
+    }
+
+    /// Asks systemctl which symlinks `action` would change before running it;
+    /// see `Message::UnitFilePreviewLoaded`.
+    fn preview_unit_file_change(
+        &mut self,
+        name: String,
+        action: UnitFileAction,
+    ) -> Command<Message> {
+        let extra = self.config.systemctl_args.clone();
+        Command::perform(
+            blocking(move || {
+                let result = preview_unit_file_change(action.verb(), &name, &extra);
+                (name, result)
+            }),
+            move |(name, result)| Message::UnitFilePreviewLoaded(name, action, result),
+        )
+    }
+
+    /// `run_configured_action` for actions sent to the manager of the current
+    /// scope.
+    fn run_scoped_action(
+        &mut self,
+        name: String,
+        label: &'static str,
+        action: fn(&str, &ExtraArgs, ServiceScope) -> ActionResult,
+    ) -> Command<Message> {
+        let extra = self.config.systemctl_args.clone();
+        let scope = self.scope;
+        self.run_unit_action(name, label, move |name| action(name, &extra, scope))
+    }
+
+    /// `run_unit_action` for actions that take the extra flags from Settings.
+    fn run_configured_action(
+        &mut self,
+        name: String,
+        label: &'static str,
+        action: fn(&str, &ExtraArgs) -> ActionResult,
+    ) -> Command<Message> {
+        let extra = self.config.systemctl_args.clone();
+        self.run_unit_action(name, label, move |name| action(name, &extra))
+    }
+
+    /// Adds the finished action to the activity panel and, if enabled, to
+    /// the activity log file.
+    fn record_action(
+        &mut self,
+        unit: Option<String>,
+        action: &'static str,
+        result: &ActionResult,
+    ) -> Command<Message> {
+        let record = ActionRecord::new(unit, action, result);
+        self.activity.push(record.clone());
+        if !self.config.log_activity {
+            return Command::none();
+        }
+
+        Command::perform(
+            blocking(move || {
+                let path = activity::default_path()
+                    .ok_or_else(|| "No data directory available".to_string())?;
+                activity::append(&path, &record)
+            }),
+            Message::ActivityLogged,
+        )
+    }
+
+    fn reset_all_failed(&self) -> Command<Message> {
+        Command::perform(
+            blocking(reset_failed_all),
+            |result| Message::BulkActionFinished("reset-failed", result),
+        )
+    }
+
+    /// A warning still reloads the list, since the action may have partly
+    /// taken effect; only a hard failure leaves it as is.
+    fn finish_action(&mut self, result: ActionResult) -> Command<Message> {
+        match result {
+            Ok(warning) => {
+                self.warning = warning;
+                self.load_services()
+            }
+            Err(e) => {
+                self.error = Some(e);
+                Command::none()
+            }
+        }
+    }
+}
+
+/// Runs a blocking call (systemctl, journalctl, file I/O) on tokio's blocking
+/// pool so it doesn't stall the executor that drives every other command.
+async fn blocking<T, F>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .expect("blocking task panicked")
+}
+
+/// Appends `services` to the list in order, folding instances of the same
+/// template into a group at the position of the first one; the instances
+/// follow it when the template is in `expanded`. A template with a single
+/// instance is shown as a plain row.
+fn push_unit_rows<'a>(
+    rows: &mut Vec<ListRow<'a>>,
+    services: Vec<&'a ServiceInfo>,
+    expanded: &HashSet<String>,
+) {
+    let mut instances: HashMap<String, Vec<&ServiceInfo>> = HashMap::new();
+    for &service in &services {
+        if let Some(template) = template_name(&service.name) {
+            instances.entry(template).or_default().push(service);
+        }
+    }
+
+    for service in services {
+        let template = template_name(&service.name)
+            .filter(|template| instances.get(template).is_some_and(|group| group.len() > 1));
+        let Some(template) = template else {
+            rows.push(ListRow::Unit {
+                service,
+                indented: false,
+            });
+            continue;
+        };
+
+        // Later instances were already emitted with the group.
+        let Some(group) = instances.remove(&template) else {
+            continue;
+        };
+        let is_expanded = expanded.contains(&template);
+        let members = if is_expanded { group.clone() } else { Vec::new() };
+        rows.push(ListRow::Group {
+            template,
+            instances: group,
+            expanded: is_expanded,
+        });
+        rows.extend(members.into_iter().map(|service| ListRow::Unit {
+            service,
+            indented: true,
+        }));
+    }
+}
+
+/// One line on why a failed unit failed: how its last run ended, how often
+/// systemd restarted it, and its most recent error in the journal.
+fn failure_summary(service: &ServiceInfo, errors: Option<&Vec<String>>) -> String {
+    let status = service.exit_status.unwrap_or_default();
+    let mut parts = vec![match service.result.as_str() {
+        "exit-code" => t_args("failed_exit_code", &[&status]),
+        "signal" | "core-dump" => t_args("failed_signal", &[&status]),
+        "timeout" => t("failed_timeout").to_string(),
+        "start-limit-hit" => t("failed_start_limit").to_string(),
+        "" => t("failed_unknown").to_string(),
+        result => t_args("failed_result", &[&result]),
+    }];
+    if let Some(restarts) = service.restarts.filter(|&n| n > 0) {
+        parts.push(t_args("failed_restarts", &[&restarts]));
+    }
+    if let Some(line) = errors.and_then(|errors| errors.last()) {
+        parts.push(t_args("failed_last_error", &[line]));
+    }
+    parts.join("; ")
+}
+
+/// Why an inactive unit didn't run, if systemd skipped it because one of its
+/// `Condition*=` checks wasn't met or refused it on an `Assert*=`.
+fn skip_reason(service: &ServiceInfo) -> Option<&'static str> {
+    if service.is_active() {
+        return None;
+    }
+    match (service.condition_met, service.assert_met) {
+        (Some(false), _) => Some(t("skipped_condition")),
+        (_, Some(false)) => Some(t("skipped_assert")),
+        _ => None,
+    }
+}
+
+/// A list cell for text of any length, such as generated unit names: cut to
+/// what fits on one line of `width` with an ellipsis, the full text in a
+/// tooltip, and clipped, so no value can widen its row or push the buttons
+/// after it out of line.
+fn clipped_cell(text: Cow<'_, str>, width: f32) -> Element<'_, Message> {
+    let max_chars = (width / CELL_CHAR_WIDTH) as usize;
+    if text.chars().count() <= max_chars {
+        return Container::new(Text::new(text))
+            .width(Length::Fixed(width))
+            .clip(true)
+            .into();
+    }
+
+    let short: String = text
+        .chars()
+        .take(max_chars.saturating_sub(1))
+        .chain(['…'])
+        .collect();
+    tooltip(
+        Container::new(Text::new(short)).width(Length::Fixed(width)).clip(true),
+        Text::new(text).size(14),
+        tooltip::Position::Bottom,
+    )
+    .style(theme::Container::Box)
+    .into()
+}
+
+/// `name` as the Name column shows it in `style`; only for display, never
+/// for passing to systemctl.
+fn display_name(name: &str, style: NameStyle) -> Cow<'_, str> {
+    match style {
+        NameStyle::Raw => Cow::Borrowed(name),
+        NameStyle::Unescaped => unescape_unit_name(name),
+        NameStyle::Short => {
+            let short = name.strip_suffix(".service").unwrap_or(name);
+            unescape_unit_name(short)
+        }
+    }
+}
+
+/// For an inactive unit that a timer, socket or path unit starts, that it
+/// is waiting to be triggered rather than broken.
+fn trigger_note(service: &ServiceInfo) -> Option<String> {
+    if service.is_active() || service.triggered_by.is_empty() {
+        return None;
+    }
+    Some(t_args("starts_on_demand", &[&service.triggered_by.join(", ")]))
+}
+
+/// Order of two units by the value shown in `column`. Units without a value
+/// (no log entry, not running) sort first.
+fn compare_by(column: ListColumn, a: &ServiceInfo, b: &ServiceInfo) -> Ordering {
+    match column {
+        ListColumn::Name => a.name.cmp(&b.name),
+        ListColumn::Description => a.description.to_lowercase().cmp(&b.description.to_lowercase()),
+        ListColumn::Load => a.load_state.cmp(&b.load_state),
+        ListColumn::Active => a.active_state.cmp(&b.active_state),
+        ListColumn::Sub => a.sub_state.cmp(&b.sub_state),
+        ListColumn::UnitFile => a.unit_file_state.cmp(&b.unit_file_state),
+        ListColumn::Preset => a.vendor_preset.cmp(&b.vendor_preset),
+        ListColumn::LastLog => a.last_log.cmp(&b.last_log),
+        ListColumn::Tasks => {
+            let tasks = |service: &ServiceInfo| service.tasks.filter(|_| service.is_running());
+            tasks(a).cmp(&tasks(b))
+        }
+        ListColumn::Details => {
+            let memory =
+                |service: &ServiceInfo| service.memory_current.filter(|_| service.is_running());
+            memory(a).cmp(&memory(b))
+        }
+    }
+}
+
+/// Directory of the unit's fragment, `None` for units without a unit file.
+fn fragment_dir(service: &ServiceInfo) -> Option<&str> {
+    if service.fragment_path.is_empty() {
+        return None;
+    }
+    Path::new(&service.fragment_path).parent()?.to_str()
 }
 
 fn matches_status_filter(service: &ServiceInfo, filter: StatusFilter) -> bool {
     match filter {
-        StatusFilter::Running => service.sub_state.eq_ignore_ascii_case("running"),
+        StatusFilter::Running => service.is_running(),
         StatusFilter::Exited => service.sub_state.eq_ignore_ascii_case("exited"),
         StatusFilter::Dead => service.sub_state.eq_ignore_ascii_case("dead"),
-        StatusFilter::Active => service.active_state.eq_ignore_ascii_case("active"),
+        StatusFilter::Active => service.is_active(),
         StatusFilter::Inactive => service.active_state.eq_ignore_ascii_case("inactive"),
+        StatusFilter::Failed => service.is_failed(),
+    }
+}
+
+fn change_color(change: &Change) -> Color {
+    match change {
+        Change::NewlyFailed { .. } => Color::from_rgb(0.85, 0.2, 0.2),
+        Change::NewlyActive { .. } => Color::from_rgb(0.2, 0.65, 0.3),
+        Change::New | Change::StateChanged { .. } => Color::from_rgb(0.9, 0.55, 0.1),
+    }
+}
+
+/// Whether the unit is between states, e.g. `activating` or `reloading`.
+fn is_transitional(service: &ServiceInfo) -> bool {
+    matches!(
+        service.active_state.as_str(),
+        "activating" | "deactivating" | "reloading" | "refreshing"
+    )
+}
+
+fn is_web_link(entry: &str) -> bool {
+    entry.starts_with("http://") || entry.starts_with("https://")
+}
+
+fn log_search_input_id() -> text_input::Id {
+    text_input::Id::new("log-search")
+}
+
+fn palette_input_id() -> text_input::Id {
+    text_input::Id::new("command-palette")
+}
+
+fn service_list_id() -> scrollable::Id {
+    scrollable::Id::new("service-list")
+}
+
+/// "12 / 512" for running units, blank otherwise.
+fn tasks_label(service: &ServiceInfo) -> String {
+    if !service.is_running() {
+        return String::new();
+    }
+
+    match (service.tasks, service.tasks_max) {
+        (Some(current), Some(max)) => format!("{} / {}", current, max),
+        (Some(current), None) => current.to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+/// "PID 812 · 45.2 MiB · 3.1 s CPU" for a running unit, leaving out what
+/// systemd doesn't report (no memory accounting, for one).
+fn details_label(service: &ServiceInfo) -> String {
+    if !service.is_running() {
+        return String::new();
+    }
+
+    let pid = service.main_pid.map(|pid| format!("PID {}", pid));
+    let memory = service
+        .memory_current
+        .map(|bytes| format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)));
+    let cpu = service
+        .cpu_usage_nsec
+        .map(|nsec| format!("{:.1} s CPU", Duration::from_nanos(nsec).as_secs_f64()));
+    [pid, memory, cpu].into_iter().flatten().collect::<Vec<_>>().join(" · ")
+}
+
+/// "512 B", "12.3 MiB", ...
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn format_relative(time: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0);
+    format_elapsed(secs)
+}
+
+fn format_elapsed(secs: u64) -> String {
+    match secs {
+        0..=59 => t_args("seconds_ago", &[&secs]),
+        60..=3599 => t_args("minutes_ago", &[&(secs / 60)]),
+        3600..=86399 => t_args("hours_ago", &[&(secs / 3600)]),
+        _ => t_args("days_ago", &[&(secs / 86400)]),
     }
 }
 
 pub fn main() -> iced::Result {
-    SystemdServiceGui::run(Settings::default())
+    let options = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(cli::Invocation::Run(options)) => options,
+        Ok(cli::Invocation::Help) => {
+            print!("{}", cli::USAGE);
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // Off unless RUST_LOG is set, e.g. RUST_LOG=systemd_service_gui=debug.
+    if std::env::var_os("RUST_LOG").is_some() {
+        tracing_subscriber::fmt()
+            .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+            .with_writer(std::io::stderr)
+            .init();
+    }
+
+    if let Some(host) = &options.host {
+        set_remote_target(RemoteTarget::Host(host.clone()));
+    } else if let Some(machine) = &options.machine {
+        set_remote_target(RemoteTarget::Machine(machine.clone()));
+    }
+
+    if options.check {
+        std::process::exit(run_check(&options));
+    }
+
+    if let Some(addr) = &options.serve {
+        std::process::exit(run_serve(addr));
+    }
+
+    let config = Config::load();
+    i18n::set_locale(config.language.as_deref());
+    // The default font is fixed at startup, so bold text follows a restart.
+    let default_font = if config.high_contrast {
+        Font {
+            weight: font::Weight::Bold,
+            ..Font::DEFAULT
+        }
+    } else {
+        Font::DEFAULT
+    };
+    let mut window = window::Settings {
+        exit_on_close_request: false,
+        ..window::Settings::default()
+    };
+    if let Some(geometry) = config.window {
+        window.size = Size::new(geometry.width as f32, geometry.height as f32);
+        if let (Some(x), Some(y)) = (geometry.x, geometry.y) {
+            window.position = window::Position::Specific(Point::new(x as f32, y as f32));
+        }
+    }
+
+    SystemdServiceGui::run(Settings {
+        window,
+        default_font,
+        ..Settings::with_flags(Flags { options, config })
+    })
+}
+
+/// Runs the HTTP endpoint until it fails to bind; see `serve.rs`.
+#[cfg(feature = "serve")]
+fn run_serve(addr: &str) -> i32 {
+    match serve::run(addr) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            3
+        }
+    }
+}
+
+#[cfg(not(feature = "serve"))]
+fn run_serve(_addr: &str) -> i32 {
+    eprintln!("Error: --serve is not available in this build; rebuild with `--features serve`");
+    2
+}
+
+/// Headless mode for scripts: 0 when nothing failed, 1 when some unit failed,
+/// 3 when the service list could not be read.
+fn run_check(options: &cli::Options) -> i32 {
+    let services = match &options.import {
+        Some(path) => import_services(path),
+        None => list_services(None, ServiceScope::System),
+    };
+    let services = match services {
+        Ok(services) => services,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 3;
+        }
+    };
+
+    let needle = options
+        .name_filter
+        .as_deref()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let matching: Vec<&ServiceInfo> = services
+        .iter()
+        .filter(|service| needle.is_empty() || service.name.to_ascii_lowercase().contains(&needle))
+        .collect();
+    let failed: Vec<&ServiceInfo> = matching.iter().copied().filter(|s| s.is_failed()).collect();
+
+    println!("{} services, {} failed", matching.len(), failed.len());
+    for service in &failed {
+        println!("failed: {} ({})", service.name, service.sub_state);
+    }
+
+    if failed.is_empty() {
+        0
+    } else {
+        1
+    }
 }
@@ -0,0 +1,63 @@
+//! Fuzzy matching for the Ctrl+K command palette.
+
+/// How well `query` matches `candidate` as a case-insensitive subsequence,
+/// or `None` if it doesn't. Consecutive characters and matches at the start
+/// of a word score extra; unmatched characters count against it, so shorter
+/// candidates win ties.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = next + candidate[next..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || matches!(candidate[found - 1], ' ' | '-' | '.' | '@' | '_') {
+            score += 3;
+        }
+        previous = Some(found);
+        next = found + 1;
+    }
+
+    Some(score * 4 - candidate.len() as i32)
+}
+
+/// The best `limit` entries for `query` by label, best first. Equal scores
+/// keep the order they were given in.
+pub fn rank<T>(query: &str, entries: Vec<(String, T)>, limit: usize) -> Vec<(String, T)> {
+    let mut scored: Vec<(i32, (String, T))> = entries
+        .into_iter()
+        .filter_map(|entry| Some((fuzzy_score(query, &entry.0)?, entry)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().take(limit).map(|(_, entry)| entry).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_runs() {
+        assert_eq!(fuzzy_score("xyz", "restart nginx.service"), None);
+        assert!(fuzzy_score("rst ngx", "restart nginx.service").is_some());
+
+        let entries = vec![
+            ("stop nginx.service".to_string(), 1),
+            ("restart nginx.service".to_string(), 2),
+            ("restart networkd.service".to_string(), 3),
+        ];
+        let ranked: Vec<i32> = rank("restart ngi", entries, 2)
+            .into_iter()
+            .map(|(_, id)| id)
+            .collect();
+        assert_eq!(ranked, [2]);
+
+        let nginx = fuzzy_score("ngx", "nginx.service").unwrap();
+        assert!(nginx > fuzzy_score("ngx", "network-manager-gx.service").unwrap());
+    }
+}
@@ -0,0 +1,93 @@
+//! `--serve <ADDR>`: a minimal HTTP endpoint for dashboards and monitoring,
+//! built with `--features serve`. Instead of opening the window it answers
+//!
+//! - `GET /services`: the service list as JSON, as `list_services` parses it
+//! - `GET /health`: the failed units, with status 503 if there are any
+//!
+//! one request at a time, reading the list fresh for each.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::systemd::{list_services, ServiceScope};
+
+pub fn run(addr: &str) -> Result<(), String> {
+    let listener =
+        TcpListener::bind(addr).map_err(|e| format!("Failed to bind {}: {}", addr, e))?;
+    if let Ok(local) = listener.local_addr() {
+        eprintln!("Serving /services and /health on http://{}", local);
+    }
+
+    for stream in listener.incoming() {
+        let result = stream.and_then(handle);
+        if let Err(e) = result {
+            tracing::debug!(error = %e, "HTTP request failed");
+        }
+    }
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Headers don't matter here, but are read so the client isn't reset.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let (status, body) = respond(method, path);
+    tracing::debug!(method, path, status, "HTTP request");
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+fn respond(method: &str, path: &str) -> (&'static str, String) {
+    if method != "GET" {
+        return ("405 Method Not Allowed", json!({ "error": "only GET is supported" }).to_string());
+    }
+
+    let path = path.split('?').next().unwrap_or_default();
+    let services = match path {
+        "/services" | "/health" => list_services(None, ServiceScope::System),
+        _ => return ("404 Not Found", json!({ "error": "not found" }).to_string()),
+    };
+    let services = match services {
+        Ok(services) => services,
+        Err(e) => return ("500 Internal Server Error", json!({ "error": e }).to_string()),
+    };
+
+    if path == "/services" {
+        return match serde_json::to_string(&services) {
+            Ok(body) => ("200 OK", body),
+            Err(e) => ("500 Internal Server Error", json!({ "error": e.to_string() }).to_string()),
+        };
+    }
+
+    let failed: Vec<&str> = services
+        .iter()
+        .filter(|service| service.is_failed())
+        .map(|service| service.name.as_str())
+        .collect();
+    let status = if failed.is_empty() { "200 OK" } else { "503 Service Unavailable" };
+    let body = json!({
+        "services": services.len(),
+        "failed": failed.len(),
+        "failed_units": failed,
+    });
+    (status, body.to_string())
+}
@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::i18n::{t, t_args};
+use crate::systemd::ServiceInfo;
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub taken_at: SystemTime,
+    pub services: Vec<ServiceInfo>,
+}
+
+/// A loaded snapshot indexed by unit name, for comparing against the live list.
+#[derive(Debug, Clone)]
+pub struct Baseline {
+    pub taken_at: SystemTime,
+    pub units: HashMap<String, ServiceInfo>,
+}
+
+impl From<Snapshot> for Baseline {
+    fn from(snapshot: Snapshot) -> Self {
+        Baseline {
+            taken_at: snapshot.taken_at,
+            units: snapshot
+                .services
+                .into_iter()
+                .map(|service| (service.name.clone(), service))
+                .collect(),
+        }
+    }
+}
+
+impl Baseline {
+    /// How `current` differs from the snapshot, or `None` if it doesn't.
+    pub fn compare(&self, current: &ServiceInfo) -> Option<Change> {
+        let Some(before) = self.units.get(&current.name) else {
+            return Some(Change::New);
+        };
+        if before.active_state == current.active_state && before.sub_state == current.sub_state {
+            return None;
+        }
+
+        let was = format!("{}/{}", before.active_state, before.sub_state);
+        Some(if current.is_failed() && !before.is_failed() {
+            Change::NewlyFailed { was }
+        } else if current.is_active() && !before.is_active() {
+            Change::NewlyActive { was }
+        } else {
+            Change::StateChanged { was }
+        })
+    }
+
+    /// Units in the snapshot that are no longer listed, sorted by name.
+    pub fn removed<'a>(&'a self, current: &[ServiceInfo]) -> Vec<&'a str> {
+        let present: HashSet<&str> = current.iter().map(|service| service.name.as_str()).collect();
+        let mut removed: Vec<&str> = self
+            .units
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !present.contains(name))
+            .collect();
+        removed.sort_unstable();
+        removed
+    }
+}
+
+/// How a unit differs from the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    New,
+    NewlyFailed { was: String },
+    NewlyActive { was: String },
+    StateChanged { was: String },
+}
+
+impl Change {
+    pub fn label(&self) -> String {
+        match self {
+            Change::New => t("change_new").to_string(),
+            Change::NewlyFailed { was } => t_args("change_failed", &[was]),
+            Change::NewlyActive { was } => t_args("change_active", &[was]),
+            Change::StateChanged { was } => t_args("change_state", &[was]),
+        }
+    }
+}
+
+pub fn default_path() -> Option<PathBuf> {
+    config::data_path(SNAPSHOT_FILE)
+}
+
+pub fn save(path: &Path, services: &[ServiceInfo]) -> Result<(), String> {
+    let snapshot = Snapshot {
+        taken_at: SystemTime::now(),
+        services: services.to_vec(),
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_vec_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn load(path: &Path) -> Result<Snapshot, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_slice(&bytes).map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(name: &str, active_state: &str, sub_state: &str) -> ServiceInfo {
+        ServiceInfo {
+            name: name.to_string(),
+            active_state: active_state.to_string(),
+            sub_state: sub_state.to_string(),
+            ..ServiceInfo::default()
+        }
+    }
+
+    #[test]
+    fn changes_since_the_snapshot_are_classified() {
+        let baseline = Baseline::from(Snapshot {
+            taken_at: SystemTime::UNIX_EPOCH,
+            services: vec![
+                unit("same.service", "active", "running"),
+                unit("crashed.service", "active", "running"),
+                unit("started.service", "inactive", "dead"),
+                unit("exited.service", "active", "running"),
+                unit("gone.service", "active", "running"),
+                unit("also-gone.service", "inactive", "dead"),
+            ],
+        });
+        let current = [
+            unit("same.service", "active", "running"),
+            unit("crashed.service", "failed", "failed"),
+            unit("started.service", "active", "running"),
+            unit("exited.service", "active", "exited"),
+            unit("added.service", "active", "running"),
+        ];
+
+        let changes: Vec<_> = current.iter().map(|service| baseline.compare(service)).collect();
+        assert_eq!(
+            changes,
+            [
+                None,
+                Some(Change::NewlyFailed { was: "active/running".to_string() }),
+                Some(Change::NewlyActive { was: "inactive/dead".to_string() }),
+                Some(Change::StateChanged { was: "active/running".to_string() }),
+                Some(Change::New),
+            ]
+        );
+        assert_eq!(baseline.removed(&current), ["also-gone.service", "gone.service"]);
+    }
+}
@@ -0,0 +1,147 @@
+use iced::theme::{self, Palette};
+use iced::widget::{button, container};
+use iced::{Border, Color, Theme};
+
+/// Accent presets offered in Settings, as translation keys and sRGB bytes.
+pub const ACCENT_PRESETS: [(&str, [u8; 3]); 6] = [
+    ("color_blue", [0x34, 0x65, 0xa4]),
+    ("color_teal", [0x00, 0x80, 0x80]),
+    ("color_green", [0x2e, 0x8b, 0x57]),
+    ("color_orange", [0xe6, 0x7e, 0x22]),
+    ("color_red", [0xc0, 0x39, 0x2b]),
+    ("color_purple", [0x8e, 0x44, 0xad]),
+];
+
+pub fn accent_color(rgb: [u8; 3]) -> Color {
+    Color::from_rgb8(rgb[0], rgb[1], rgb[2])
+}
+
+/// A solid button filled with a user-chosen accent color.
+pub struct AccentButton(pub Color);
+
+impl button::StyleSheet for AccentButton {
+    type Style = Theme;
+
+    fn active(&self, _theme: &Theme) -> button::Appearance {
+        button::Appearance {
+            background: Some(self.0.into()),
+            text_color: readable_text_on(self.0),
+            border: Border::with_radius(2),
+            ..button::Appearance::default()
+        }
+    }
+
+    fn hovered(&self, theme: &Theme) -> button::Appearance {
+        let Color { r, g, b, a } = self.0;
+        let lighten = |c: f32| c + (1.0 - c) * 0.15;
+
+        button::Appearance {
+            background: Some(Color::from_rgba(lighten(r), lighten(g), lighten(b), a).into()),
+            ..self.active(theme)
+        }
+    }
+}
+
+/// The built-in iced theme called `name` (as `Theme` displays it, e.g.
+/// "Dracula"); the default theme for `None` or a name this iced doesn't have.
+pub fn builtin_theme(name: Option<&str>) -> Theme {
+    name.and_then(|name| Theme::ALL.iter().find(|theme| theme.to_string() == name))
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Palette for the high-contrast option: white on black with saturated
+/// state colors.
+pub fn high_contrast_theme() -> Theme {
+    Theme::custom(
+        String::from("High contrast"),
+        Palette {
+            background: Color::BLACK,
+            text: Color::WHITE,
+            primary: Color::from_rgb(1.0, 0.85, 0.0),
+            success: Color::from_rgb(0.3, 1.0, 0.3),
+            danger: Color::from_rgb(1.0, 0.35, 0.35),
+        },
+    )
+}
+
+/// Wraps another button style with a solid outline that thickens on hover,
+/// so every control stays clearly delimited in high-contrast mode.
+pub struct OutlinedButton(pub theme::Button);
+
+impl OutlinedButton {
+    fn outline(appearance: button::Appearance, color: Color, width: f32) -> button::Appearance {
+        button::Appearance {
+            border: Border {
+                color,
+                width,
+                radius: 2.0.into(),
+            },
+            ..appearance
+        }
+    }
+}
+
+impl button::StyleSheet for OutlinedButton {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> button::Appearance {
+        Self::outline(theme.active(&self.0), theme.palette().text, 2.0)
+    }
+
+    fn hovered(&self, theme: &Theme) -> button::Appearance {
+        Self::outline(theme.hovered(&self.0), theme.palette().primary, 4.0)
+    }
+
+    fn pressed(&self, theme: &Theme) -> button::Appearance {
+        Self::outline(theme.pressed(&self.0), theme.palette().primary, 4.0)
+    }
+
+    fn disabled(&self, theme: &Theme) -> button::Appearance {
+        let Color { r, g, b, .. } = theme.palette().text;
+        Self::outline(theme.disabled(&self.0), Color::from_rgba(r, g, b, 0.5), 2.0)
+    }
+}
+
+/// Row background for a unit whose state just changed; `strength` fades from
+/// 1.0 to 0.0 over the highlight's lifetime.
+pub struct ChangeHighlight(pub f32);
+
+impl container::StyleSheet for ChangeHighlight {
+    type Style = Theme;
+
+    fn appearance(&self, _theme: &Theme) -> container::Appearance {
+        container::Appearance {
+            background: Some(Color::from_rgba(0.95, 0.8, 0.2, 0.45 * self.0).into()),
+            border: Border::with_radius(2),
+            ..container::Appearance::default()
+        }
+    }
+}
+
+/// Background of every other row in the service list, a faint tint of the
+/// theme's text color so it works on light and dark themes alike.
+pub struct StripedRow;
+
+impl container::StyleSheet for StripedRow {
+    type Style = Theme;
+
+    fn appearance(&self, theme: &Theme) -> container::Appearance {
+        let text = theme.palette().text;
+        container::Appearance {
+            background: Some(Color { a: 0.05, ..text }.into()),
+            border: Border::with_radius(2),
+            ..container::Appearance::default()
+        }
+    }
+}
+
+/// Black or white, whichever reads better on `background`.
+fn readable_text_on(background: Color) -> Color {
+    let luminance = 0.299 * background.r + 0.587 * background.g + 0.114 * background.b;
+    if luminance > 0.6 {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
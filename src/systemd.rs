@@ -1,8 +1,19 @@
-use std::process::Command;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ServiceInfo {
     pub name: String,
     pub description: String,
@@ -10,214 +21,3738 @@ pub struct ServiceInfo {
     pub active_state: String,
     pub sub_state: String,
     pub unit_file_state: String,
+    pub vendor_preset: String,
     pub followed_by: Vec<String>,
+    pub last_log: Option<SystemTime>,
+    pub tasks: Option<u64>,
+    pub tasks_max: Option<u64>,
+    /// Main process (`MainPID`); `None` while the unit isn't running.
+    pub main_pid: Option<u32>,
+    /// Bytes used by the unit's cgroup (`MemoryCurrent`).
+    pub memory_current: Option<u64>,
+    /// CPU time consumed since the unit started, in nanoseconds
+    /// (`CPUUsageNSec`).
+    pub cpu_usage_nsec: Option<u64>,
+    /// The unit file systemd loaded the unit from; empty if it has none.
+    pub fragment_path: String,
+    /// `Documentation=` entries: URLs or `man:` pages.
+    pub documentation: Vec<String>,
+    /// Drop-in files (`*.d/*.conf`) layered over the unit file.
+    pub drop_in_paths: Vec<String>,
+    /// How the last run ended (`Result`): `success`, `exit-code`, `signal`,
+    /// `timeout`, `start-limit-hit`, ...
+    pub result: String,
+    /// Automatic restarts since the unit was last started by hand (`NRestarts`).
+    pub restarts: Option<u64>,
+    /// Exit code of the main process, or the signal that killed it
+    /// (`ExecMainStatus`).
+    pub exit_status: Option<i32>,
+    /// Whether the `Condition*=` checks passed on the last start attempt
+    /// (`ConditionResult`); `None` if they were never evaluated.
+    pub condition_met: Option<bool>,
+    /// Same for the `Assert*=` checks (`AssertResult`).
+    pub assert_met: Option<bool>,
+    /// Whether the unit supports `systemctl reload` (`CanReload`).
+    pub can_reload: bool,
+    /// The slice the unit runs in, e.g. `system.slice` (`Slice`).
+    pub slice: String,
+    /// Its cgroup path, e.g. `/system.slice/nginx.service`; empty while the
+    /// unit isn't running (`ControlGroup`).
+    pub control_group: String,
+    /// Timer, socket or path units that start this one on demand
+    /// (`TriggeredBy`).
+    pub triggered_by: Vec<String>,
+}
+
+/// The `UnitFileState` values systemd reports, see systemctl(1) `is-enabled`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitFileState {
+    Enabled,
+    EnabledRuntime,
+    Linked,
+    LinkedRuntime,
+    Alias,
+    Masked,
+    MaskedRuntime,
+    Static,
+    Disabled,
+    Indirect,
+    Generated,
+    Transient,
+    Bad,
+    Unknown(String),
+}
+
+impl UnitFileState {
+    pub fn parse(value: &str) -> UnitFileState {
+        match value.trim() {
+            "enabled" => UnitFileState::Enabled,
+            "enabled-runtime" => UnitFileState::EnabledRuntime,
+            "linked" => UnitFileState::Linked,
+            "linked-runtime" => UnitFileState::LinkedRuntime,
+            "alias" => UnitFileState::Alias,
+            "masked" => UnitFileState::Masked,
+            "masked-runtime" => UnitFileState::MaskedRuntime,
+            "static" => UnitFileState::Static,
+            "disabled" => UnitFileState::Disabled,
+            "indirect" => UnitFileState::Indirect,
+            "generated" => UnitFileState::Generated,
+            "transient" => UnitFileState::Transient,
+            "bad" => UnitFileState::Bad,
+            other => UnitFileState::Unknown(other.to_string()),
+        }
+    }
+
+    /// Whether `systemctl enable`/`disable` meaningfully applies. Generated and
+    /// transient units have no persistent file to link, static and alias units
+    /// have no [Install] section of their own, and masked units must be
+    /// unmasked first.
+    pub fn is_toggleable(&self) -> bool {
+        matches!(
+            self,
+            UnitFileState::Enabled
+                | UnitFileState::EnabledRuntime
+                | UnitFileState::Disabled
+                | UnitFileState::Indirect
+                | UnitFileState::Linked
+                | UnitFileState::LinkedRuntime
+        )
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, UnitFileState::Enabled | UnitFileState::EnabledRuntime)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            UnitFileState::Enabled => "Enabled: started at boot through its [Install] symlinks",
+            UnitFileState::EnabledRuntime => "Enabled until the next reboot (symlinks under /run)",
+            UnitFileState::Linked => "Unit file linked in from outside the unit search path",
+            UnitFileState::LinkedRuntime => "Unit file linked in until the next reboot",
+            UnitFileState::Alias => "An alias name for another unit file",
+            UnitFileState::Masked => "Masked: linked to /dev/null and cannot be started",
+            UnitFileState::MaskedRuntime => "Masked until the next reboot",
+            UnitFileState::Static => "Static: no [Install] section, only started as a dependency",
+            UnitFileState::Disabled => "Disabled: has an [Install] section but is not enabled",
+            UnitFileState::Indirect => "Indirect: enabled through Also= or another unit, not itself",
+            UnitFileState::Generated => "Generated dynamically by a generator; cannot be enabled",
+            UnitFileState::Transient => "Transient: created at runtime and cannot be enabled",
+            UnitFileState::Bad => "The unit file is invalid",
+            UnitFileState::Unknown(_) => "Unit file state not reported",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnitFileInfo {
+    pub name: String,
+    pub state: String,
+    pub preset: String,
 }
 
+/// A queued or running systemd job, as reported by `systemctl list-jobs`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobInfo {
+    pub id: String,
+    pub unit: String,
+    pub job_type: String,
+    pub state: String,
+}
+
+/// A unit's current state plus runtime properties not included in
+/// `list-units`: the main PID, tasks (processes and threads) in its cgroup
+/// against `TasksMax`, the cgroup's memory and accumulated CPU time, and the
+/// unit file it was loaded from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ServiceStatus {
     pub name: String,
     pub active: bool,
     pub running: bool,
+    pub active_state: String,
+    pub sub_state: String,
     pub pid: Option<u32>,
+    pub tasks: Option<u64>,
+    pub tasks_max: Option<u64>,
+    /// Bytes, from `MemoryCurrent`.
+    pub memory: Option<u64>,
+    /// Total CPU time consumed, in nanoseconds (`CPUUsageNSec`).
+    pub cpu_usage_nsec: Option<u64>,
+    pub fragment_path: String,
+    pub documentation: Vec<String>,
+    pub drop_in_paths: Vec<String>,
+    pub result: String,
+    pub restarts: Option<u64>,
+    pub exit_status: Option<i32>,
+    pub condition_met: Option<bool>,
+    pub assert_met: Option<bool>,
+    pub can_reload: bool,
+    pub slice: String,
+    pub control_group: String,
+    pub triggered_by: Vec<String>,
 }
 
 impl ServiceInfo {
     pub fn is_active(&self) -> bool {
-        self.active_state == "active"
+        self.active_state.eq_ignore_ascii_case("active")
     }
 
     pub fn is_running(&self) -> bool {
-        self.sub_state == "running"
+        self.sub_state.eq_ignore_ascii_case("running")
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.active_state.eq_ignore_ascii_case("failed")
     }
-}
 
-pub fn list_services() -> Result<Vec<ServiceInfo>, String> {
-    let output = Command::new("systemctl")
-        .args(&["list-units", "--type=service", "--all", "--no-pager", "--output=json"])
-        .output()
-        .map_err(|e| format!("Failed to execute systemctl: {}", e))?;
+    /// Whether drop-ins (`DropInPaths`) are layered over the unit file.
+    pub fn has_drop_ins(&self) -> bool {
+        !self.drop_in_paths.is_empty()
+    }
 
-    if !output.status.success() {
-        return Err(format!("systemctl command failed: {}", output.status));
+    /// systemd has no unit file for this name loaded, often because a new
+    /// file was added without a `daemon-reload`.
+    pub fn is_not_found(&self) -> bool {
+        self.load_state.eq_ignore_ascii_case("not-found")
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let json: Value = serde_json::from_str(&stdout)
-        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+    pub fn unit_file_state(&self) -> UnitFileState {
+        UnitFileState::parse(&self.unit_file_state)
+    }
 
-    let rows = match json {
-        Value::Array(rows) => rows,
-        Value::Object(mut obj) => match obj.remove("units") {
-            Some(Value::Array(rows)) => rows,
-            _ => return Err("Unexpected JSON format from systemctl".to_string()),
-        },
-        _ => return Err("Unexpected JSON format from systemctl".to_string()),
-    };
+    /// True when a toggleable unit's enablement differs from its vendor preset.
+    pub fn diverges_from_preset(&self) -> bool {
+        let state = self.unit_file_state();
+        let preset = UnitFileState::parse(&self.vendor_preset);
+        state.is_toggleable()
+            && matches!(preset, UnitFileState::Enabled | UnitFileState::Disabled)
+            && state.is_enabled() != preset.is_enabled()
+    }
+}
 
-    let mut services = Vec::with_capacity(rows.len());
+/// Runs a command to completion and collects its output. Everything that
+/// calls `systemctl` goes through this, so tests can substitute canned output
+/// and exit codes for the real binary.
+pub trait CommandRunner {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output>;
 
-    for row in rows {
-        let name = extract_string(
-            &row,
-            &["name", "unit", "Unit", "id", "Id", "names", "Names"],
-        );
-        if name.is_empty() {
-            continue;
+    /// Hands the command's stdout to `line` a line at a time until it
+    /// returns `false` or the output ends, then stops the command. The
+    /// returned `Output` has the exit status and stderr; stdout is empty.
+    fn stream(
+        &self,
+        program: &str,
+        args: &[&str],
+        line: &mut dyn FnMut(&str) -> bool,
+    ) -> io::Result<Output> {
+        let mut output = self.output(program, args)?;
+        for text in String::from_utf8_lossy(&output.stdout).lines() {
+            if !line(text) {
+                break;
+            }
         }
-
-        services.push(ServiceInfo {
-            name,
-            description: extract_string(&row, &["description", "Description"]),
-            load_state: extract_string(&row, &["load_state", "load", "LoadState", "Load"]),
-            active_state: extract_string(&row, &["active_state", "active", "ActiveState", "Active"]),
-            sub_state: extract_string(&row, &["sub_state", "sub", "SubState", "Sub"]),
-            unit_file_state: extract_string(
-                &row,
-                &["unit_file_state", "unit_file", "UnitFileState", "UnitFile"],
-            ),
-            followed_by: extract_string_vec(
-                &row,
-                &["followed_by", "followed", "following", "FollowedBy", "Following"],
-            ),
-        });
+        output.stdout.clear();
+        Ok(output)
     }
 
-    Ok(services)
+    /// The machine `systemctl` calls are sent to, see
+    /// `SystemctlCommand::target`; `None` for the local one.
+    fn target(&self) -> Option<&RemoteTarget> {
+        None
+    }
 }
 
-fn extract_string(row: &Value, keys: &[&str]) -> String {
-    for key in keys {
-        if let Some(value) = row.get(*key) {
-            match value {
-                Value::String(s) => {
-                    if !s.is_empty() {
-                        return s.clone();
-                    }
+/// Runs commands for real, with a debug trace of the command line, exit
+/// status and duration. With tracing disabled this costs a level check per call.
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        let mut command = Command::new(program);
+        command.args(args);
+        let started = Instant::now();
+        let result = command.output();
+        trace_finished(&command, started, &result);
+        result
+    }
+
+    fn stream(
+        &self,
+        program: &str,
+        args: &[&str],
+        line: &mut dyn FnMut(&str) -> bool,
+    ) -> io::Result<Output> {
+        let mut command = Command::new(program);
+        command.args(args).stdout(Stdio::piped()).stderr(Stdio::piped());
+        let started = Instant::now();
+        let mut child = command.spawn()?;
+
+        // Read on its own thread, so a full stderr pipe can't stall stdout.
+        let stderr = child.stderr.take().map(|mut stderr| {
+            thread::spawn(move || {
+                let mut buf = Vec::new();
+                let _ = stderr.read_to_end(&mut buf);
+                buf
+            })
+        });
+        if let Some(stdout) = child.stdout.take() {
+            for text in BufReader::new(stdout).lines() {
+                let Ok(text) = text else { break };
+                if !line(&text) {
+                    break;
                 }
-                Value::Number(n) => return n.to_string(),
-                Value::Bool(b) => return b.to_string(),
-                _ => {}
             }
         }
+
+        let _ = child.kill();
+        let result = child.wait().map(|status| Output {
+            status,
+            stdout: Vec::new(),
+            stderr: stderr.and_then(|reader| reader.join().ok()).unwrap_or_default(),
+        });
+        trace_finished(&command, started, &result);
+        result
     }
+}
 
-    String::new()
+/// Runs commands like `SystemRunner`, with `systemctl` calls sent to another
+/// machine.
+struct RemoteRunner {
+    target: RemoteTarget,
 }
 
-fn extract_string_vec(row: &Value, keys: &[&str]) -> Vec<String> {
-    for key in keys {
-        if let Some(value) = row.get(*key) {
-            match value {
-                Value::Array(values) => {
-                    let out: Vec<String> = values
-                        .iter()
-                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                        .collect();
-                    if !out.is_empty() {
-                        return out;
-                    }
-                }
-                Value::String(s) => {
-                    if !s.is_empty() {
-                        return vec![s.clone()];
-                    }
-                }
-                _ => {}
+impl CommandRunner for RemoteRunner {
+    fn output(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+        SystemRunner.output(program, args)
+    }
+
+    fn stream(
+        &self,
+        program: &str,
+        args: &[&str],
+        line: &mut dyn FnMut(&str) -> bool,
+    ) -> io::Result<Output> {
+        SystemRunner.stream(program, args, line)
+    }
+
+    fn target(&self) -> Option<&RemoteTarget> {
+        Some(&self.target)
+    }
+}
+
+static REMOTE_RUNNER: OnceLock<RemoteRunner> = OnceLock::new();
+
+/// Sends every later `systemctl` call to `target`; set once at startup from
+/// `--host`/`--machine`.
+pub fn set_remote_target(target: RemoteTarget) {
+    let _ = REMOTE_RUNNER.set(RemoteRunner { target });
+}
+
+pub fn remote_target() -> Option<&'static RemoteTarget> {
+    REMOTE_RUNNER.get().map(|runner| &runner.target)
+}
+
+/// The runner used outside tests: the remote one after `set_remote_target`,
+/// D-Bus when built with the `dbus` feature and systemd is reachable on the
+/// system bus, otherwise plain subprocesses.
+#[cfg(feature = "dbus")]
+fn default_runner() -> &'static dyn CommandRunner {
+    if let Some(runner) = REMOTE_RUNNER.get() {
+        return runner;
+    }
+    static RUNNER: OnceLock<Option<crate::dbus::DbusRunner>> = OnceLock::new();
+    match RUNNER.get_or_init(crate::dbus::DbusRunner::connect) {
+        Some(runner) => runner,
+        None => &SystemRunner,
+    }
+}
+
+#[cfg(not(feature = "dbus"))]
+fn default_runner() -> &'static dyn CommandRunner {
+    match REMOTE_RUNNER.get() {
+        Some(runner) => runner,
+        None => &SystemRunner,
+    }
+}
+
+/// Which service manager a call goes to: the system one, or the calling
+/// user's own (`systemctl --user`), which runs desktop services such as
+/// pipewire or syncthing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ServiceScope {
+    #[default]
+    System,
+    User,
+}
+
+/// Another machine to manage instead of the local one: over SSH
+/// (`systemctl --host`) or a local container (`systemctl --machine`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteTarget {
+    Host(String),
+    Machine(String),
+}
+
+/// One `systemctl` invocation, assembled in argument order: the verb, then
+/// its flags and units. Every call in this module is built with it, so unit
+/// name completion, the flags chosen in Settings and output options are
+/// spelled out in one place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemctlCommand {
+    args: Vec<String>,
+}
+
+impl SystemctlCommand {
+    pub fn new(verb: &str) -> Self {
+        SystemctlCommand { args: vec![verb.to_string()] }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// `--user` for the user's service manager; nothing for the system one.
+    pub fn scope(self, scope: ServiceScope) -> Self {
+        match scope {
+            ServiceScope::System => self,
+            ServiceScope::User => self.arg("--user"),
+        }
+    }
+
+    /// `--host=<host>`: run on `[user@]host` over SSH.
+    pub fn host(self, host: &str) -> Self {
+        self.arg(format!("--host={}", host))
+    }
+
+    /// `--machine=<name>`: talk to a local container's service manager.
+    pub fn machine(self, machine: &str) -> Self {
+        self.arg(format!("--machine={}", machine))
+    }
+
+    /// `host` or `machine` for `target`, moved right after the verb whenever
+    /// this is called; nothing for the local machine.
+    pub fn target(self, target: Option<&RemoteTarget>) -> Self {
+        let Some(target) = target else {
+            return self;
+        };
+        let mut args = self.args.into_iter();
+        let command = SystemctlCommand::new(&args.next().unwrap_or_default());
+        match target {
+            RemoteTarget::Host(host) => command.host(host),
+            RemoteTarget::Machine(machine) => command.machine(machine),
+        }
+        .args(args)
+    }
+
+    /// The extra flags configured for this command's verb, see `ExtraArgs`.
+    pub fn extra(self, extra: &ExtraArgs) -> Self {
+        let flags = extra.args(&self.args[0]).to_vec();
+        self.args(flags)
+    }
+
+    pub fn no_pager(self) -> Self {
+        self.arg("--no-pager")
+    }
+
+    /// `--output=json`, for the listing verbs that support it.
+    pub fn json(self) -> Self {
+        self.arg("--output=json")
+    }
+
+    /// A unit argument; bare names get `.service` appended, see `unit_name`.
+    pub fn unit(self, name: &str) -> Self {
+        self.arg(unit_name(name))
+    }
+
+    pub fn units<'a>(self, names: impl IntoIterator<Item = &'a str>) -> Self {
+        names.into_iter().fold(self, SystemctlCommand::unit)
+    }
+
+    pub fn arg_list(&self) -> Vec<&str> {
+        self.args.iter().map(String::as_str).collect()
+    }
+
+    /// The shell command line, e.g. `systemctl restart foo.service`, for
+    /// showing or copying instead of executing. Arguments with characters
+    /// outside the usual unit-name set (such as `\x2d` escapes) are
+    /// single-quoted.
+    pub fn command_line(&self) -> String {
+        let mut line = String::from("systemctl");
+        for arg in &self.args {
+            line.push(' ');
+            let plain = !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "@._:-/=".contains(c));
+            if plain {
+                line.push_str(arg);
+            } else {
+                line.push('\'');
+                line.push_str(&arg.replace('\'', "'\\''"));
+                line.push('\'');
             }
         }
+        line
+    }
+
+    fn run(&self, runner: &dyn CommandRunner) -> Result<Output, String> {
+        let command = self.clone().target(runner.target());
+        let output = runner
+            .output("systemctl", &command.arg_list())
+            .map_err(|e| format!("Failed to execute systemctl: {}", e))?;
+        // Queries like `is-active` exit non-zero without complaint; only keep
+        // failures systemctl had something to say about.
+        if !output.status.success() && !output.stderr.is_empty() {
+            record_failure(&command, &output);
+        }
+        Ok(output)
     }
+}
+
+/// A `systemctl` invocation that exited unsuccessfully, kept for bug reports.
+#[derive(Debug, Clone)]
+pub struct FailedCommand {
+    pub command: String,
+    pub status: String,
+    pub stderr: String,
+}
+
+static LAST_FAILURE: Mutex<Option<FailedCommand>> = Mutex::new(None);
+
+fn record_failure(command: &SystemctlCommand, output: &Output) {
+    let failure = FailedCommand {
+        command: command.command_line(),
+        status: output.status.to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    };
+    if let Ok(mut last) = LAST_FAILURE.lock() {
+        *last = Some(failure);
+    }
+}
+
+/// The most recent `systemctl` call that failed, if any did this session.
+pub fn last_failure() -> Option<FailedCommand> {
+    LAST_FAILURE.lock().ok()?.clone()
+}
+
+/// First line of `systemctl --version`, e.g. `systemd 255 (255.4-1ubuntu8)`.
+pub fn systemd_version() -> Result<String, String> {
+    let output = SystemctlCommand::new("--version").run(default_runner())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| "systemctl --version printed nothing".to_string())
+}
+
+fn trace_finished(command: &Command, started: Instant, result: &io::Result<Output>) {
+    match result {
+        Ok(output) => tracing::debug!(
+            command = ?command,
+            status = %output.status,
+            elapsed = ?started.elapsed(),
+            "command finished"
+        ),
+        Err(e) => tracing::warn!(command = ?command, error = %e, "command failed to start"),
+    }
+}
 
-    Vec::new()
+/// Lists service units. With a `state` (e.g. `failed`) systemctl filters
+/// them itself with `--state=`, which on machines with thousands of units is
+/// far cheaper than listing all of them and filtering here.
+pub fn list_services(state: Option<&str>, scope: ServiceScope) -> Result<Vec<ServiceInfo>, String> {
+    list_services_with(default_runner(), state, scope)
 }
 
-pub fn get_service_status(service_name: &str) -> Result<ServiceStatus, String> {
-    let output = Command::new("systemctl")
-        .args(&["show", service_name, "--property=ActiveState,SubState,MainPID", "--no-pager"])
-        .output()
-        .map_err(|e| format!("Failed to execute systemctl: {}", e))?;
+fn list_services_with(
+    runner: &dyn CommandRunner,
+    state: Option<&str>,
+    scope: ServiceScope,
+) -> Result<Vec<ServiceInfo>, String> {
+    let output = SystemctlCommand::new("list-units")
+        .scope(scope)
+        .args(["--type=service", "--all"])
+        .args(state.map(|state| format!("--state={}", state)))
+        .no_pager()
+        .json()
+        .run(runner)?;
 
     if !output.status.success() {
         return Err(format!("systemctl command failed: {}", output.status));
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut active = false;
-    let mut running = false;
-    let mut pid = None;
+    let mut services = parse_services(&String::from_utf8_lossy(&output.stdout))?;
 
-    for line in stdout.lines() {
-        if line.starts_with("ActiveState=") {
-            active = line.trim_start_matches("ActiveState=") == "active";
-        } else if line.starts_with("SubState=") {
-            running = line.trim_start_matches("SubState=") == "running";
-        } else if line.starts_with("MainPID=") {
-            let pid_str = line.trim_start_matches("MainPID=");
-            if let Ok(p) = pid_str.parse::<u32>() {
-                pid = Some(p);
-            }
+    // list-units does not report enablement, so fill it in from the unit files.
+    // This is best effort: older systemctl builds may not support the JSON output.
+    // A filtered list only needs the files of the units it returned.
+    let patterns: Vec<String> = match state {
+        Some(_) => services.iter().map(|service| service.name.clone()).collect(),
+        None => Vec::new(),
+    };
+    if state.is_none() || !patterns.is_empty() {
+        if let Ok(unit_files) = list_unit_files(runner, &patterns, scope) {
+            apply_unit_files(&mut services, &unit_files);
         }
     }
 
-    Ok(ServiceStatus {
-        name: service_name.to_string(),
-        active,
-        running,
-        pid,
-    })
+    Ok(services)
+}
+
+/// Parses the output of `systemctl list-units --output=json`, either a bare
+/// array or wrapped in `{"units": [...]}`. Enablement isn't part of it.
+pub fn parse_services(json: &str) -> Result<Vec<ServiceInfo>, String> {
+    let UnitRows(rows) =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|row| !row.name.is_empty())
+        .map(ServiceInfo::from)
+        .collect())
+}
+
+/// Reads units from a file saved with `systemctl list-units --output=json`,
+/// for viewing another machine's state offline.
+pub fn import_services(path: &Path) -> Result<Vec<ServiceInfo>, String> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_services(&json)
+}
+
+/// Unit files of all services, or only of those matching `patterns`.
+fn list_unit_files(
+    runner: &dyn CommandRunner,
+    patterns: &[String],
+    scope: ServiceScope,
+) -> Result<Vec<UnitFileInfo>, String> {
+    let output = SystemctlCommand::new("list-unit-files")
+        .scope(scope)
+        .arg("--type=service")
+        .no_pager()
+        .json()
+        .args(patterns.iter().map(String::as_str))
+        .run(runner)?;
+
+    if !output.status.success() {
+        return Err(format!("systemctl command failed: {}", output.status));
+    }
+
+    let rows: Vec<RawUnitFile> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|row| !row.name.is_empty())
+        .map(|row| UnitFileInfo {
+            name: row.name,
+            state: row.state,
+            preset: row.preset,
+        })
+        .collect())
 }
 
-pub fn start_service(service_name: &str) -> Result<(), String> {
-    let output = Command::new("systemctl")
-        .args(&["start", service_name])
-        .output()
-        .map_err(|e| format!("Failed to execute systemctl: {}", e))?;
+/// Runtime status of every unit in `names`, from a single `systemctl show`
+/// rather than one process per unit. Results are in the same order as `names`.
+pub fn get_statuses(names: &[&str], scope: ServiceScope) -> Result<Vec<ServiceStatus>, String> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let output = SystemctlCommand::new("show")
+        .scope(scope)
+        .arg(
+            "--property=ActiveState,SubState,MainPID,TasksCurrent,TasksMax,MemoryCurrent,CPUUsageNSec,\
+             FragmentPath,Documentation,DropInPaths,Result,NRestarts,ExecMainStatus,\
+             ConditionResult,ConditionTimestamp,AssertResult,AssertTimestamp,CanReload,Slice,ControlGroup,\
+             TriggeredBy",
+        )
+        .no_pager()
+        .args(names.iter().copied())
+        .run(default_runner())?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to start service: {}", stderr));
+        return Err(format!("Failed to read unit status: {}", stderr));
     }
 
-    Ok(())
+    // One blank-line separated block of properties per unit, in argument order.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(names
+        .iter()
+        .zip(stdout.split("\n\n"))
+        .map(|(name, block)| parse_status_block(name, block))
+        .collect())
+}
+
+fn parse_status_block(name: &str, block: &str) -> ServiceStatus {
+    let properties = parse_properties(block);
+    let get = |key: &str| properties.get(key).map(String::as_str).unwrap_or_default();
+
+    ServiceStatus {
+        name: name.to_string(),
+        active: get("ActiveState") == "active",
+        running: get("SubState") == "running",
+        active_state: get("ActiveState").to_string(),
+        sub_state: get("SubState").to_string(),
+        pid: get("MainPID").parse().ok().filter(|&pid| pid != 0),
+        tasks: parse_count(get("TasksCurrent")),
+        tasks_max: parse_count(get("TasksMax")),
+        memory: parse_count(get("MemoryCurrent")),
+        cpu_usage_nsec: parse_count(get("CPUUsageNSec")),
+        fragment_path: get("FragmentPath").to_string(),
+        documentation: parse_documentation(get("Documentation")),
+        drop_in_paths: get("DropInPaths").split_whitespace().map(str::to_string).collect(),
+        result: get("Result").to_string(),
+        restarts: parse_count(get("NRestarts")),
+        exit_status: get("ExecMainStatus").parse().ok(),
+        condition_met: parse_check(get("ConditionResult"), get("ConditionTimestamp")),
+        assert_met: parse_check(get("AssertResult"), get("AssertTimestamp")),
+        can_reload: get("CanReload") == "yes",
+        slice: get("Slice").to_string(),
+        control_group: get("ControlGroup").to_string(),
+        triggered_by: get("TriggeredBy").split_whitespace().map(str::to_string).collect(),
+    }
+}
+
+/// A `ConditionResult`/`AssertResult` pair with its timestamp. systemd reports
+/// `no` for units it never tried to start, so only a checked result counts.
+fn parse_check(result: &str, timestamp: &str) -> Option<bool> {
+    if timestamp.is_empty() || timestamp == "n/a" {
+        return None;
+    }
+    Some(result == "yes")
+}
+
+/// Every property systemd knows for the unit (`systemctl show` without
+/// `--property`), sorted by name.
+pub fn get_properties(service_name: &str) -> Result<BTreeMap<String, String>, String> {
+    properties_with(default_runner(), service_name)
 }
 
-pub fn stop_service(service_name: &str) -> Result<(), String> {
-    let output = Command::new("systemctl")
-        .args(&["stop", service_name])
-        .output()
-        .map_err(|e| format!("Failed to execute systemctl: {}", e))?;
+fn properties_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let output = SystemctlCommand::new("show").no_pager().unit(service_name).run(runner)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to stop service: {}", stderr));
+        return Err(format!("Failed to read unit properties: {}", stderr));
     }
 
-    Ok(())
+    Ok(parse_properties(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .collect())
+}
+
+/// Parses one unit's `systemctl show` block into property name and value.
+/// Values are split off at the first `=` only, so they may contain `=`
+/// themselves (`ExecStart`, `Environment`) or be empty. A line that doesn't
+/// start with a property name continues the previous value.
+fn parse_properties(block: &str) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    let mut last: Option<String> = None;
+
+    for line in block.lines() {
+        match line.split_once('=') {
+            Some((key, value)) if is_property_name(key) => {
+                properties.insert(key.to_string(), value.to_string());
+                last = Some(key.to_string());
+            }
+            _ => {
+                if let Some(value) = last.as_ref().and_then(|key| properties.get_mut(key)) {
+                    value.push('\n');
+                    value.push_str(line);
+                }
+            }
+        }
+    }
+
+    properties
+}
+
+/// systemd property names are CamelCase identifiers, e.g. `NRestarts`.
+fn is_property_name(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_uppercase()) && key.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Splits the space-separated `Documentation=` list. Newer systemd versions
+/// quote each entry, older ones don't.
+fn parse_documentation(value: &str) -> Vec<String> {
+    value
+        .split_whitespace()
+        .map(|entry| entry.trim_matches('"'))
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parses a numeric property, mapping systemd's "unset" spellings to `None`.
+fn parse_count(value: &str) -> Option<u64> {
+    match value.trim() {
+        "" | "[not set]" | "infinity" => None,
+        value => value.parse().ok().filter(|&n| n != u64::MAX),
+    }
+}
+
+/// One variable of a unit's environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvVar {
+    pub key: String,
+    pub value: String,
+    /// The `EnvironmentFile=` it came from; `None` for `Environment=`.
+    pub file: Option<String>,
+}
+
+impl EnvVar {
+    /// Whether the value is likely a credential and shouldn't be shown by
+    /// default.
+    pub fn is_sensitive(&self) -> bool {
+        let key = self.key.to_uppercase();
+        ["TOKEN", "SECRET", "PASSWORD"].iter().any(|word| key.contains(word))
+    }
+}
+
+/// The environment a unit's processes get from its unit file, as far as it
+/// can be resolved here.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnitEnvironment {
+    /// `Environment=` variables, then each environment file's in order. A
+    /// key set again later overrides the earlier one, as in systemd.
+    pub vars: Vec<EnvVar>,
+    /// Environment files that couldn't be read, with the reason. Often a
+    /// permission problem, since they tend to hold credentials.
+    pub unreadable: Vec<(String, String)>,
 }
 
-pub fn restart_service(service_name: &str) -> Result<(), String> {
-    let output = Command::new("systemctl")
-        .args(&["restart", service_name])
-        .output()
-        .map_err(|e| format!("Failed to execute systemctl: {}", e))?;
+/// `Environment=` and the contents of `EnvironmentFiles=` of the unit.
+pub fn get_environment(service_name: &str) -> Result<UnitEnvironment, String> {
+    environment_with(default_runner(), service_name)
+}
+
+fn environment_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+) -> Result<UnitEnvironment, String> {
+    let output = SystemctlCommand::new("show")
+        .arg("--property=Environment")
+        .arg("--property=EnvironmentFiles")
+        .unit(service_name)
+        .run(runner)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to restart service: {}", stderr));
+        return Err(format!("Failed to read unit environment: {}", stderr.trim()));
     }
 
-    Ok(())
+    let mut environment = UnitEnvironment::default();
+    // Each environment file gets its own `EnvironmentFiles=` line, so this
+    // isn't a job for `parse_properties`.
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(value) = line.strip_prefix("Environment=") {
+            environment.vars.extend(split_environment(value).map(|(key, value)| EnvVar {
+                key,
+                value,
+                file: None,
+            }));
+        } else if let Some(value) = line.strip_prefix("EnvironmentFiles=") {
+            // E.g. `/etc/default/ssh (ignore_errors=yes)`.
+            let (path, flags) = value.rsplit_once(" (").unwrap_or((value, ""));
+            match fs::read_to_string(path) {
+                Ok(content) => environment.vars.extend(
+                    parse_environment_file(&content).map(|(key, value)| EnvVar {
+                        key,
+                        value,
+                        file: Some(path.to_string()),
+                    }),
+                ),
+                Err(e) if e.kind() == io::ErrorKind::NotFound && flags.contains("=yes") => {}
+                Err(e) => environment.unreadable.push((path.to_string(), e.to_string())),
+            }
+        }
+    }
+    Ok(environment)
+}
+
+/// Splits `systemctl show`'s `Environment=` value into variables. Entries are
+/// separated by spaces; ones containing spaces or special characters are
+/// double-quoted with C-style escapes.
+fn split_environment(value: &str) -> impl Iterator<Item = (String, String)> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' => match chars.next() {
+                Some('n') => word.push('\n'),
+                Some('t') => word.push('\t'),
+                Some(escaped) => word.push(escaped),
+                None => {}
+            },
+            ' ' if !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words.into_iter().filter_map(|word| {
+        let (key, value) = word.split_once('=')?;
+        Some((key.to_string(), value.to_string()))
+    })
+}
+
+/// `KEY=VALUE` lines of an environment file. Blank lines and `#`/`;`
+/// comments are skipped and quotes around a value are dropped.
+fn parse_environment_file(content: &str) -> impl Iterator<Item = (String, String)> + '_ {
+    content.lines().filter_map(|line| {
+        let line = line.trim();
+        if line.starts_with(['#', ';']) {
+            return None;
+        }
+        let (key, value) = line.split_once('=')?;
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|&quote| value.strip_prefix(quote)?.strip_suffix(quote))
+            .unwrap_or(value);
+        Some((key.trim().to_string(), value.to_string()))
+    })
+}
+
+/// Whether the unit is active right now, from the exit code of
+/// `systemctl is-active`. Much cheaper than `show` for watching one unit.
+pub fn is_active(service_name: &str) -> Result<bool, String> {
+    quick_check(default_runner(), "is-active", service_name)
+}
+
+/// Whether the unit is in the failed state right now (`systemctl is-failed`).
+pub fn is_failed(service_name: &str) -> Result<bool, String> {
+    quick_check(default_runner(), "is-failed", service_name)
+}
+
+/// Runs an `is-*` query. Exit 0 means yes and any other code means no,
+/// unless systemctl wrote an error (e.g. it couldn't reach the manager).
+fn quick_check(runner: &dyn CommandRunner, verb: &str, service_name: &str) -> Result<bool, String> {
+    let output = SystemctlCommand::new(verb).arg("--quiet").unit(service_name).run(runner)?;
+    if output.status.success() {
+        return Ok(true);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.trim().is_empty() {
+        Ok(false)
+    } else {
+        Err(format!("Failed to check unit state: {}", stderr))
+    }
+}
+
+/// Units that depend on `service_name` (`systemctl list-dependencies
+/// --reverse`), i.e. what stopping it may take down or leave degraded.
+pub fn get_reverse_dependencies(service_name: &str) -> Result<Vec<String>, String> {
+    reverse_dependencies_with(default_runner(), service_name)
 }
 
-pub fn reload_service(service_name: &str) -> Result<(), String> {
-    let output = Command::new("systemctl")
-        .args(&["reload", service_name])
-        .output()
-        .map_err(|e| format!("Failed to execute systemctl: {}", e))?;
+fn reverse_dependencies_with(runner: &dyn CommandRunner, service_name: &str) -> Result<Vec<String>, String> {
+    let unit = unit_name(service_name);
+    let output = SystemctlCommand::new("list-dependencies")
+        .args(["--reverse", "--plain"])
+        .no_pager()
+        .arg(unit.as_ref())
+        .run(runner)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to reload service: {}", stderr));
+        return Err(format!("Failed to list dependencies: {}", stderr));
     }
 
-    Ok(())
+    // The first line is the unit itself; the rest are indented by depth.
+    // Some versions still prefix a state bullet even with --plain.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut seen = HashSet::new();
+    Ok(stdout
+        .lines()
+        .skip(1)
+        .map(|line| line.trim_start_matches(|c: char| c.is_whitespace() || c == '●' || c == '○'))
+        .map(str::trim_end)
+        .filter(|name| !name.is_empty() && *name != unit)
+        .filter(|name| seen.insert(name.to_string()))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Active units that stopping `service_name` takes down with it: those that
+/// `Requires=` or `BindsTo=` it (`RequiredBy`, `BoundBy`), followed through
+/// to the units requiring those in turn. Weaker dependencies (`Wants=`)
+/// survive the stop and are left out.
+pub fn get_stop_dependents(service_name: &str) -> Result<Vec<String>, String> {
+    stop_dependents_with(default_runner(), service_name)
+}
+
+fn stop_dependents_with(runner: &dyn CommandRunner, service_name: &str) -> Result<Vec<String>, String> {
+    let unit = unit_name(service_name).into_owned();
+    let mut seen = HashSet::from([unit.clone()]);
+    let mut dependents = Vec::new();
+    let mut level = vec![unit.clone()];
+
+    // One `show` per level of the dependency tree rather than per unit.
+    while !level.is_empty() {
+        let output = SystemctlCommand::new("show")
+            .arg("--property=ActiveState,RequiredBy,BoundBy")
+            .no_pager()
+            .args(level.iter().map(String::as_str))
+            .run(runner)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to list dependencies: {}", stderr));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut next = Vec::new();
+        for (name, block) in level.iter().zip(stdout.split("\n\n")) {
+            let properties = parse_properties(block);
+            let get = |key: &str| properties.get(key).map(String::as_str).unwrap_or_default();
+            let active = matches!(get("ActiveState"), "active" | "activating" | "reloading");
+            if *name != unit && active {
+                dependents.push(name.clone());
+            }
+            let required_by = get("RequiredBy").split_whitespace();
+            for dependent in required_by.chain(get("BoundBy").split_whitespace()) {
+                if seen.insert(dependent.to_string()) {
+                    next.push(dependent.to_string());
+                }
+            }
+        }
+        level = next;
+    }
+
+    Ok(dependents)
+}
+
+/// Units that carry the desktop session this app runs in: the session user's
+/// manager (`user@<uid>.service`) and runtime directory, and the display
+/// manager. Stopping any of them ends the session, this window included.
+/// The user comes from logind when `XDG_SESSION_ID` is set, else `uid`.
+pub fn session_units(uid: Option<u32>) -> Vec<String> {
+    let session_id = std::env::var("XDG_SESSION_ID").ok();
+    session_units_with(default_runner(), session_id.as_deref(), uid)
+}
+
+fn session_units_with(runner: &dyn CommandRunner, session_id: Option<&str>, uid: Option<u32>) -> Vec<String> {
+    let value = |program: &str, args: &[&str]| {
+        runner
+            .output(program, args)
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let session_uid = session_id
+        .and_then(|id| value("loginctl", &["show-session", id, "--property=User", "--value"]))
+        .and_then(|user| user.parse().ok())
+        .or(uid);
+
+    let mut units = Vec::new();
+    if let Some(uid) = session_uid.filter(|&uid| uid != 0) {
+        units.push(format!("user@{}.service", uid));
+        units.push(format!("user-runtime-dir@{}.service", uid));
+    }
+    // display-manager.service is an alias; list-units shows the real name.
+    let display_manager = SystemctlCommand::new("show")
+        .unit("display-manager.service")
+        .args(["--property=Id", "--value"]);
+    if let Some(display_manager) = value("systemctl", &display_manager.arg_list()) {
+        units.push(display_manager);
+    }
+    units
+}
+
+pub fn list_jobs() -> Result<Vec<JobInfo>, String> {
+    let output = SystemctlCommand::new("list-jobs").no_pager().json().run(default_runner())?;
+
+    if !output.status.success() {
+        return Err(format!("systemctl command failed: {}", output.status));
+    }
+
+    // With nothing queued, some versions print "No jobs running." even in JSON mode.
+    if !output.stdout.trim_ascii_start().starts_with(b"[") {
+        return Ok(Vec::new());
+    }
+
+    let rows: Vec<RawJob> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|row| !row.unit.is_empty())
+        .map(|row| JobInfo {
+            id: row.id,
+            unit: row.unit,
+            job_type: row.job_type,
+            state: row.state,
+        })
+        .collect())
+}
+
+/// How many days back `last_log_times` searches the journal.
+pub const LAST_LOG_WINDOW_DAYS: u64 = 7;
+
+/// Finds the most recent journal entry written by each of `units` within
+/// `LAST_LOG_WINDOW_DAYS`. Units that did not log in that window are absent from
+/// the result.
+pub fn last_log_times(units: &[String]) -> Result<HashMap<String, SystemTime>, String> {
+    last_log_times_with(default_runner(), units)
+}
+
+fn last_log_times_with(
+    runner: &dyn CommandRunner,
+    units: &[String],
+) -> Result<HashMap<String, SystemTime>, String> {
+    let mut found = HashMap::new();
+    if units.is_empty() {
+        return Ok(found);
+    }
+    let wanted: HashSet<&str> = units.iter().map(String::as_str).collect();
+
+    let since = format!("--since=-{}d", LAST_LOG_WINDOW_DAYS);
+    let mut args = vec![
+        "--no-pager",
+        "--output=json",
+        "--output-fields=_SYSTEMD_UNIT",
+        "--reverse",
+        &since,
+    ];
+    for unit in units {
+        args.extend(["-u", unit]);
+    }
+
+    // Entries arrive newest first, so the first one seen per unit is its latest.
+    let output = runner
+        .stream("journalctl", &args, &mut |line| {
+            let Ok(entry) = serde_json::from_str::<JournalEntry>(line) else {
+                return true;
+            };
+            let Some(unit) = entry.unit else { return true };
+            let Ok(micros) = entry.realtime.parse::<u64>() else {
+                return true;
+            };
+
+            if wanted.contains(unit.as_str()) && !found.contains_key(&unit) {
+                found.insert(unit, UNIX_EPOCH + Duration::from_micros(micros));
+            }
+            found.len() < wanted.len()
+        })
+        .map_err(|e| format!("Failed to execute journalctl: {}", e))?;
+
+    if found.is_empty() && !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to read journal: {}", stderr));
+    }
+
+    Ok(found)
+}
+
+/// How many of the newest journal lines `get_service_logs` returns.
+pub const LOG_LINES: usize = 200;
+
+/// Minimum priority for `journalctl -p`; each level includes the more severe ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogPriority {
+    Err,
+    Warning,
+    Notice,
+    Info,
+}
+
+impl LogPriority {
+    pub const ALL: [LogPriority; 4] = [
+        LogPriority::Err,
+        LogPriority::Warning,
+        LogPriority::Notice,
+        LogPriority::Info,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogPriority::Err => "err",
+            LogPriority::Warning => "warning",
+            LogPriority::Notice => "notice",
+            LogPriority::Info => "info",
+        }
+    }
+}
+
+/// Scope of the log panel: the whole journal or only the current boot, and
+/// optionally only entries at or above a priority.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LogFilter {
+    pub current_boot: bool,
+    pub priority: Option<LogPriority>,
+}
+
+/// The newest `LOG_LINES` journal lines of the unit, oldest first.
+pub fn get_service_logs(service_name: &str, filter: LogFilter) -> Result<String, String> {
+    get_service_logs_with(default_runner(), service_name, filter)
+}
+
+fn get_service_logs_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    filter: LogFilter,
+) -> Result<String, String> {
+    let args = journal_args(&unit_name(service_name), filter, LOG_LINES);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = runner
+        .output("journalctl", &args)
+        .map_err(|e| format!("Failed to execute journalctl: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to read journal: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The newest `lines` journal lines of the unit, oldest first, for the
+/// full-size log view. Without permission to read the system journal,
+/// journalctl succeeds with none of the unit's entries and only a hint on
+/// stderr; that hint is returned as the error.
+pub fn get_service_log_lines(service_name: &str, lines: usize) -> Result<Vec<String>, String> {
+    get_service_log_lines_with(default_runner(), service_name, lines)
+}
+
+fn get_service_log_lines_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    lines: usize,
+) -> Result<Vec<String>, String> {
+    let args = journal_args(&unit_name(service_name), LogFilter::default(), lines);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = runner
+        .output("journalctl", &args)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => "journalctl is not installed".to_string(),
+            _ => format!("Failed to execute journalctl: {}", e),
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // journalctl's own markers, such as "-- No entries --", start with "-- ".
+    let no_entries = stdout.lines().all(|line| line.starts_with("-- "));
+    if !output.status.success() || (no_entries && !stderr.trim().is_empty()) {
+        return Err(format!("Failed to read journal: {}", stderr.trim()));
+    }
+
+    Ok(stdout.lines().map(str::to_string).collect())
+}
+
+fn journal_args(unit: &str, filter: LogFilter, lines: usize) -> Vec<String> {
+    let mut args = vec![
+        "--no-pager".to_string(),
+        "--output=short-iso".to_string(),
+        format!("--lines={}", lines),
+        format!("--unit={}", unit),
+    ];
+    if filter.current_boot {
+        args.push("--boot".to_string());
+    }
+    if let Some(priority) = filter.priority {
+        args.push(format!("--priority={}", priority.as_str()));
+    }
+    args
+}
+
+/// Journal entries scanned by a log search, newest first.
+pub const LOG_SEARCH_LINES: usize = 2000;
+
+/// A journal line from a service that contains the searched text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogMatch {
+    pub unit: String,
+    pub line: String,
+}
+
+/// Searches the last `LOG_SEARCH_LINES` journal entries of all services for
+/// `term` (case-insensitive), newest first: who logged this error string?
+pub fn search_logs(term: &str) -> Result<Vec<LogMatch>, String> {
+    search_logs_with(default_runner(), term)
+}
+
+fn search_logs_with(runner: &dyn CommandRunner, term: &str) -> Result<Vec<LogMatch>, String> {
+    let needle = term.trim().to_lowercase();
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Matched here rather than with `--grep`, which needs a journalctl built
+    // with PCRE2 and treats the term as a regular expression.
+    let lines = format!("--lines={}", LOG_SEARCH_LINES);
+    let output = runner
+        .output(
+            "journalctl",
+            &[
+                "--no-pager",
+                "--output=json",
+                "--output-fields=_SYSTEMD_UNIT,MESSAGE",
+                "--reverse",
+                &lines,
+            ],
+        )
+        .map_err(|e| format!("Failed to execute journalctl: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to read journal: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JournalMessage>(line).ok())
+        .filter_map(|entry| {
+            let unit = entry.unit.filter(|unit| unit.ends_with(".service"))?;
+            let line = journal_text(&entry.message)?;
+            line.to_lowercase().contains(&needle).then_some(LogMatch { unit, line })
+        })
+        .collect())
+}
+
+/// Output kept from a custom action, so a chatty script can't flood the panel.
+pub const CUSTOM_OUTPUT_LIMIT: usize = 64 * 1024;
+
+/// Turns a custom action's command template into the program and its
+/// arguments, with `{unit}` replaced by `unit` in each argument. The command
+/// runs without a shell and the unit name is checked to be a plain unit
+/// name, so it only ever ends up inside one argument and can't add commands,
+/// redirections or further arguments. Words are separated by whitespace;
+/// single or double quotes keep spaces in one.
+pub fn custom_command(template: &str, unit: &str) -> Result<Vec<String>, String> {
+    let valid_unit = !unit.is_empty()
+        && unit.chars().all(|c| c.is_ascii_alphanumeric() || ":-_.@\\".contains(c));
+    if !valid_unit {
+        return Err(format!("Not a unit name: {}", unit));
+    }
+
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    for c in template.chars() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (_, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("Unbalanced quote in command".to_string());
+    }
+    words.extend(word);
+
+    match words.first() {
+        None => Err("Empty command".to_string()),
+        Some(program) if program.contains("{unit}") => {
+            Err("The program itself can't be {unit}; use it in an argument".to_string())
+        }
+        Some(_) => Ok(words.into_iter().map(|word| word.replace("{unit}", unit)).collect()),
+    }
+}
+
+/// Runs a custom action for `unit` and returns what it printed (stdout, then
+/// stderr), cut at `CUSTOM_OUTPUT_LIMIT`. A non-zero exit is an error that
+/// carries the output too.
+pub fn run_custom_action(template: &str, unit: &str) -> Result<String, String> {
+    run_custom_action_with(default_runner(), template, unit)
+}
+
+fn run_custom_action_with(
+    runner: &dyn CommandRunner,
+    template: &str,
+    unit: &str,
+) -> Result<String, String> {
+    let command = custom_command(template, unit)?;
+    let (program, args) = command.split_first().expect("custom_command returns a program");
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = runner
+        .output(program, &args)
+        .map_err(|e| format!("Failed to execute {}: {}", program, e))?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    if text.len() > CUSTOM_OUTPUT_LIMIT {
+        let mut end = CUSTOM_OUTPUT_LIMIT;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push_str("\n…");
+    }
+
+    match output.status.code() {
+        Some(0) => Ok(text),
+        Some(code) => Err(format!("{} exited with {}\n{}", program, code, text.trim_end())),
+        None => Err(format!("{} was killed by a signal\n{}", program, text.trim_end())),
+    }
+}
+
+/// How long each unit took to start during the current boot, slowest first,
+/// from `systemd-analyze blame`. Its text output is parsed since `--json`
+/// isn't available for `blame` on most systemd versions.
+pub fn analyze_blame() -> Result<Vec<(String, Duration)>, String> {
+    analyze_blame_with(default_runner())
+}
+
+fn analyze_blame_with(runner: &dyn CommandRunner) -> Result<Vec<(String, Duration)>, String> {
+    let output = runner
+        .output("systemd-analyze", &["blame", "--no-pager"])
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => "systemd-analyze is not installed".to_string(),
+            _ => format!("Failed to execute systemd-analyze: {}", e),
+        })?;
+
+    // E.g. "Bootup is not yet finished" or, in a container or on a system
+    // not booted with systemd, a failure to read the boot timestamps.
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to analyze boot: {}", stderr.trim()));
+    }
+
+    let mut units: Vec<(String, Duration)> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (time, unit) = line.trim().rsplit_once(' ')?;
+            Some((unit.to_string(), parse_timespan(time)?))
+        })
+        .collect();
+    units.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(units)
+}
+
+/// Parses a systemd time span such as `1min 2.345s`, `532ms` or `12us`.
+fn parse_timespan(text: &str) -> Option<Duration> {
+    let mut total = Duration::ZERO;
+    for part in text.split_whitespace() {
+        let split = part.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (number, unit) = part.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let seconds = match unit {
+            "d" => number * 86400.0,
+            "h" => number * 3600.0,
+            "min" => number * 60.0,
+            "s" => number,
+            "ms" => number / 1e3,
+            "us" | "µs" => number / 1e6,
+            _ => return None,
+        };
+        total += Duration::from_secs_f64(seconds);
+    }
+    Some(total)
+}
+
+/// A journal field as text. journalctl writes fields that aren't valid
+/// UTF-8 (or contain control characters) as an array of bytes.
+fn journal_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                .collect::<Option<_>>()?;
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        _ => None,
+    }
+}
+
+/// The unit's last `count` journal lines at error priority or worse, as bare
+/// messages, for explaining why it failed.
+pub fn recent_errors(service_name: &str, count: usize) -> Result<Vec<String>, String> {
+    recent_errors_with(default_runner(), service_name, count)
+}
+
+fn recent_errors_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    count: usize,
+) -> Result<Vec<String>, String> {
+    let lines = format!("--lines={}", count);
+    let unit = format!("--unit={}", unit_name(service_name));
+    let output = runner
+        .output(
+            "journalctl",
+            &["--no-pager", "--output=cat", "--priority=err", &lines, &unit],
+        )
+        .map_err(|e| format!("Failed to execute journalctl: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("Failed to read journal: {}", stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct JournalMessage {
+    #[serde(rename = "_SYSTEMD_UNIT")]
+    unit: Option<String>,
+    #[serde(rename = "MESSAGE", default)]
+    message: Value,
+}
+
+#[derive(Deserialize)]
+struct JournalEntry {
+    #[serde(rename = "_SYSTEMD_UNIT")]
+    unit: Option<String>,
+    #[serde(rename = "__REALTIME_TIMESTAMP", default)]
+    realtime: String,
+}
+
+fn apply_unit_files(services: &mut [ServiceInfo], unit_files: &[UnitFileInfo]) {
+    let by_name: HashMap<&str, &UnitFileInfo> = unit_files
+        .iter()
+        .map(|info| (info.name.as_str(), info))
+        .collect();
+
+    for service in services {
+        // Template instances (getty@tty1.service) inherit from their template file.
+        let info = by_name
+            .get(service.name.as_str())
+            .or_else(|| template_name(&service.name).and_then(|t| by_name.get(t.as_str())));
+
+        if let Some(info) = info {
+            if service.unit_file_state.is_empty() {
+                service.unit_file_state = info.state.clone();
+            }
+            if service.vendor_preset.is_empty() {
+                service.vendor_preset = info.preset.clone();
+            }
+        }
+    }
+}
+
+/// Unit type suffixes systemctl understands, see systemd.unit(5).
+const UNIT_SUFFIXES: [&str; 11] = [
+    "service", "socket", "target", "device", "mount", "automount", "swap", "timer", "path",
+    "slice", "scope",
+];
+
+/// Appends `.service` to a name without a unit type suffix, so `nginx` and
+/// `nginx.service` mean the same unit everywhere rather than leaving it to
+/// systemctl's guessing.
+pub fn unit_name(name: &str) -> Cow<'_, str> {
+    let typed = name
+        .rsplit_once('.')
+        .is_some_and(|(_, suffix)| UNIT_SUFFIXES.contains(&suffix));
+    if typed || name.is_empty() {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("{}.service", name))
+    }
+}
+
+/// Decodes the `\xNN` escapes systemd uses for characters not allowed in
+/// unit names, e.g. `foo\x2dbar@tty1` → `foo-bar@tty1`. Escapes that don't
+/// decode to valid UTF-8 are left as they are.
+pub fn unescape_unit_name(name: &str) -> Cow<'_, str> {
+    if !name.contains("\\x") {
+        return Cow::Borrowed(name);
+    }
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut rest = name.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let decoded = match tail {
+            [b'x', high, low, ..] if byte == b'\\' => std::str::from_utf8(&[*high, *low])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match decoded {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[3..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    match String::from_utf8(bytes) {
+        Ok(unescaped) => Cow::Owned(unescaped),
+        Err(_) => Cow::Borrowed(name),
+    }
+}
+
+/// Units listed under more than one name, mapped from each extra name to the
+/// one kept in the list. A unit whose `Following` names another listed unit
+/// is an alias of it; units loaded from the same unit file are aliases of the
+/// one named like the file (or, failing that, the first by name). Chains
+/// resolve to their end, so every value is itself not an alias.
+pub fn alias_targets(services: &[ServiceInfo]) -> HashMap<String, String> {
+    let listed: HashSet<&str> = services.iter().map(|service| service.name.as_str()).collect();
+    let mut targets: HashMap<String, String> = HashMap::new();
+
+    for service in services {
+        let followed = service
+            .followed_by
+            .iter()
+            .find(|name| **name != service.name && listed.contains(name.as_str()));
+        if let Some(target) = followed {
+            targets.insert(service.name.clone(), target.clone());
+        }
+    }
+
+    let mut by_fragment: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for service in services {
+        if !service.fragment_path.is_empty() && !targets.contains_key(&service.name) {
+            by_fragment.entry(&service.fragment_path).or_default().push(&service.name);
+        }
+    }
+    for (fragment, mut names) in by_fragment {
+        if names.len() < 2 {
+            continue;
+        }
+        names.sort_unstable();
+        let file_name = Path::new(fragment).file_name().and_then(|name| name.to_str());
+        let canonical = names
+            .iter()
+            .copied()
+            .find(|name| Some(*name) == file_name)
+            .unwrap_or(names[0]);
+        for name in names.into_iter().filter(|name| *name != canonical) {
+            targets.insert(name.to_string(), canonical.to_string());
+        }
+    }
+
+    // Follow chains such as a → b → c, stopping on a cycle.
+    let resolved = targets
+        .keys()
+        .map(|name| {
+            let mut target = &targets[name];
+            for _ in 0..targets.len() {
+                match targets.get(target) {
+                    Some(next) if next != name => target = next,
+                    _ => break,
+                }
+            }
+            (name.clone(), target.clone())
+        })
+        .collect();
+    resolved
+}
+
+/// `getty@.service` for an instance such as `getty@tty1.service`.
+pub fn template_name(name: &str) -> Option<String> {
+    let (prefix, rest) = name.split_once('@')?;
+    let suffix = rest.rsplit_once('.').map(|(_, suffix)| suffix)?;
+    Some(format!("{}@.{}", prefix, suffix))
+}
+
+/// One row of `systemctl list-units --output=json`. See `RawUnit::string_field`
+/// for the key spellings used by different systemd versions.
+#[derive(Default)]
+struct RawUnit {
+    name: String,
+    description: String,
+    load_state: String,
+    active_state: String,
+    sub_state: String,
+    unit_file_state: String,
+    vendor_preset: String,
+    followed_by: Vec<String>,
+}
+
+impl RawUnit {
+    /// The field a key spelling maps to, or `None` for keys we don't read.
+    fn string_field(&mut self, key: &str) -> Option<&mut String> {
+        Some(match key {
+            "name" | "unit" | "Unit" | "id" | "Id" | "names" | "Names" => &mut self.name,
+            "description" | "Description" => &mut self.description,
+            "load_state" | "load" | "LoadState" | "Load" => &mut self.load_state,
+            "active_state" | "active" | "ActiveState" | "Active" => &mut self.active_state,
+            "sub_state" | "sub" | "SubState" | "Sub" => &mut self.sub_state,
+            "unit_file_state" | "unit_file" | "UnitFileState" | "UnitFile" => {
+                &mut self.unit_file_state
+            }
+            "vendor_preset" | "preset" | "UnitFilePreset" => &mut self.vendor_preset,
+            _ => return None,
+        })
+    }
+}
+
+/// Rows may carry several spellings of the same field. The first one with a
+/// value wins; a `null` (or empty) spelling is skipped rather than hiding a
+/// later one, and doesn't count as a duplicate key.
+impl<'de> Deserialize<'de> for RawUnit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RawUnitVisitor;
+
+        impl<'de> Visitor<'de> for RawUnitVisitor {
+            type Value = RawUnit;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a unit object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<RawUnit, A::Error> {
+                let mut raw = RawUnit::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    if let Some(field) = raw.string_field(&key) {
+                        let LenientString(value) = map.next_value()?;
+                        if field.is_empty() {
+                            *field = value;
+                        }
+                    } else if matches!(
+                        key.as_str(),
+                        "followed_by" | "followed" | "following" | "FollowedBy" | "Following"
+                    ) {
+                        let LenientStringVec(value) = map.next_value()?;
+                        if raw.followed_by.is_empty() {
+                            raw.followed_by = value;
+                        }
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                Ok(raw)
+            }
+        }
+
+        deserializer.deserialize_map(RawUnitVisitor)
+    }
+}
+
+impl From<RawUnit> for ServiceInfo {
+    fn from(raw: RawUnit) -> Self {
+        ServiceInfo {
+            name: raw.name,
+            description: raw.description,
+            load_state: raw.load_state,
+            active_state: raw.active_state,
+            sub_state: raw.sub_state,
+            unit_file_state: raw.unit_file_state,
+            vendor_preset: raw.vendor_preset,
+            followed_by: raw.followed_by,
+            last_log: None,
+            tasks: None,
+            tasks_max: None,
+            main_pid: None,
+            memory_current: None,
+            cpu_usage_nsec: None,
+            fragment_path: String::new(),
+            documentation: Vec::new(),
+            drop_in_paths: Vec::new(),
+            result: String::new(),
+            restarts: None,
+            exit_status: None,
+            condition_met: None,
+            assert_met: None,
+            can_reload: false,
+            slice: String::new(),
+            control_group: String::new(),
+            triggered_by: Vec::new(),
+        }
+    }
+}
+
+/// One row of `systemctl list-unit-files --output=json`.
+#[derive(Deserialize)]
+struct RawUnitFile {
+    #[serde(default, deserialize_with = "lenient_string")]
+    #[serde(rename = "unit_file", alias = "UnitFile", alias = "name")]
+    name: String,
+    #[serde(default, deserialize_with = "lenient_string", alias = "State")]
+    state: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    #[serde(alias = "Preset", alias = "vendor_preset")]
+    preset: String,
+}
+
+/// One row of `systemctl list-jobs --output=json`.
+#[derive(Deserialize)]
+struct RawJob {
+    #[serde(default, deserialize_with = "lenient_string")]
+    #[serde(rename = "job", alias = "id", alias = "Id", alias = "Job")]
+    id: String,
+    #[serde(default, deserialize_with = "lenient_string", alias = "Unit")]
+    unit: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    #[serde(rename = "type", alias = "Type", alias = "job_type")]
+    job_type: String,
+    #[serde(default, deserialize_with = "lenient_string", alias = "State")]
+    state: String,
+}
+
+/// The unit rows, whether systemctl emitted a bare array or wraps it in
+/// `{"units": [...]}`. Rows are deserialized as they are read, without
+/// buffering the document.
+struct UnitRows(Vec<RawUnit>);
+
+impl<'de> Deserialize<'de> for UnitRows {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RowsVisitor;
+
+        impl<'de> Visitor<'de> for RowsVisitor {
+            type Value = UnitRows;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an array of units or an object with a `units` array")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<UnitRows, A::Error> {
+                let mut rows = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(row) = seq.next_element()? {
+                    rows.push(row);
+                }
+                Ok(UnitRows(rows))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<UnitRows, A::Error> {
+                let mut rows = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "units" {
+                        rows = Some(map.next_value()?);
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                rows.map(UnitRows)
+                    .ok_or_else(|| de::Error::missing_field("units"))
+            }
+        }
+
+        deserializer.deserialize_any(RowsVisitor)
+    }
+}
+
+/// Accepts strings, numbers and booleans as text; anything else becomes empty.
+fn lenient_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    struct StringVisitor;
+
+    impl<'de> Visitor<'de> for StringVisitor {
+        type Value = String;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a string, number or boolean")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<String, E> {
+            Ok(v)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_bool<E: de::Error>(self, v: bool) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<String, E> {
+            Ok(String::new())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<String, A::Error> {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            Ok(String::new())
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<String, A::Error> {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            Ok(String::new())
+        }
+    }
+
+    deserializer.deserialize_any(StringVisitor)
+}
+
+/// Accepts an array of strings or a single string; empty items are skipped.
+fn lenient_string_vec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    struct StringVecVisitor;
+
+    impl<'de> Visitor<'de> for StringVecVisitor {
+        type Value = Vec<String>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an array of strings or a string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Vec<String>, E> {
+            Ok(if v.is_empty() { Vec::new() } else { vec![v.to_string()] })
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Vec<String>, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<String>, A::Error> {
+            let mut out = Vec::new();
+            while let Some(LenientString(item)) = seq.next_element()? {
+                if !item.is_empty() {
+                    out.push(item);
+                }
+            }
+            Ok(out)
+        }
+    }
+
+    deserializer.deserialize_any(StringVecVisitor)
+}
+
+/// Sequence element wrapper so arrays can reuse `lenient_string`.
+struct LenientString(String);
+
+impl<'de> Deserialize<'de> for LenientString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        lenient_string(deserializer).map(LenientString)
+    }
+}
+
+struct LenientStringVec(Vec<String>);
+
+impl<'de> Deserialize<'de> for LenientStringVec {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        lenient_string_vec(deserializer).map(LenientStringVec)
+    }
+}
+
+/// Outcome of a mutating systemctl call: `Ok(None)` when it ran cleanly,
+/// `Ok(Some(warning))` when systemctl complained but the operation didn't
+/// fail outright (the UI shows a notice rather than an error and still
+/// refreshes), and `Err` on a hard failure.
+pub type ActionResult = Result<Option<String>, String>;
+
+/// A nonzero systemctl exit that is not really a failure, matched by exit
+/// code and a fragment of stderr.
+type SoftFailure = (i32, &'static str);
+
+/// The queued job was replaced by a newer one before it finished (exit 1);
+/// the unit is in transition rather than broken.
+const JOB_CANCELED: SoftFailure = (1, "canceled");
+/// The unit isn't loaded (LSB exit 5), which for stop/reset-failed means
+/// there was nothing to do.
+const NOT_LOADED: SoftFailure = (5, "not loaded");
+
+/// Classifies a finished action. A nonzero exit matching one of `soft` is
+/// downgraded to a warning; `Warning:` lines on success are passed on as one
+/// too (e.g. unit files changed on disk without a daemon-reload).
+fn action_result(output: &Output, failure: &str, soft: &[SoftFailure]) -> ActionResult {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stderr = stderr.trim();
+
+    if output.status.success() {
+        let warnings: Vec<&str> = stderr
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("Warning:"))
+            .map(str::trim)
+            .collect();
+        return Ok((!warnings.is_empty()).then(|| warnings.join("\n")));
+    }
+
+    let code = output.status.code();
+    if soft
+        .iter()
+        .any(|&(soft_code, marker)| code == Some(soft_code) && stderr.contains(marker))
+    {
+        return Ok(Some(stderr.to_string()));
+    }
+
+    Err(format!("{}: {}", failure, stderr))
+}
+
+/// Flags that may be added to systemctl invocations from Settings, with the
+/// verbs each one is accepted for. Anything else in the config is dropped,
+/// so arbitrary arguments can't be smuggled into a privileged command.
+pub const EXTRA_FLAGS: [(&str, &[&str]); 5] = [
+    ("--no-block", &["start", "stop", "restart", "try-restart"]),
+    ("--no-ask-password", &["start", "stop", "restart", "try-restart", "enable", "disable"]),
+    ("--now", &["enable", "disable"]),
+    ("--runtime", &["enable", "disable"]),
+    ("--force", &["enable"]),
+];
+
+/// The verbs `EXTRA_FLAGS` can apply to, in display order.
+pub const EXTRA_FLAG_VERBS: [&str; 6] = ["start", "stop", "restart", "try-restart", "enable", "disable"];
+
+pub fn flag_allowed(verb: &str, flag: &str) -> bool {
+    EXTRA_FLAGS
+        .iter()
+        .any(|&(allowed, verbs)| allowed == flag && verbs.contains(&verb))
+}
+
+/// User-chosen extra flags per systemctl verb, e.g. `enable` → `["--now"]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExtraArgs(BTreeMap<String, Vec<String>>);
+
+impl ExtraArgs {
+    pub fn args(&self, verb: &str) -> &[String] {
+        self.0.get(verb).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn contains(&self, verb: &str, flag: &str) -> bool {
+        self.args(verb).iter().any(|arg| arg == flag)
+    }
+
+    /// Turns `flag` on or off for `verb`; flags not in `EXTRA_FLAGS` for that
+    /// verb are ignored.
+    pub fn set(&mut self, verb: &str, flag: &str, enabled: bool) {
+        if !flag_allowed(verb, flag) {
+            return;
+        }
+        let args = self.0.entry(verb.to_string()).or_default();
+        args.retain(|arg| arg != flag);
+        if enabled {
+            args.push(flag.to_string());
+        }
+        if args.is_empty() {
+            self.0.remove(verb);
+        }
+    }
+
+    /// Drops anything `set` wouldn't have allowed, e.g. from a hand-edited config.
+    pub fn retain_allowed(&mut self) {
+        for (verb, args) in &mut self.0 {
+            args.retain(|arg| flag_allowed(verb, arg));
+            args.dedup();
+        }
+        self.0.retain(|_, args| !args.is_empty());
+    }
+}
+
+/// `systemctl <verb> <extra flags> <unit>`.
+fn unit_command(
+    runner: &dyn CommandRunner,
+    verb: &str,
+    extra: &ExtraArgs,
+    service_name: &str,
+) -> Result<Output, String> {
+    SystemctlCommand::new(verb).extra(extra).unit(service_name).run(runner)
+}
+
+pub fn start_service(service_name: &str, extra: &ExtraArgs, scope: ServiceScope) -> ActionResult {
+    start_service_with(default_runner(), service_name, extra, scope)
+}
+
+fn start_service_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    extra: &ExtraArgs,
+    scope: ServiceScope,
+) -> ActionResult {
+    let output = SystemctlCommand::new("start")
+        .scope(scope)
+        .extra(extra)
+        .unit(service_name)
+        .run(runner)?;
+
+    action_result(&output, "Failed to start service", &[JOB_CANCELED])
+}
+
+pub fn stop_service(service_name: &str, extra: &ExtraArgs, scope: ServiceScope) -> ActionResult {
+    stop_service_with(default_runner(), service_name, extra, scope)
+}
+
+fn stop_service_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    extra: &ExtraArgs,
+    scope: ServiceScope,
+) -> ActionResult {
+    let output = SystemctlCommand::new("stop")
+        .scope(scope)
+        .extra(extra)
+        .unit(service_name)
+        .run(runner)?;
+
+    action_result(&output, "Failed to stop service", &[JOB_CANCELED, NOT_LOADED])
+}
+
+pub fn restart_service(service_name: &str, extra: &ExtraArgs, scope: ServiceScope) -> ActionResult {
+    restart_service_with(default_runner(), service_name, extra, scope)
+}
+
+fn restart_service_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    extra: &ExtraArgs,
+    scope: ServiceScope,
+) -> ActionResult {
+    let output = SystemctlCommand::new("restart")
+        .scope(scope)
+        .extra(extra)
+        .unit(service_name)
+        .run(runner)?;
+
+    action_result(&output, "Failed to restart service", &[JOB_CANCELED])
+}
+
+/// How often `restart_and_wait` checks the unit's state.
+const RESTART_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Restarts the unit, then checks its `ActiveState` until it is `active`,
+/// has `failed`, or `timeout` has passed. The restart command itself returns
+/// once the job is done, which for `Type=simple` units or with `--no-block`
+/// says little about whether the service actually came up. A `Type=oneshot`
+/// unit without `RemainAfterExit=` ends up inactive and so runs into the
+/// timeout.
+pub fn restart_and_wait(service_name: &str, extra: &ExtraArgs, timeout: Duration) -> ActionResult {
+    restart_and_wait_with(default_runner(), service_name, extra, timeout, RESTART_POLL_INTERVAL)
+}
+
+fn restart_and_wait_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    extra: &ExtraArgs,
+    timeout: Duration,
+    interval: Duration,
+) -> ActionResult {
+    let restarted = restart_service_with(runner, service_name, extra, ServiceScope::System)?;
+    let started = Instant::now();
+    loop {
+        let output = SystemctlCommand::new("show")
+            .args(["--property=ActiveState", "--value"])
+            .unit(service_name)
+            .run(runner)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Failed to read unit state: {}", stderr));
+        }
+        match String::from_utf8_lossy(&output.stdout).trim() {
+            "active" => return Ok(restarted),
+            "failed" => return Err(format!("{} failed after the restart", service_name)),
+            state if started.elapsed() >= timeout => {
+                return Err(format!(
+                    "{} did not come up within {}s (still {})",
+                    service_name,
+                    timeout.as_secs(),
+                    state
+                ));
+            }
+            _ => std::thread::sleep(interval),
+        }
+    }
+}
+
+/// Stops the unit now and disables it so it doesn't come back at boot. It is
+/// stopped first, so if that fails it is still enabled as before.
+pub fn stop_and_disable_service(service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    stop_and_disable_service_with(default_runner(), service_name, extra)
+}
+
+fn stop_and_disable_service_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    extra: &ExtraArgs,
+) -> ActionResult {
+    let stopped = stop_service_with(runner, service_name, extra, ServiceScope::System)?;
+
+    let output = unit_command(runner, "disable", extra, service_name)?;
+    let failure = format!("Stopped {}, but failed to disable it", service_name);
+    let disabled = action_result(&output, &failure, &[])?;
+
+    Ok(join_warnings(stopped, disabled))
+}
+
+/// systemctl's hint, printed with a zero exit status, when `enable` or
+/// `disable` has nothing to do because the unit has no [Install] section.
+const NO_INSTALL_CONFIG: &str = "no installation config";
+
+/// Enables the unit to start at boot (`systemctl enable`) without starting it
+/// now.
+pub fn enable_service(service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    enable_service_with(default_runner(), service_name, extra)
+}
+
+fn enable_service_with(runner: &dyn CommandRunner, service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    let output = unit_command(runner, "enable", extra, service_name)?;
+
+    unit_file_result(&output, "Failed to enable service")
+}
+
+/// Stops the unit from starting at boot (`systemctl disable`) without
+/// stopping it now.
+pub fn disable_service(service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    disable_service_with(default_runner(), service_name, extra)
+}
+
+fn disable_service_with(runner: &dyn CommandRunner, service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    let output = unit_command(runner, "disable", extra, service_name)?;
+
+    unit_file_result(&output, "Failed to disable service")
+}
+
+/// `action_result` for a plain `enable` or `disable`, which also counts
+/// systemctl's "no installation config" hint as a failure: it exits 0 for
+/// static units but hasn't changed anything.
+fn unit_file_result(output: &Output, failure: &str) -> ActionResult {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() && stderr.contains(NO_INSTALL_CONFIG) {
+        return Err(format!("{}: {}", failure, stderr.trim()));
+    }
+    action_result(output, failure, &[])
+}
+
+/// The inverse of `stop_and_disable_service`: enables the unit, then starts it.
+pub fn enable_and_start_service(service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    enable_and_start_service_with(default_runner(), service_name, extra)
+}
+
+fn enable_and_start_service_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    extra: &ExtraArgs,
+) -> ActionResult {
+    let output = unit_command(runner, "enable", extra, service_name)?;
+    let enabled = action_result(&output, "Failed to enable service", &[])?;
+
+    let output = unit_command(runner, "start", extra, service_name)?;
+    let failure = format!("Enabled {}, but failed to start it", service_name);
+    let started = action_result(&output, &failure, &[JOB_CANCELED])?;
+
+    Ok(join_warnings(enabled, started))
+}
+
+/// A symlink `systemctl enable` or `disable` would create or remove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymlinkChange {
+    Created { link: String, target: String },
+    Removed(String),
+}
+
+impl fmt::Display for SymlinkChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SymlinkChange::Created { link, target } => write!(f, "+ {} → {}", link, target),
+            SymlinkChange::Removed(link) => write!(f, "- {}", link),
+        }
+    }
+}
+
+/// What `systemctl <verb> --dry-run` reports for the unit, `verb` being
+/// `enable` or `disable`. `None` when this systemd doesn't know `--dry-run`
+/// (added in v236), so callers can go ahead without a preview.
+pub fn preview_unit_file_change(
+    verb: &str,
+    service_name: &str,
+    extra: &ExtraArgs,
+) -> Result<Option<Vec<SymlinkChange>>, String> {
+    preview_unit_file_change_with(default_runner(), verb, service_name, extra)
+}
+
+fn preview_unit_file_change_with(
+    runner: &dyn CommandRunner,
+    verb: &str,
+    service_name: &str,
+    extra: &ExtraArgs,
+) -> Result<Option<Vec<SymlinkChange>>, String> {
+    let output = SystemctlCommand::new(verb)
+        .arg("--dry-run")
+        .extra(extra)
+        .unit(service_name)
+        .run(runner)?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        if stderr.contains("--dry-run") {
+            return Ok(None);
+        }
+        return Err(format!("Failed to preview {}: {}", verb, stderr.trim()));
+    }
+    Ok(Some(stderr.lines().filter_map(parse_symlink_change).collect()))
+}
+
+/// Parses systemctl's `Created symlink A → B.` and `Removed A.` messages;
+/// newer versions quote the paths.
+fn parse_symlink_change(line: &str) -> Option<SymlinkChange> {
+    let path = |s: &str| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+    let line = line.trim().strip_suffix('.').unwrap_or(line.trim());
+    if let Some(rest) = line.strip_prefix("Created symlink ") {
+        let (link, target) = rest.split_once(" → ")?;
+        return Some(SymlinkChange::Created { link: path(link), target: path(target) });
+    }
+    let rest = line.strip_prefix("Removed ")?;
+    Some(SymlinkChange::Removed(path(rest.strip_prefix("symlink ").unwrap_or(rest))))
+}
+
+/// Reloads the manager configuration and then restarts the unit, so an
+/// edited unit file takes effect.
+pub fn reload_and_restart_service(service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    reload_and_restart_service_with(default_runner(), service_name, extra)
+}
+
+fn reload_and_restart_service_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    extra: &ExtraArgs,
+) -> ActionResult {
+    let output = SystemctlCommand::new("daemon-reload").run(runner)?;
+    let reloaded = action_result(&output, "Failed to reload systemd manager configuration", &[])?;
+
+    let output = unit_command(runner, "restart", extra, service_name)?;
+    let failure = format!("Reloaded systemd, but failed to restart {}", service_name);
+    let restarted = action_result(&output, &failure, &[JOB_CANCELED])?;
+
+    Ok(join_warnings(reloaded, restarted))
+}
+
+fn join_warnings(first: Option<String>, second: Option<String>) -> Option<String> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(format!("{}\n{}", first, second)),
+        (first, second) => first.or(second),
+    }
+}
+
+/// Restarts several units with a single `systemctl restart`, e.g. every unit
+/// matching the name filter. systemctl queues all of them before failing, so
+/// an error names the units it couldn't restart.
+pub fn restart_services(service_names: &[String], extra: &ExtraArgs) -> ActionResult {
+    restart_services_with(default_runner(), service_names, extra)
+}
+
+fn restart_services_with(
+    runner: &dyn CommandRunner,
+    service_names: &[String],
+    extra: &ExtraArgs,
+) -> ActionResult {
+    let output = SystemctlCommand::new("restart")
+        .extra(extra)
+        .units(service_names.iter().map(String::as_str))
+        .run(runner)?;
+
+    action_result(&output, "Failed to restart services", &[JOB_CANCELED])
+}
+
+/// Starts (`active`) or stops each unit that isn't in that state yet, one at
+/// a time so a failing unit doesn't hold back the rest. Gives `Ok(true)` for
+/// units that were changed and `Ok(false)` for ones already there.
+pub fn set_units_active(
+    units: &[String],
+    active: bool,
+    extra: &ExtraArgs,
+) -> Vec<(String, Result<bool, String>)> {
+    set_units_active_with(default_runner(), units, active, extra)
+}
+
+fn set_units_active_with(
+    runner: &dyn CommandRunner,
+    units: &[String],
+    active: bool,
+    extra: &ExtraArgs,
+) -> Vec<(String, Result<bool, String>)> {
+    units
+        .iter()
+        .map(|unit| {
+            let result = quick_check(runner, "is-active", unit).and_then(|is_active| {
+                if is_active == active {
+                    return Ok(false);
+                }
+                let changed = if active {
+                    start_service_with(runner, unit, extra, ServiceScope::System)
+                } else {
+                    stop_service_with(runner, unit, extra, ServiceScope::System)
+                };
+                changed.map(|_| true)
+            });
+            (unit.clone(), result)
+        })
+        .collect()
+}
+
+/// Restarts the unit only if it is currently active; inactive units are left
+/// stopped and the call succeeds without doing anything.
+pub fn try_restart_service(service_name: &str, extra: &ExtraArgs) -> ActionResult {
+    let output = unit_command(default_runner(), "try-restart", extra, service_name)?;
+
+    action_result(&output, "Failed to try-restart service", &[JOB_CANCELED])
+}
+
+/// Clears the failed state (and restart counter) of a single unit.
+pub fn reset_failed_service(service_name: &str) -> ActionResult {
+    let output = SystemctlCommand::new("reset-failed").unit(service_name).run(default_runner())?;
+
+    action_result(&output, "Failed to reset failed state", &[NOT_LOADED])
+}
+
+/// Clears the failed state of every unit.
+pub fn reset_failed_all() -> ActionResult {
+    let output = SystemctlCommand::new("reset-failed").run(default_runner())?;
+
+    action_result(&output, "Failed to reset failed units", &[])
+}
+
+pub fn reload_service(service_name: &str, scope: ServiceScope) -> ActionResult {
+    reload_service_with(default_runner(), service_name, scope)
+}
+
+fn reload_service_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    scope: ServiceScope,
+) -> ActionResult {
+    let output = SystemctlCommand::new("reload").scope(scope).unit(service_name).run(runner)?;
+
+    action_result(&output, "Failed to reload service", &[JOB_CANCELED])
+}
+
+/// Reloads the manager configuration, then `reload`s each of `units` (not
+/// restarts), so changed configuration is applied without disrupting them.
+/// A unit that fails to reload doesn't stop the rest; they are listed in the
+/// warning. Only a failed daemon-reload is an error.
+pub fn reload_all(units: &[String]) -> ActionResult {
+    reload_all_with(default_runner(), units)
+}
+
+fn reload_all_with(runner: &dyn CommandRunner, units: &[String]) -> ActionResult {
+    let output = SystemctlCommand::new("daemon-reload").run(runner)?;
+    let reloaded = action_result(&output, "Failed to reload systemd manager configuration", &[])?;
+
+    let failed: Vec<String> = units
+        .iter()
+        .filter_map(|unit| match reload_service_with(runner, unit, ServiceScope::System) {
+            Ok(_) => None,
+            Err(e) => Some(format!("{} ({})", unit, e)),
+        })
+        .collect();
+    if failed.is_empty() {
+        return Ok(reloaded);
+    }
+
+    Ok(Some(format!(
+        "Reloaded {} of {} units; failed: {}",
+        units.len() - failed.len(),
+        units.len(),
+        failed.join(", ")
+    )))
+}
+
+pub fn daemon_reload() -> ActionResult {
+    let output = SystemctlCommand::new("daemon-reload").run(default_runner())?;
+
+    action_result(&output, "Failed to reload systemd manager configuration", &[])
+}
+
+/// Re-executes the systemd manager, serializing and restoring its state. More
+/// disruptive than `daemon_reload`; mostly needed after upgrading systemd.
+pub fn daemon_reexec() -> ActionResult {
+    let output = SystemctlCommand::new("daemon-reexec").run(default_runner())?;
+
+    action_result(&output, "Failed to re-execute systemd manager", &[])
+}
+
+/// The drop-in file `systemctl edit` would create for `service_name`.
+pub fn override_path(service_name: &str) -> PathBuf {
+    Path::new("/etc/systemd/system")
+        .join(format!("{}.d", unit_name(service_name)))
+        .join("override.conf")
+}
+
+/// Returns the current override, or an empty string if there is none yet.
+pub fn read_override(service_name: &str) -> Result<String, String> {
+    let path = override_path(service_name);
+    match fs::read_to_string(&path) {
+        Ok(contents) => Ok(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Validates and writes the override, then reloads the manager so it takes
+/// effect. Falls back to pkexec when /etc is not writable by this user.
+pub fn write_override(service_name: &str, contents: &str) -> Result<(), String> {
+    validate_unit_snippet(contents)?;
+
+    let path = override_path(service_name);
+    let direct = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, contents));
+
+    match direct {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            write_file_privileged(&path, contents)?;
+        }
+        Err(e) => return Err(format!("Failed to write {}: {}", path.display(), e)),
+    }
+
+    daemon_reload().map(|_| ())
+}
+
+fn write_file_privileged(path: &Path, contents: &str) -> Result<(), String> {
+    let started = Instant::now();
+    let mut command = Command::new("pkexec");
+    command
+        .args(["sh", "-c", "mkdir -p -- \"$(dirname -- \"$1\")\" && cat > \"$1\"", "sh"])
+        .arg(path);
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute pkexec: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(contents.as_bytes())
+            .map_err(|e| format!("Failed to send override to pkexec: {}", e))?;
+    }
+
+    let output = child.wait_with_output();
+    trace_finished(&command, started, &output);
+    let output = output.map_err(|e| format!("Failed to wait for pkexec: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = pkexec_failure(&stderr).map_or(stderr.trim(), |reason| reason);
+        return Err(format!("Failed to write {} as root: {}", path.display(), reason));
+    }
+
+    Ok(())
+}
+
+/// A readable explanation for pkexec's own failures, which otherwise read as
+/// "Error executing command as another user: ...". `None` for errors of the
+/// command it ran.
+fn pkexec_failure(stderr: &str) -> Option<&'static str> {
+    if stderr.contains("No authentication agent found") {
+        Some(
+            "no authentication agent available; run as root or start a PolicyKit agent \
+             (e.g. polkit-gnome-authentication-agent-1 or lxpolkit)",
+        )
+    } else if stderr.contains("Request dismissed") || stderr.contains("Not authorized") {
+        Some("authorization was cancelled or denied")
+    } else {
+        None
+    }
+}
+
+/// Resource limits the detail panel offers to change with `set-property`.
+pub const LIMIT_PROPERTIES: [&str; 3] = ["MemoryMax", "CPUQuota", "TasksMax"];
+
+/// Changes a resource limit of a running unit, e.g. `CPUQuota=20%`. With
+/// `runtime` it only lasts until the next reboot; otherwise systemctl also
+/// writes it to a drop-in under `/etc/systemd/system.control`.
+pub fn set_property(
+    service_name: &str,
+    property: &str,
+    value: &str,
+    runtime: bool,
+) -> ActionResult {
+    set_property_with(default_runner(), service_name, property, value, runtime)
+}
+
+fn set_property_with(
+    runner: &dyn CommandRunner,
+    service_name: &str,
+    property: &str,
+    value: &str,
+    runtime: bool,
+) -> ActionResult {
+    validate_limit(property, value)?;
+    let output = SystemctlCommand::new("set-property")
+        .args(runtime.then_some("--runtime"))
+        .unit(service_name)
+        .arg(format!("{}={}", property, value.trim()))
+        .run(runner)?;
+
+    action_result(&output, &format!("Failed to set {}", property), &[])
+}
+
+/// Checks a value for one of `LIMIT_PROPERTIES` before handing it to
+/// systemctl: a byte size with an optional `K`..`E` suffix, a count, a
+/// percentage, or `infinity`, depending on the property.
+pub fn validate_limit(property: &str, value: &str) -> Result<(), String> {
+    let value = value.trim();
+    let number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let percentage = |s: &str| {
+        s.strip_suffix('%')
+            .is_some_and(|n| n.split_once('.').map_or(number(n), |(a, b)| number(a) && number(b)))
+    };
+    let size = |s: &str| number(s.strip_suffix(['K', 'M', 'G', 'T', 'P', 'E']).unwrap_or(s));
+
+    let valid = match property {
+        "MemoryMax" => value == "infinity" || percentage(value) || size(value),
+        "CPUQuota" => percentage(value),
+        "TasksMax" => value == "infinity" || percentage(value) || number(value),
+        _ => return Err(format!("{} can't be changed here", property)),
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid value for {}: {}", property, value))
+    }
+}
+
+/// Checks that `contents` looks like a unit file fragment: only comments,
+/// `[Section]` headers and `Key=Value` lines, with every directive inside a
+/// section. Values can continue onto the next line with a trailing `\`.
+pub fn validate_unit_snippet(contents: &str) -> Result<(), String> {
+    let mut in_section = false;
+    let mut continued = false;
+
+    for (index, raw) in contents.lines().enumerate() {
+        let line = raw.trim();
+        let line_no = index + 1;
+
+        if continued {
+            continued = line.ends_with('\\');
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let name = line
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .filter(|name| !name.trim().is_empty())
+                .ok_or_else(|| format!("Line {}: malformed section header", line_no))?;
+            if name.contains(['[', ']']) {
+                return Err(format!("Line {}: malformed section header", line_no));
+            }
+            in_section = true;
+            continue;
+        }
+
+        let Some((key, _)) = line.split_once('=') else {
+            return Err(format!("Line {}: expected Key=Value", line_no));
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Line {}: invalid key '{}'", line_no, key));
+        }
+        if !in_section {
+            return Err(format!("Line {}: '{}' must be inside a [Section]", line_no, key));
+        }
+
+        continued = line.ends_with('\\');
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    /// Replays canned outputs in order and records the argument lists it saw.
+    /// Running out of responses looks like a missing binary.
+    #[derive(Default)]
+    struct MockRunner {
+        responses: RefCell<VecDeque<io::Result<Output>>>,
+        calls: RefCell<Vec<Vec<String>>>,
+        target: Option<RemoteTarget>,
+    }
+
+    impl MockRunner {
+        fn on(self, target: RemoteTarget) -> Self {
+            MockRunner { target: Some(target), ..self }
+        }
+
+        fn respond(self, code: i32, stdout: &str, stderr: &str) -> Self {
+            self.responses.borrow_mut().push_back(Ok(Output {
+                status: ExitStatus::from_raw(code << 8),
+                stdout: stdout.as_bytes().to_vec(),
+                stderr: stderr.as_bytes().to_vec(),
+            }));
+            self
+        }
+
+        fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.borrow().clone()
+        }
+    }
+
+    impl CommandRunner for MockRunner {
+        fn output(&self, program: &str, args: &[&str]) -> io::Result<Output> {
+            let mut call = vec![program.to_string()];
+            call.extend(args.iter().map(|arg| arg.to_string()));
+            self.calls.borrow_mut().push(call);
+            self.responses
+                .borrow_mut()
+                .pop_front()
+                .unwrap_or_else(|| Err(io::Error::from(io::ErrorKind::NotFound)))
+        }
+
+        fn target(&self) -> Option<&RemoteTarget> {
+            self.target.as_ref()
+        }
+    }
+
+    const UNIT_FILES: &str = r#"[
+        {"unit_file": "sshd.service", "state": "enabled", "preset": "disabled"}
+    ]"#;
+
+    #[test]
+    fn list_services_parses_bare_array() {
+        let runner = MockRunner::default()
+            .respond(
+                0,
+                r#"[{"unit": "sshd.service", "load": "loaded", "active": "active",
+                     "sub": "running", "description": "OpenSSH Daemon"}]"#,
+                "",
+            )
+            .respond(0, UNIT_FILES, "");
+
+        let services = list_services_with(&runner, None, ServiceScope::System).unwrap();
+        assert_eq!(services.len(), 1);
+        let sshd = &services[0];
+        assert_eq!(sshd.name, "sshd.service");
+        assert_eq!(sshd.description, "OpenSSH Daemon");
+        assert!(sshd.is_running());
+        assert_eq!(sshd.unit_file_state, "enabled");
+        assert_eq!(sshd.vendor_preset, "disabled");
+
+        let calls = runner.calls();
+        assert_eq!(calls[0][1], "list-units");
+        assert_eq!(calls[1][1], "list-unit-files");
+    }
+
+    #[test]
+    fn list_services_parses_units_object() {
+        let runner = MockRunner::default()
+            .respond(
+                0,
+                r#"{"version": 2, "units": [
+                    {"Unit": "cron.service", "LoadState": "loaded", "ActiveState": "failed",
+                     "SubState": "failed", "Description": null},
+                    {"Unit": "", "ActiveState": "active"}
+                ]}"#,
+                "",
+            )
+            .respond(0, "[]", "");
+
+        let services = list_services_with(&runner, None, ServiceScope::System).unwrap();
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].name, "cron.service");
+        assert!(services[0].is_failed());
+        assert_eq!(services[0].description, "");
+    }
+
+    #[test]
+    fn list_services_filters_by_state() {
+        let runner = MockRunner::default()
+            .respond(
+                0,
+                r#"[{"unit": "cron.service", "load": "loaded", "active": "failed",
+                     "sub": "failed", "description": "Cron"}]"#,
+                "",
+            )
+            .respond(0, "[]", "");
+
+        let services = list_services_with(&runner, Some("failed"), ServiceScope::System).unwrap();
+        assert_eq!(services.len(), 1);
+        assert_eq!(
+            runner.calls(),
+            [
+                vec![
+                    "systemctl", "list-units", "--type=service", "--all", "--state=failed",
+                    "--no-pager", "--output=json",
+                ],
+                vec![
+                    "systemctl", "list-unit-files", "--type=service", "--no-pager",
+                    "--output=json", "cron.service",
+                ],
+            ]
+        );
+
+        // Nothing failed: no unit files to look up.
+        let runner = MockRunner::default().respond(0, "[]", "");
+        let services = list_services_with(&runner, Some("failed"), ServiceScope::System).unwrap();
+        assert!(services.is_empty());
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[test]
+    fn user_scope_talks_to_the_user_manager() {
+        let runner = MockRunner::default().respond(0, "[]", "").respond(0, "[]", "");
+        list_services_with(&runner, None, ServiceScope::User).unwrap();
+        assert_eq!(runner.calls()[0][..3], ["systemctl", "list-units", "--user"]);
+        assert_eq!(runner.calls()[1][..3], ["systemctl", "list-unit-files", "--user"]);
+
+        let runner = MockRunner::default().respond(0, "", "");
+        let result = start_service_with(
+            &runner,
+            "syncthing.service",
+            &ExtraArgs::default(),
+            ServiceScope::User,
+        );
+        assert_eq!(result, Ok(None));
+        assert_eq!(runner.calls(), [["systemctl", "start", "--user", "syncthing.service"]]);
+    }
+
+    #[test]
+    fn parse_services_skips_null_aliases() {
+        let services = parse_services(
+            r#"[{"unit": null, "Unit": "a.service", "id": "ignored.service",
+                 "load": null, "LoadState": "loaded", "Load": null,
+                 "active": "active", "ActiveState": null,
+                 "sub": null, "SubState": null, "Sub": "running",
+                 "description": null, "Description": "A",
+                 "followed": null, "Following": ["b.service", null, ""]}]"#,
+        )
+        .unwrap();
+
+        let unit = &services[0];
+        assert_eq!(unit.name, "a.service");
+        assert_eq!(unit.load_state, "loaded");
+        assert_eq!(unit.active_state, "active");
+        assert_eq!(unit.sub_state, "running");
+        assert_eq!(unit.description, "A");
+        assert_eq!(unit.followed_by, ["b.service"]);
+    }
+
+    #[test]
+    fn parse_services_reads_saved_list_units_output() {
+        let services = parse_services(
+            r#"[{"unit": "sshd.service", "load": "loaded", "active": "active",
+                 "sub": "running", "description": "OpenSSH server"}]"#,
+        )
+        .unwrap();
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].name, "sshd.service");
+        assert!(services[0].is_running());
+
+        assert!(parse_services("not json").unwrap_err().starts_with("Failed to parse JSON"));
+    }
+
+    #[test]
+    fn unit_snippets_are_validated_line_by_line() {
+        let cases = [
+            ("[Service]\nMemoryMax=1G\n", Ok(())),
+            ("", Ok(())),
+            ("# MemoryMax=1G\n; [odd\n[Service]\n", Ok(())),
+            ("[Service]\nExecStart=/bin/foo \\\n  --a=1 \\\n  --b=x\nRestart=always\n", Ok(())),
+            ("MemoryMax=1G\n[Service]\n", Err("Line 1: 'MemoryMax' must be inside a [Section]")),
+            ("[]\n", Err("Line 1: malformed section header")),
+            ("[ ]\n", Err("Line 1: malformed section header")),
+            ("[Service]\n[a]b]\n", Err("Line 2: malformed section header")),
+            ("[Service\n", Err("Line 1: malformed section header")),
+            ("[Service]\nExec Start=/bin/true\n", Err("Line 2: invalid key 'Exec Start'")),
+            ("[Service]\n=1G\n", Err("Line 2: invalid key ''")),
+            ("[Service]\nRestart\n", Err("Line 2: expected Key=Value")),
+        ];
+        for (contents, expected) in cases {
+            assert_eq!(
+                validate_unit_snippet(contents),
+                expected.map_err(str::to_string),
+                "{:?}",
+                contents
+            );
+        }
+    }
+
+    #[test]
+    fn unit_file_states_are_parsed_with_their_toggleability() {
+        let cases = [
+            ("enabled", UnitFileState::Enabled, true),
+            ("enabled-runtime", UnitFileState::EnabledRuntime, true),
+            ("disabled", UnitFileState::Disabled, true),
+            ("static", UnitFileState::Static, false),
+            ("masked", UnitFileState::Masked, false),
+            ("masked-runtime", UnitFileState::MaskedRuntime, false),
+            ("indirect", UnitFileState::Indirect, true),
+            ("generated", UnitFileState::Generated, false),
+            ("frobnicated", UnitFileState::Unknown("frobnicated".to_string()), false),
+            ("", UnitFileState::Unknown(String::new()), false),
+        ];
+        for (value, state, toggleable) in cases {
+            let parsed = UnitFileState::parse(value);
+            assert_eq!(parsed, state, "{:?}", value);
+            assert_eq!(parsed.is_toggleable(), toggleable, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn journal_args_combine_boot_and_priority() {
+        let base = ["--no-pager", "--output=short-iso", "--lines=200", "--unit=a.service"];
+        assert_eq!(journal_args("a.service", LogFilter::default(), LOG_LINES), base);
+
+        let filter = LogFilter {
+            current_boot: true,
+            priority: Some(LogPriority::Err),
+        };
+        let mut expected = base.to_vec();
+        expected.extend(["--boot", "--priority=err"]);
+        assert_eq!(journal_args("a.service", filter, LOG_LINES), expected);
+    }
+
+    #[test]
+    fn last_log_times_keep_the_newest_entry_per_unit() {
+        let runner = MockRunner::default().respond(
+            0,
+            r#"{"_SYSTEMD_UNIT": "nginx.service", "__REALTIME_TIMESTAMP": "3000000"}
+               {"_SYSTEMD_UNIT": "other.service", "__REALTIME_TIMESTAMP": "2500000"}
+               not json
+               {"_SYSTEMD_UNIT": "nginx.service", "__REALTIME_TIMESTAMP": "2000000"}
+               {"_SYSTEMD_UNIT": "cron.service", "__REALTIME_TIMESTAMP": "1000000"}"#,
+            "",
+        );
+        let units = ["nginx.service".to_string(), "cron.service".to_string()];
+        let found = last_log_times_with(&runner, &units).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found["nginx.service"], UNIX_EPOCH + Duration::from_secs(3));
+        assert_eq!(found["cron.service"], UNIX_EPOCH + Duration::from_secs(1));
+        assert_eq!(runner.calls()[0][6..], ["-u", "nginx.service", "-u", "cron.service"]);
+
+        let runner = MockRunner::default().respond(1, "", "No journal files were found.");
+        let error = last_log_times_with(&runner, &units).unwrap_err();
+        assert!(error.contains("No journal files"), "{}", error);
+    }
+
+    #[test]
+    fn log_lines_surface_the_permission_hint() {
+        let runner = MockRunner::default().respond(
+            0,
+            "2024-05-01T10:00:00+0200 host nginx[12]: started\n\
+             2024-05-01T10:00:01+0200 host nginx[12]: ready\n",
+            "",
+        );
+        let lines = get_service_log_lines_with(&runner, "nginx", 50).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with("ready"));
+        assert_eq!(runner.calls()[0][3..], ["--lines=50", "--unit=nginx.service"]);
+
+        let runner = MockRunner::default().respond(
+            0,
+            "-- No entries --\n",
+            "Hint: You are currently not seeing messages from other users and the system.\n",
+        );
+        let error = get_service_log_lines_with(&runner, "nginx", 50).unwrap_err();
+        assert!(error.contains("not seeing messages"), "{}", error);
+
+        let error = get_service_log_lines_with(&MockRunner::default(), "nginx", 50).unwrap_err();
+        assert_eq!(error, "journalctl is not installed");
+    }
+
+    #[test]
+    fn service_logs_report_journalctl_failure() {
+        let runner = MockRunner::default().respond(1, "", "No journal boot entry found for the specified boot offset (+0).\n");
+
+        let filter = LogFilter {
+            current_boot: true,
+            priority: None,
+        };
+        let error = get_service_logs_with(&runner, "a", filter).unwrap_err();
+        assert!(error.starts_with("Failed to read journal: No journal boot entry"), "{}", error);
+        assert_eq!(runner.calls()[0][0], "journalctl");
+        assert_eq!(runner.calls()[0][4], "--unit=a.service");
+    }
+
+    #[test]
+    fn list_services_ignores_unit_file_failure() {
+        let runner = MockRunner::default()
+            .respond(0, r#"[{"unit": "a.service", "active": "inactive"}]"#, "")
+            .respond(1, "", "Unknown command verb list-unit-files.");
+
+        let services = list_services_with(&runner, None, ServiceScope::System).unwrap();
+        assert_eq!(services[0].unit_file_state, "");
+    }
+
+    #[test]
+    fn list_services_reports_exit_status() {
+        let runner = MockRunner::default().respond(1, "", "Failed to connect to bus");
+
+        let error = list_services_with(&runner, None, ServiceScope::System).unwrap_err();
+        assert!(error.starts_with("systemctl command failed"), "{}", error);
+    }
+
+    #[test]
+    fn list_services_reports_bad_json() {
+        let runner = MockRunner::default().respond(0, "UNIT LOAD ACTIVE SUB", "");
+
+        let error = list_services_with(&runner, None, ServiceScope::System).unwrap_err();
+        assert!(error.starts_with("Failed to parse JSON"), "{}", error);
+    }
+
+    #[test]
+    fn list_services_reports_missing_systemctl() {
+        let error =
+            list_services_with(&MockRunner::default(), None, ServiceScope::System).unwrap_err();
+        assert!(error.starts_with("Failed to execute systemctl"), "{}", error);
+    }
+
+    #[test]
+    fn unit_name_appends_service_suffix() {
+        assert_eq!(unit_name("nginx"), "nginx.service");
+        assert_eq!(unit_name("nginx.service"), "nginx.service");
+        assert_eq!(unit_name("foo.socket"), "foo.socket");
+        assert_eq!(unit_name("getty@tty1"), "getty@tty1.service");
+        assert_eq!(unit_name("getty@tty1.service"), "getty@tty1.service");
+        assert_eq!(unit_name("node.js"), "node.js.service");
+    }
+
+    #[test]
+    fn actions_use_normalized_names() {
+        let runner = MockRunner::default().respond(0, "", "").respond(0, "", "");
+
+        start_service_with(&runner, "nginx", &ExtraArgs::default(), ServiceScope::System).unwrap();
+        quick_check(&runner, "is-active", "getty@tty1").unwrap();
+        assert_eq!(runner.calls()[0], ["systemctl", "start", "nginx.service"]);
+        assert_eq!(runner.calls()[1], ["systemctl", "is-active", "--quiet", "getty@tty1.service"]);
+    }
+
+    #[test]
+    fn systemctl_command_builds_args_in_order() {
+        let mut extra = ExtraArgs::default();
+        extra.set("restart", "--no-block", true);
+        let command = SystemctlCommand::new("restart").extra(&extra).units(["nginx", "getty@tty1"]);
+        assert_eq!(
+            command.arg_list(),
+            ["restart", "--no-block", "nginx.service", "getty@tty1.service"]
+        );
+        assert_eq!(
+            SystemctlCommand::new("list-jobs").no_pager().json().arg_list(),
+            ["list-jobs", "--no-pager", "--output=json"]
+        );
+        assert_eq!(
+            SystemctlCommand::new("start").unit("foo\\x2dbar").command_line(),
+            "systemctl start 'foo\\x2dbar.service'"
+        );
+    }
+
+    #[test]
+    fn remote_flags_follow_the_verb() {
+        assert_eq!(
+            SystemctlCommand::new("restart").host("root@box").unit("nginx").arg_list(),
+            ["restart", "--host=root@box", "nginx.service"]
+        );
+        assert_eq!(
+            SystemctlCommand::new("list-units")
+                .machine("web")
+                .scope(ServiceScope::User)
+                .json()
+                .command_line(),
+            "systemctl list-units --machine=web --user --output=json"
+        );
+        let machine = RemoteTarget::Machine("web".to_string());
+        assert_eq!(
+            SystemctlCommand::new("stop").unit("nginx").target(Some(&machine)).arg_list(),
+            ["stop", "--machine=web", "nginx.service"]
+        );
+        assert_eq!(
+            SystemctlCommand::new("stop").unit("nginx").target(None).arg_list(),
+            ["stop", "nginx.service"]
+        );
+    }
+
+    #[test]
+    fn actions_go_to_the_runners_target() {
+        let runner = MockRunner::default()
+            .on(RemoteTarget::Host("root@box".to_string()))
+            .respond(0, "", "");
+        let result =
+            restart_service_with(&runner, "nginx", &ExtraArgs::default(), ServiceScope::User);
+        assert_eq!(result, Ok(None));
+        assert_eq!(
+            runner.calls(),
+            [["systemctl", "restart", "--host=root@box", "--user", "nginx.service"]]
+        );
+    }
+
+    #[test]
+    fn unescape_decodes_hex_escapes() {
+        assert_eq!(unescape_unit_name("nginx.service"), "nginx.service");
+        assert_eq!(unescape_unit_name("foo\\x2dbar@a\\x20b.service"), "foo-bar@a b.service");
+        assert_eq!(unescape_unit_name("odd\\xzz\\x"), "odd\\xzz\\x");
+    }
+
+    #[test]
+    fn aliases_collapse_onto_the_unit_named_like_the_file() {
+        let unit = |name: &str, fragment: &str, followed_by: &[&str]| ServiceInfo {
+            name: name.to_string(),
+            fragment_path: fragment.to_string(),
+            followed_by: followed_by.iter().map(|name| name.to_string()).collect(),
+            ..ServiceInfo::default()
+        };
+        let services = [
+            unit("syslog.service", "/usr/lib/systemd/system/rsyslog.service", &[]),
+            unit("rsyslog.service", "/usr/lib/systemd/system/rsyslog.service", &[]),
+            unit("dbus-org.freedesktop.a.service", "", &["a-helper.service"]),
+            unit("a-helper.service", "", &["a.service"]),
+            unit("a.service", "/etc/systemd/system/a.service", &[]),
+            unit("b.service", "/etc/systemd/system/b.service", &["gone.service"]),
+        ];
+
+        let aliases = alias_targets(&services);
+        assert_eq!(aliases["syslog.service"], "rsyslog.service");
+        assert_eq!(aliases["a-helper.service"], "a.service");
+        assert_eq!(aliases["dbus-org.freedesktop.a.service"], "a.service");
+        assert_eq!(aliases.len(), 3);
+    }
+
+    #[test]
+    fn extra_args_are_validated_and_passed() {
+        let mut extra = ExtraArgs::default();
+        extra.set("enable", "--now", true);
+        extra.set("start", "--now", true);
+        extra.set("enable", "--force", true);
+        extra.set("enable", "--force", false);
+        assert_eq!(extra.args("enable"), ["--now"]);
+        assert!(extra.args("start").is_empty());
+
+        let mut edited: ExtraArgs =
+            serde_json::from_str(r#"{"stop": ["--no-block", "; rm -rf /"], "mask": ["--now"]}"#)
+                .unwrap();
+        edited.retain_allowed();
+        assert_eq!(edited.args("stop"), ["--no-block"]);
+        assert!(edited.args("mask").is_empty());
+
+        let runner = MockRunner::default().respond(0, "", "").respond(0, "", "");
+        enable_and_start_service_with(&runner, "a.service", &extra).unwrap();
+        assert_eq!(runner.calls()[0], ["systemctl", "enable", "--now", "a.service"]);
+        assert_eq!(runner.calls()[1], ["systemctl", "start", "a.service"]);
+    }
+
+    #[test]
+    fn reverse_dependencies_are_flattened() {
+        let runner = MockRunner::default().respond(
+            0,
+            "dbus.service\n  ● systemd-logind.service\n  multi-user.target\n    graphical.target\n  \
+             multi-user.target\n",
+            "",
+        );
+
+        assert_eq!(
+            reverse_dependencies_with(&runner, "dbus").unwrap(),
+            ["systemd-logind.service", "multi-user.target", "graphical.target"]
+        );
+        assert_eq!(
+            runner.calls()[0],
+            ["systemctl", "list-dependencies", "--reverse", "--plain", "--no-pager", "dbus.service"]
+        );
+    }
+
+    #[test]
+    fn stop_dependents_follow_requires_and_bindsto() {
+        let runner = MockRunner::default()
+            .respond(0, "ActiveState=active\nRequiredBy=app.service\nBoundBy=sidecar.service\n", "")
+            .respond(
+                0,
+                "ActiveState=active\nRequiredBy=web.service\nBoundBy=\n\n\
+                 ActiveState=inactive\nRequiredBy=\nBoundBy=\n",
+                "",
+            )
+            .respond(0, "ActiveState=activating\nRequiredBy=app.service\nBoundBy=\n", "");
+
+        assert_eq!(
+            stop_dependents_with(&runner, "db").unwrap(),
+            ["app.service", "web.service"]
+        );
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0][4], "db.service");
+        assert_eq!(calls[1][4..], ["app.service", "sidecar.service"]);
+        assert_eq!(calls[2][4..], ["web.service"]);
+    }
+
+    #[test]
+    fn properties_are_sorted_by_name() {
+        let runner = MockRunner::default().respond(
+            0,
+            "Type=simple\nExecStart={ path=/usr/bin/app ; argv[]=/usr/bin/app }\nDescription=App\n",
+            "",
+        );
+
+        let properties = properties_with(&runner, "app").unwrap();
+        let names: Vec<&str> = properties.keys().map(String::as_str).collect();
+        assert_eq!(names, ["Description", "ExecStart", "Type"]);
+        assert_eq!(runner.calls()[0], ["systemctl", "show", "--no-pager", "app.service"]);
+    }
+
+    #[test]
+    fn pkexec_failures_are_explained() {
+        let no_agent = "Error executing command as another user: No authentication agent found.\n";
+        assert!(pkexec_failure(no_agent).unwrap().starts_with("no authentication agent available"));
+        assert_eq!(
+            pkexec_failure("Error executing command as another user: Request dismissed\n"),
+            Some("authorization was cancelled or denied")
+        );
+        assert_eq!(pkexec_failure("sh: 1: cannot create /etc/x: Read-only file system\n"), None);
+    }
+
+    #[test]
+    fn log_search_matches_messages_of_services() {
+        let runner = MockRunner::default().respond(
+            0,
+            "{\"_SYSTEMD_UNIT\":\"app.service\",\"MESSAGE\":\"Connection REFUSED by db\"}\n\
+             {\"_SYSTEMD_UNIT\":\"session-2.scope\",\"MESSAGE\":\"connection refused\"}\n\
+             {\"MESSAGE\":\"kernel: connection refused\"}\n\
+             {\"_SYSTEMD_UNIT\":\"db.service\",\"MESSAGE\":[99,111,110,110,101,99,116,105,111,110,\
+             32,114,101,102,117,115,101,100,7]}\n\
+             {\"_SYSTEMD_UNIT\":\"db.service\",\"MESSAGE\":\"ready\"}\n",
+            "",
+        );
+
+        let matches = search_logs_with(&runner, " connection refused ").unwrap();
+        let units: Vec<&str> = matches.iter().map(|m| m.unit.as_str()).collect();
+        assert_eq!(units, ["app.service", "db.service"]);
+        assert_eq!(matches[0].line, "Connection REFUSED by db");
+        assert_eq!(runner.calls()[0][5], "--lines=2000");
+
+        assert_eq!(search_logs_with(&MockRunner::default(), "  "), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn reload_all_keeps_going_after_a_failure() {
+        let runner = MockRunner::default()
+            .respond(0, "", "")
+            .respond(1, "", "Job for a.service failed.")
+            .respond(0, "", "");
+        let units = ["a.service".to_string(), "b.service".to_string()];
+
+        let warning = reload_all_with(&runner, &units).unwrap().unwrap();
+        assert!(warning.starts_with("Reloaded 1 of 2 units; failed: a.service ("));
+        assert_eq!(runner.calls()[0], ["systemctl", "daemon-reload"]);
+        assert_eq!(runner.calls()[2], ["systemctl", "reload", "b.service"]);
+
+        let runner = MockRunner::default().respond(1, "", "Access denied");
+        assert!(reload_all_with(&runner, &units).is_err());
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[test]
+    fn quick_check_maps_exit_codes() {
+        let runner = MockRunner::default()
+            .respond(0, "", "")
+            .respond(3, "", "")
+            .respond(1, "", "Failed to connect to bus: No such file or directory");
+
+        assert_eq!(quick_check(&runner, "is-active", "a.service"), Ok(true));
+        assert_eq!(quick_check(&runner, "is-active", "a.service"), Ok(false));
+        assert!(quick_check(&runner, "is-active", "a.service").is_err());
+        assert_eq!(runner.calls()[0], ["systemctl", "is-active", "--quiet", "a.service"]);
+    }
+
+    #[test]
+    fn session_units_follow_logind_user_and_display_manager() {
+        let runner = MockRunner::default()
+            .respond(0, "1000\n", "")
+            .respond(0, "gdm.service\n", "");
+
+        assert_eq!(
+            session_units_with(&runner, Some("2"), Some(0)),
+            ["user@1000.service", "user-runtime-dir@1000.service", "gdm.service"]
+        );
+        assert_eq!(
+            runner.calls()[0],
+            ["loginctl", "show-session", "2", "--property=User", "--value"]
+        );
+
+        // No logind session and running as root: only the display manager.
+        let runner = MockRunner::default().respond(0, "sddm.service\n", "");
+        assert_eq!(session_units_with(&runner, None, Some(0)), ["sddm.service"]);
+    }
+
+    #[test]
+    fn restart_services_passes_all_units_at_once() {
+        let runner = MockRunner::default().respond(0, "", "");
+        let mut extra = ExtraArgs::default();
+        extra.set("restart", "--no-block", true);
+
+        let names = ["nginx".to_string(), "nginx-exporter.service".to_string()];
+        assert_eq!(restart_services_with(&runner, &names, &extra), Ok(None));
+        assert_eq!(
+            runner.calls(),
+            [["systemctl", "restart", "--no-block", "nginx.service", "nginx-exporter.service"]]
+        );
+    }
+
+    #[test]
+    fn status_block_reads_failure_details() {
+        let status = parse_status_block(
+            "a.service",
+            "ActiveState=failed\nSubState=failed\nResult=exit-code\nNRestarts=5\nExecMainStatus=203\n",
+        );
+        assert_eq!(status.result, "exit-code");
+        assert_eq!(status.restarts, Some(5));
+        assert_eq!(status.exit_status, Some(203));
+    }
+
+    #[test]
+    fn status_block_reads_condition_checks() {
+        let skipped = parse_status_block(
+            "a.service",
+            "ActiveState=inactive\nConditionResult=no\n\
+             ConditionTimestamp=Tue 2026-10-13 09:12:01 CEST\nAssertResult=no\nAssertTimestamp=\n",
+        );
+        assert_eq!(skipped.condition_met, Some(false));
+        assert_eq!(skipped.assert_met, None);
+
+        let ran = parse_status_block(
+            "b.service",
+            "ConditionResult=yes\nConditionTimestamp=Tue 2026-10-13 09:12:01 CEST\n",
+        );
+        assert_eq!(ran.condition_met, Some(true));
+    }
+
+    #[test]
+    fn properties_keep_equals_signs_and_empty_values() {
+        let properties = parse_properties(
+            "Description=\n\
+             ExecStart={ path=/usr/bin/app ; argv[]=/usr/bin/app --mode=fast ; ignore_errors=no }\n\
+             Environment=LANG=C.UTF-8 \"GREETING=a b\"\n\
+             StatusText=first line\n\
+             second line\n\
+             garbage without equals\n\
+             MainPID=42",
+        );
+
+        assert_eq!(properties["Description"], "");
+        assert_eq!(
+            properties["ExecStart"],
+            "{ path=/usr/bin/app ; argv[]=/usr/bin/app --mode=fast ; ignore_errors=no }"
+        );
+        assert_eq!(properties["Environment"], "LANG=C.UTF-8 \"GREETING=a b\"");
+        assert_eq!(properties["StatusText"], "first line\nsecond line\ngarbage without equals");
+        assert_eq!(properties["MainPID"], "42");
+        assert_eq!(properties.len(), 5);
+    }
+
+    #[test]
+    fn status_block_handles_missing_and_unset_values() {
+        let status = parse_status_block(
+            "a.service",
+            "ActiveState=inactive\nMainPID=0\nTasksMax=infinity\nMemoryCurrent=[not set]\n\
+             CPUUsageNSec=[not set]\n",
+        );
+        assert_eq!(status.active_state, "inactive");
+        assert!(!status.active);
+        assert_eq!(status.pid, None);
+        assert_eq!(status.tasks_max, None);
+        assert_eq!(status.memory, None);
+        assert_eq!(status.cpu_usage_nsec, None);
+        assert_eq!(status.sub_state, "");
+        assert!(status.documentation.is_empty());
+        assert!(status.triggered_by.is_empty());
+
+        let status = parse_status_block("a.service", "TriggeredBy=a.socket a.timer\n");
+        assert_eq!(status.triggered_by, ["a.socket", "a.timer"]);
+    }
+
+    #[test]
+    fn set_units_active_skips_units_already_there() {
+        let runner = MockRunner::default()
+            .respond(0, "", "")
+            .respond(3, "", "")
+            .respond(0, "", "")
+            .respond(3, "", "")
+            .respond(1, "", "Job for app.service failed.\n");
+        let units = ["postgresql".to_string(), "redis".to_string(), "app".to_string()];
+
+        let results = set_units_active_with(&runner, &units, true, &ExtraArgs::default());
+        assert_eq!(results[0], ("postgresql".to_string(), Ok(false)));
+        assert_eq!(results[1], ("redis".to_string(), Ok(true)));
+        assert!(results[2].1.as_ref().unwrap_err().contains("app.service failed"));
+
+        let calls = runner.calls();
+        assert_eq!(calls.len(), 5);
+        assert_eq!(calls[2], ["systemctl", "start", "redis.service"]);
+    }
+
+    #[test]
+    fn environment_is_split_and_missing_files_reported() {
+        let runner = MockRunner::default().respond(
+            0,
+            "Environment=LANG=C \"GREETING=hello world\" API_TOKEN=abc\n\
+             EnvironmentFiles=/nonexistent/app.env (ignore_errors=no)\n\
+             EnvironmentFiles=/nonexistent/optional.env (ignore_errors=yes)\n",
+            "",
+        );
+
+        let environment = environment_with(&runner, "app").unwrap();
+        let vars: Vec<(&str, &str)> = environment
+            .vars
+            .iter()
+            .map(|var| (var.key.as_str(), var.value.as_str()))
+            .collect();
+        assert_eq!(vars, [("LANG", "C"), ("GREETING", "hello world"), ("API_TOKEN", "abc")]);
+        assert!(!environment.vars[0].is_sensitive());
+        assert!(environment.vars[2].is_sensitive());
+        assert_eq!(environment.unreadable.len(), 1);
+        assert_eq!(environment.unreadable[0].0, "/nonexistent/app.env");
+
+        let file = "# comment\nPORT=8080\n\nDB_PASSWORD=\"s3cret\"\n";
+        let parsed: Vec<_> = parse_environment_file(file).collect();
+        assert_eq!(
+            parsed,
+            [
+                ("PORT".to_string(), "8080".to_string()),
+                ("DB_PASSWORD".to_string(), "s3cret".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_command_substitutes_unit_per_argument() {
+        let command = custom_command(
+            "curl -fsS 'http://localhost/health?unit={unit}' --user-agent \"unit check\"",
+            "web@1.service",
+        )
+        .unwrap();
+        assert_eq!(
+            command,
+            [
+                "curl",
+                "-fsS",
+                "http://localhost/health?unit=web@1.service",
+                "--user-agent",
+                "unit check"
+            ]
+        );
+
+        assert!(custom_command("echo {unit}", "a.service; rm -rf ~").is_err());
+        assert!(custom_command("{unit} --check", "sshd.service").is_err());
+        assert!(custom_command("echo 'open", "sshd.service").is_err());
+        assert!(custom_command("  ", "sshd.service").is_err());
+    }
+
+    #[test]
+    fn custom_action_failure_carries_output() {
+        let runner = MockRunner::default()
+            .respond(0, "healthy\n", "")
+            .respond(2, "", "connection refused\n");
+
+        let ok = run_custom_action_with(&runner, "check {unit}", "web.service").unwrap();
+        assert_eq!(ok, "healthy\n");
+        let err = run_custom_action_with(&runner, "check {unit}", "web.service").unwrap_err();
+        assert_eq!(err, "check exited with 2\nconnection refused");
+        assert_eq!(runner.calls()[0], ["check", "web.service"]);
+    }
+
+    #[test]
+    fn disabling_a_static_unit_is_an_error() {
+        let runner = MockRunner::default()
+            .respond(
+                0,
+                "",
+                "The unit files have no installation config (WantedBy=, RequiredBy=, Also=,\n\
+                 Alias= settings in the [Install] section, and DefaultInstance= for template\n\
+                 units). This means they are not meant to be enabled or disabled using \
+                 systemctl.\n",
+            )
+            .respond(0, "", "Removed \"/etc/systemd/system/multi-user.target.wants/cups.service\".");
+
+        let error = disable_service_with(&runner, "systemd-journald", &ExtraArgs::default());
+        assert!(error.unwrap_err().starts_with("Failed to disable service: The unit files"));
+        assert_eq!(disable_service_with(&runner, "cups", &ExtraArgs::default()), Ok(None));
+        assert_eq!(runner.calls()[1], ["systemctl", "disable", "cups.service"]);
+    }
+
+    #[test]
+    fn blame_is_parsed_and_sorted() {
+        let runner = MockRunner::default().respond(
+            0,
+            "         532ms systemd-journald.service\n\
+             1min 2.345s plymouth-quit-wait.service\n\
+             5.001s NetworkManager-wait-online.service\n\
+             12us sys-kernel-config.mount\n",
+            "",
+        );
+
+        let blame = analyze_blame_with(&runner).unwrap();
+        let names: Vec<&str> = blame.iter().map(|(unit, _)| unit.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "plymouth-quit-wait.service",
+                "NetworkManager-wait-online.service",
+                "systemd-journald.service",
+                "sys-kernel-config.mount"
+            ]
+        );
+        assert_eq!(blame[0].1, Duration::from_millis(62_345));
+        assert_eq!(blame[3].1, Duration::from_micros(12));
+
+        let error = analyze_blame_with(&MockRunner::default()).unwrap_err();
+        assert_eq!(error, "systemd-analyze is not installed");
+    }
+
+    #[test]
+    fn recent_errors_skips_blank_lines() {
+        let runner = MockRunner::default().respond(0, "first error\n\n  second error\n", "");
+
+        assert_eq!(
+            recent_errors_with(&runner, "a", 3),
+            Ok(vec!["first error".to_string(), "second error".to_string()])
+        );
+        assert_eq!(
+            runner.calls()[0],
+            ["journalctl", "--no-pager", "--output=cat", "--priority=err", "--lines=3", "--unit=a.service"]
+        );
+    }
+
+    #[test]
+    fn start_service_succeeds() {
+        let runner = MockRunner::default().respond(0, "", "");
+
+        let result = start_service_with(
+            &runner,
+            "nginx.service",
+            &ExtraArgs::default(),
+            ServiceScope::System,
+        );
+        assert_eq!(result, Ok(None));
+        assert_eq!(runner.calls(), [["systemctl", "start", "nginx.service"]]);
+    }
+
+    #[test]
+    fn start_service_passes_on_warnings() {
+        let runner = MockRunner::default().respond(
+            0,
+            "",
+            "Warning: The unit file of nginx.service changed on disk.\n",
+        );
+
+        assert_eq!(
+            start_service_with(
+                &runner,
+                "nginx.service",
+                &ExtraArgs::default(),
+                ServiceScope::System,
+            ),
+            Ok(Some("The unit file of nginx.service changed on disk.".to_string()))
+        );
+    }
+
+    #[test]
+    fn start_service_fails() {
+        let runner = MockRunner::default().respond(
+            1,
+            "",
+            "Job for nginx.service failed because the control process exited with error code.",
+        );
+
+        let error = start_service_with(
+            &runner,
+            "nginx.service",
+            &ExtraArgs::default(),
+            ServiceScope::System,
+        )
+        .unwrap_err();
+        assert!(error.starts_with("Failed to start service: Job for"), "{}", error);
+    }
+
+    #[test]
+    fn start_service_treats_canceled_job_as_warning() {
+        let runner = MockRunner::default().respond(1, "", "Job for nginx.service canceled.");
+
+        let result = start_service_with(
+            &runner,
+            "nginx.service",
+            &ExtraArgs::default(),
+            ServiceScope::System,
+        );
+        assert!(matches!(result, Ok(Some(_))));
+    }
+
+    #[test]
+    fn stop_and_disable_runs_both_steps() {
+        let runner = MockRunner::default()
+            .respond(0, "", "")
+            .respond(0, "", "Removed \"/etc/systemd/system/multi-user.target.wants/a.service\".\n");
+
+        assert_eq!(stop_and_disable_service_with(&runner, "a.service", &ExtraArgs::default()), Ok(None));
+        assert_eq!(
+            runner.calls(),
+            [["systemctl", "stop", "a.service"], ["systemctl", "disable", "a.service"]]
+        );
+    }
+
+    #[test]
+    fn stop_and_disable_keeps_unit_enabled_when_stop_fails() {
+        let runner = MockRunner::default().respond(1, "", "Access denied");
+
+        let error = stop_and_disable_service_with(&runner, "a.service", &ExtraArgs::default()).unwrap_err();
+        assert!(error.starts_with("Failed to stop service"), "{}", error);
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[test]
+    fn enable_and_start_reports_failed_start() {
+        let runner = MockRunner::default()
+            .respond(0, "", "")
+            .respond(1, "", "Job for a.service failed.");
+
+        let error = enable_and_start_service_with(&runner, "a.service", &ExtraArgs::default()).unwrap_err();
+        assert!(error.starts_with("Enabled a.service, but failed to start it"), "{}", error);
+        assert_eq!(runner.calls()[0], ["systemctl", "enable", "a.service"]);
+    }
+
+    #[test]
+    fn preview_parses_dry_run_symlinks() {
+        let runner = MockRunner::default().respond(
+            0,
+            "",
+            "Created symlink '/etc/systemd/system/multi-user.target.wants/a.service' \
+             → '/usr/lib/systemd/system/a.service'.\n\
+             Removed \"/etc/systemd/system/default.target.wants/a.service\".\n",
+        );
+
+        let changes = preview_unit_file_change_with(&runner, "enable", "a.service", &ExtraArgs::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            changes,
+            [
+                SymlinkChange::Created {
+                    link: "/etc/systemd/system/multi-user.target.wants/a.service".to_string(),
+                    target: "/usr/lib/systemd/system/a.service".to_string(),
+                },
+                SymlinkChange::Removed("/etc/systemd/system/default.target.wants/a.service".to_string()),
+            ]
+        );
+        assert_eq!(runner.calls(), [["systemctl", "enable", "--dry-run", "a.service"]]);
+
+        let runner = MockRunner::default().respond(1, "", "systemctl: unrecognized option '--dry-run'");
+        assert_eq!(
+            preview_unit_file_change_with(&runner, "disable", "a.service", &ExtraArgs::default()),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn restart_and_wait_polls_until_active_or_failed() {
+        let runner = MockRunner::default()
+            .respond(0, "", "")
+            .respond(0, "activating\n", "")
+            .respond(0, "active\n", "");
+        let wait = |runner: &MockRunner, timeout| {
+            restart_and_wait_with(runner, "a.service", &ExtraArgs::default(), timeout, Duration::ZERO)
+        };
+
+        assert_eq!(wait(&runner, Duration::from_secs(5)), Ok(None));
+        assert_eq!(
+            runner.calls()[2],
+            ["systemctl", "show", "--property=ActiveState", "--value", "a.service"]
+        );
+
+        let runner = MockRunner::default().respond(0, "", "").respond(0, "failed\n", "");
+        let error = wait(&runner, Duration::from_secs(5)).unwrap_err();
+        assert_eq!(error, "a.service failed after the restart");
+
+        let runner = MockRunner::default().respond(0, "", "").respond(0, "activating\n", "");
+        let error = wait(&runner, Duration::ZERO).unwrap_err();
+        assert!(error.starts_with("a.service did not come up within 0s"), "{}", error);
+    }
+
+    #[test]
+    fn set_property_validates_before_running() {
+        let valid = [("MemoryMax", "512M"), ("MemoryMax", "infinity"), ("CPUQuota", "12.5%"), ("TasksMax", "64")];
+        for (property, value) in valid {
+            assert_eq!(validate_limit(property, value), Ok(()), "{}={}", property, value);
+        }
+        let invalid = [("MemoryMax", "lots"), ("CPUQuota", "50"), ("TasksMax", "10K"), ("Nice", "5")];
+        for (property, value) in invalid {
+            assert!(validate_limit(property, value).is_err(), "{}={}", property, value);
+        }
+
+        let runner = MockRunner::default();
+        assert!(set_property_with(&runner, "a.service", "CPUQuota", "half", true).is_err());
+        assert!(runner.calls().is_empty());
+
+        let runner = MockRunner::default().respond(1, "", "Unknown assignment: CPUQuota=900000%");
+        let error = set_property_with(&runner, "a", "CPUQuota", "900000%", true).unwrap_err();
+        assert!(error.starts_with("Failed to set CPUQuota"), "{}", error);
+        assert_eq!(
+            runner.calls(),
+            [["systemctl", "set-property", "--runtime", "a.service", "CPUQuota=900000%"]]
+        );
+    }
+
+    #[test]
+    fn reload_and_restart_reports_failed_restart() {
+        let runner = MockRunner::default()
+            .respond(0, "", "")
+            .respond(1, "", "Job for a.service failed.");
+
+        let error = reload_and_restart_service_with(&runner, "a.service", &ExtraArgs::default()).unwrap_err();
+        assert!(error.starts_with("Reloaded systemd, but failed to restart a.service"), "{}", error);
+        assert_eq!(
+            runner.calls(),
+            [vec!["systemctl", "daemon-reload"], vec!["systemctl", "restart", "a.service"]]
+        );
+    }
+
+    #[test]
+    fn stop_service_treats_unloaded_unit_as_warning() {
+        let runner = MockRunner::default().respond(
+            5,
+            "",
+            "Failed to stop gone.service: Unit gone.service not loaded.",
+        );
+
+        let result = stop_service_with(
+            &runner,
+            "gone.service",
+            &ExtraArgs::default(),
+            ServiceScope::System,
+        );
+        assert!(matches!(result, Ok(Some(_))));
+        assert_eq!(runner.calls(), [["systemctl", "stop", "gone.service"]]);
+    }
+
+    #[test]
+    fn stop_service_fails_on_access_denied() {
+        let runner = MockRunner::default().respond(
+            4,
+            "",
+            "Failed to stop nginx.service: Access denied",
+        );
+
+        let error = stop_service_with(
+            &runner,
+            "nginx.service",
+            &ExtraArgs::default(),
+            ServiceScope::System,
+        )
+        .unwrap_err();
+        assert!(error.contains("Access denied"), "{}", error);
+    }
 }
@@ -0,0 +1,54 @@
+//! Watches a unit's fragment file for edits, for the "Watch unit file" toggle
+//! in the detail panel, which then offers a daemon-reload and restart.
+
+use std::path::{Path, PathBuf};
+
+use iced::futures::SinkExt;
+use iced::{subscription, Subscription};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+/// Yields `Ok(())` each time `path` is written or replaced, for the lifetime
+/// of the subscription. A watch that can't be set up is reported once as an
+/// `Err`.
+pub fn changes(path: PathBuf) -> Subscription<Result<(), String>> {
+    subscription::channel(path.clone(), 16, move |mut output| async move {
+        let (sender, mut events) = mpsc::unbounded_channel();
+        let file_name = path.file_name().map(|name| name.to_owned());
+        let handler = move |event: notify::Result<Event>| {
+            let Ok(event) = event else { return };
+            let edited = match event.kind {
+                EventKind::Modify(ModifyKind::Metadata(_)) => false,
+                EventKind::Create(_) | EventKind::Modify(_) => true,
+                _ => false,
+            };
+            if edited && event.paths.iter().any(|p| p.file_name() == file_name.as_deref()) {
+                let _ = sender.send(());
+            }
+        };
+
+        // Editors usually save by renaming a new file over the old one, which
+        // ends a watch on the file itself, so watch its directory instead.
+        let directory = path.parent().unwrap_or(Path::new("/"));
+        let watcher = notify::recommended_watcher(handler).and_then(|mut watcher| {
+            watcher.watch(directory, RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+
+        match watcher {
+            Ok(_watcher) => {
+                while events.recv().await.is_some() {
+                    let _ = output.send(Ok(())).await;
+                }
+                std::future::pending().await
+            }
+            Err(e) => {
+                let _ = output
+                    .send(Err(format!("Failed to watch {}: {}", path.display(), e)))
+                    .await;
+                std::future::pending().await
+            }
+        }
+    })
+}